@@ -0,0 +1,30 @@
+//! Lists the documents shipped in the asset folder, for the web build, which
+//! can't list folders at runtime.
+
+use std::{env, fs, path::Path};
+
+/// Asset folders the app lists, relative to `assets/`.
+const LISTED_DIRS: &[&str] = &["behaviors", "market_rates", "mission", "scenarios"];
+
+fn main() {
+    let mut files = vec![];
+    for dir in LISTED_DIRS {
+        let path = Path::new("assets").join(dir);
+        println!("cargo:rerun-if-changed={}", path.display());
+        let Ok(entries) = fs::read_dir(&path) else {
+            continue;
+        };
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            if entry.path().is_file() {
+                files.push(format!("{}/{}", dir, entry.file_name().to_string_lossy()));
+            }
+        }
+    }
+    files.sort();
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("asset_files.rs");
+    fs::write(
+        out,
+        format!("pub const ASSET_FILES: &[&str] = &{:?};\n", files),
+    )
+    .unwrap();
+}
@@ -0,0 +1,329 @@
+use std::collections::BTreeMap;
+
+use serde::de::DeserializeOwned;
+use sugarfunge_api_types::primitives::{Account, AssetId, Balance, ClassId, MarketId, Seed};
+use sugarfunge_client::units::whole_tokens;
+
+/// Invalid command line, reported with exit code 2.
+#[derive(Debug)]
+pub struct UsageError(pub String);
+
+impl std::fmt::Display for UsageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+pub struct Args {
+    pub url: Option<String>,
+    pub node_url: Option<String>,
+    pub group: String,
+    pub command: String,
+    pub flags: Flags,
+}
+
+impl Args {
+    pub fn parse(args: impl Iterator<Item = String>) -> Result<Self, UsageError> {
+        let mut positional = vec![];
+        let mut flags = BTreeMap::new();
+        let mut args = args.peekable();
+        while let Some(arg) = args.next() {
+            match arg.strip_prefix("--") {
+                Some(name) => {
+                    let value = match args.peek() {
+                        Some(value) if !value.starts_with("--") => args.next().unwrap(),
+                        _ => return Err(UsageError(format!("Missing value for --{}", name))),
+                    };
+                    if flags.insert(name.to_string(), value).is_some() {
+                        return Err(UsageError(format!("--{} given more than once", name)));
+                    }
+                }
+                None => positional.push(arg),
+            }
+        }
+        let mut positional = positional.into_iter();
+        let (Some(group), Some(command), None) =
+            (positional.next(), positional.next(), positional.next())
+        else {
+            return Err(UsageError("Expected <group> <command>".to_string()));
+        };
+        let url = flags.remove("url");
+        let node_url = flags.remove("node-url");
+        Ok(Self {
+            url,
+            node_url,
+            group,
+            command,
+            flags: Flags(flags),
+        })
+    }
+}
+
+/// `--name value` options of a command. Each getter removes its option so
+/// `finish` can reject the ones the command doesn't know.
+pub struct Flags(BTreeMap<String, String>);
+
+impl Flags {
+    fn take(&mut self, name: &str) -> Option<String> {
+        self.0.remove(name)
+    }
+
+    pub fn string(&mut self, name: &str) -> Result<String, UsageError> {
+        self.take(name)
+            .ok_or_else(|| UsageError(format!("Missing --{}", name)))
+    }
+
+    pub fn seed(&mut self) -> Result<Seed, UsageError> {
+        self.string("seed").map(Seed::from)
+    }
+
+    pub fn account(&mut self, name: &str) -> Result<Account, UsageError> {
+        self.string(name).map(Account::from)
+    }
+
+    pub fn u64(&mut self, name: &str) -> Result<u64, UsageError> {
+        parse_u64(name, &self.string(name)?)
+    }
+
+    pub fn optional_u64(&mut self, name: &str) -> Result<Option<u64>, UsageError> {
+        self.take(name)
+            .map(|value| parse_u64(name, &value))
+            .transpose()
+    }
+
+    pub fn class_id(&mut self) -> Result<ClassId, UsageError> {
+        self.u64("class-id").map(ClassId::from)
+    }
+
+    pub fn asset_id(&mut self) -> Result<AssetId, UsageError> {
+        self.u64("asset-id").map(AssetId::from)
+    }
+
+    pub fn market_id(&mut self, name: &str) -> Result<MarketId, UsageError> {
+        self.u64(name).map(MarketId::from)
+    }
+
+    /// Same units as the debug UI: a plain integer amount.
+    pub fn amount(&mut self) -> Result<Balance, UsageError> {
+        self.u64("amount")
+            .map(|amount| Balance::from(amount as u128))
+    }
+
+    /// Whole tokens, scaled like the debug UI fund form.
+    pub fn whole_amount(&mut self, name: &str) -> Result<Balance, UsageError> {
+        self.u64(name).map(whole_tokens)
+    }
+
+    /// Comma separated, e.g. `1,2,3`.
+    pub fn u64_list(&mut self, name: &str) -> Result<Vec<u64>, UsageError> {
+        split_list(&self.string(name)?, ',')
+            .map(|value| parse_u64(name, value))
+            .collect()
+    }
+
+    /// Semicolon separated lists, e.g. `1,2;3` for `[[1, 2], [3]]`.
+    pub fn u64_lists(&mut self, name: &str) -> Result<Vec<Vec<u64>>, UsageError> {
+        split_list(&self.string(name)?, ';')
+            .map(|list| {
+                split_list(list, ',')
+                    .map(|value| parse_u64(name, value))
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn string_list(&mut self, name: &str) -> Result<Vec<String>, UsageError> {
+        Ok(split_list(&self.string(name)?, ',')
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Inline JSON, defaulting to `{}` like the debug UI metadata fields.
+    pub fn metadata(&mut self) -> Result<serde_json::Value, UsageError> {
+        match self.take("metadata") {
+            Some(metadata) => serde_json::from_str(&metadata)
+                .map_err(|err| UsageError(format!("Invalid --metadata: {}", err))),
+            None => Ok(serde_json::json!({})),
+        }
+    }
+
+    /// Inline JSON, or a `.json`/`.ron` file such as a market rates template
+    /// when the value starts with `@`.
+    pub fn document<T: DeserializeOwned>(&mut self, name: &str) -> Result<T, UsageError> {
+        let value = self.string(name)?;
+        let invalid = |err: String| UsageError(format!("Invalid --{}: {}", name, err));
+        match value.strip_prefix('@') {
+            Some(path) => {
+                let contents =
+                    std::fs::read_to_string(path).map_err(|err| invalid(err.to_string()))?;
+                if path.ends_with(".ron") {
+                    ron::from_str(&contents).map_err(|err| invalid(err.to_string()))
+                } else {
+                    serde_json::from_str(&contents).map_err(|err| invalid(err.to_string()))
+                }
+            }
+            None => serde_json::from_str(&value).map_err(|err| invalid(err.to_string())),
+        }
+    }
+
+    pub fn finish(&self) -> Result<(), UsageError> {
+        match self.0.keys().next() {
+            Some(name) => Err(UsageError(format!("Unknown option --{}", name))),
+            None => Ok(()),
+        }
+    }
+}
+
+fn parse_u64(name: &str, value: &str) -> Result<u64, UsageError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| UsageError(format!("--{} expects a number, got {:?}", name, value)))
+}
+
+fn split_list(value: &str, separator: char) -> impl Iterator<Item = &str> {
+    value
+        .split(separator)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, UsageError> {
+        Args::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn usage_error(args: &[&str]) -> String {
+        match parse(args) {
+            Ok(_) => panic!("{:?} parsed", args),
+            Err(err) => err.0,
+        }
+    }
+
+    fn flags(args: &[&str]) -> Flags {
+        match parse(args) {
+            Ok(args) => args.flags,
+            Err(err) => panic!("{:?}: {}", args, err),
+        }
+    }
+
+    #[test]
+    fn parses_command_and_flags() {
+        let args = parse(&[
+            "asset",
+            "mint",
+            "--url",
+            "http://node",
+            "--node-url",
+            "ws://node",
+            "--amount",
+            "5",
+        ])
+        .unwrap();
+        assert_eq!(args.group, "asset");
+        assert_eq!(args.command, "mint");
+        assert_eq!(args.url.as_deref(), Some("http://node"));
+        assert_eq!(args.node_url.as_deref(), Some("ws://node"));
+        let mut flags = args.flags;
+        assert_eq!(u128::from(flags.amount().unwrap()), 5);
+        assert!(flags.finish().is_ok());
+    }
+
+    #[test]
+    fn rejects_bad_command_lines() {
+        assert_eq!(usage_error(&["asset"]), "Expected <group> <command>");
+        assert_eq!(
+            usage_error(&["asset", "mint", "extra"]),
+            "Expected <group> <command>"
+        );
+        assert_eq!(
+            usage_error(&["asset", "mint", "--seed", "//Alice", "--seed", "//Bob"]),
+            "--seed given more than once"
+        );
+        assert_eq!(
+            usage_error(&["asset", "mint", "--seed", "--to", "A"]),
+            "Missing value for --seed"
+        );
+        assert_eq!(
+            usage_error(&["asset", "mint", "--seed"]),
+            "Missing value for --seed"
+        );
+    }
+
+    #[test]
+    fn finish_rejects_unknown_flags() {
+        let mut flags = flags(&["asset", "mint", "--seed", "//Alice", "--colour", "red"]);
+        flags.seed().unwrap();
+        assert_eq!(flags.finish().unwrap_err().0, "Unknown option --colour");
+    }
+
+    #[test]
+    fn parses_number_lists() {
+        let mut flags = flags(&[
+            "bag",
+            "deposit",
+            "--ids",
+            "1, 2,",
+            "--amounts",
+            "1,2; 3",
+            "--bad",
+            "1;x",
+        ]);
+        assert_eq!(flags.u64_list("ids").unwrap(), vec![1, 2]);
+        assert_eq!(
+            flags.u64_lists("amounts").unwrap(),
+            vec![vec![1, 2], vec![3]]
+        );
+        assert_eq!(
+            flags.u64_lists("bad").unwrap_err().0,
+            "--bad expects a number, got \"x\""
+        );
+        assert_eq!(flags.u64_list("ids").unwrap_err().0, "Missing --ids");
+    }
+
+    #[test]
+    fn whole_amount_is_scaled() {
+        let mut flags = flags(&["account", "fund", "--amount", "2"]);
+        assert_eq!(
+            u128::from(flags.whole_amount("amount").unwrap()),
+            2_000_000_000_000_000_000
+        );
+    }
+
+    #[test]
+    fn reads_documents() {
+        let dir = std::env::temp_dir().join(format!("sf-cli-args-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let json = dir.join("ids.json");
+        let ron = dir.join("ids.ron");
+        std::fs::write(&json, "[1, 2]").unwrap();
+        std::fs::write(&ron, "[3, 4]").unwrap();
+        let json = format!("@{}", json.display());
+        let ron = format!("@{}", ron.display());
+        let missing = format!("@{}", dir.join("missing.json").display());
+        let mut flags = flags(&[
+            "market",
+            "create-rate",
+            "--inline",
+            "[5]",
+            "--json",
+            &json,
+            "--ron",
+            &ron,
+            "--missing",
+            &missing,
+        ]);
+        assert_eq!(flags.document::<Vec<u64>>("inline").unwrap(), vec![5]);
+        assert_eq!(flags.document::<Vec<u64>>("json").unwrap(), vec![1, 2]);
+        assert_eq!(flags.document::<Vec<u64>>("ron").unwrap(), vec![3, 4]);
+        assert!(flags
+            .document::<Vec<u64>>("missing")
+            .unwrap_err()
+            .0
+            .starts_with("Invalid --missing: "));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,449 @@
+//! Runs SugarFunge API operations from the command line, mirroring the debug
+//! UI tabs. Prints the JSON output on success.
+//!
+//! Exit codes: 0 success, 1 rejected by the API or refused by the client,
+//! 2 invalid command line, 3 API unreachable, 4 unexpected response,
+//! 5 scenario failed.
+
+use std::process::ExitCode;
+
+use serde::Serialize;
+use sugarfunge_api_types::{
+    account::*,
+    asset::*,
+    bag,
+    bundle::*,
+    market::*,
+    primitives::{AssetId, Balance, BundleId, ClassId, ValidatorId},
+    validator::*,
+};
+use sugarfunge_client::{
+    scenario::Scenario, units::whole_tokens, Error, SugarFungeClient, DEFAULT_NODE_URL, DEFAULT_URL,
+};
+
+mod args;
+
+use args::{Args, Flags, UsageError};
+
+const USAGE: &str = "\
+Usage: sf-cli <group> <command> [--option value]... [--url URL] [--node-url URL]
+
+account create
+account fund --seed S --to ACCOUNT --amount N
+account exists --account ACCOUNT
+account seeded --seed S
+account balance --account ACCOUNT
+class create --seed S --owner ACCOUNT --class-id N [--metadata JSON]
+class info --class-id N
+asset create --seed S --class-id N --asset-id N [--metadata JSON]
+asset info --class-id N --asset-id N
+asset update-metadata --seed S --class-id N --asset-id N --metadata JSON
+asset mint --seed S --to ACCOUNT --class-id N --asset-id N --amount N
+asset burn --seed S --from ACCOUNT --class-id N --asset-id N --amount N
+asset balance --account ACCOUNT --class-id N --asset-id N
+asset balances --account ACCOUNT [--class-id N]
+asset transfer-from --seed S --from ACCOUNT --to ACCOUNT --class-id N --asset-id N --amount N
+bag register --seed S --class-id N [--metadata JSON]
+bag create --seed S --class-id N --owners A,B --shares N,N
+bag sweep --seed S --bag ACCOUNT --to ACCOUNT
+bag deposit --seed S --bag ACCOUNT --class-ids N,N --asset-ids N,N;N --amounts N,N;N
+bundle register --seed S --class-id N --asset-id N --schema JSON|@FILE [--metadata JSON]
+bundle mint --seed S --from ACCOUNT --to ACCOUNT --bundle-id ID --amount N
+bundle burn --seed S --from ACCOUNT --to ACCOUNT --bundle-id ID --amount N
+market create --seed S --market-id N
+market create-rate --seed S --market-id N --market-rate-id N --rates JSON|@FILE
+market deposit --seed S --market-id N --market-rate-id N --amount N
+market exchange --seed S --market-id N --market-rate-id N --amount N
+validator add --seed S --validator-id ID
+validator remove --seed S --validator-id ID
+validator list
+scenario run --file PATH.scenario.ron
+
+Funding amounts and bag shares are in 10^18 units, like the debug UI.
+--url defaults to http://127.0.0.1:4000. --node-url, the node the validator
+set is read from, defaults to ws://127.0.0.1:9944. Rates files use the
+assets/market_rates template format.
+
+Exit codes: 0 success, 1 rejected by the API or refused by the client
+(e.g. removing the last validator), 2 invalid command line, 3 API or
+node unreachable, 4 unexpected response, 5 scenario failed.";
+
+enum Failure {
+    Usage(UsageError),
+    Client(Error),
+    /// Holds the report, printed like a successful output.
+    ScenarioFailed(String),
+}
+
+impl From<UsageError> for Failure {
+    fn from(err: UsageError) -> Self {
+        Failure::Usage(err)
+    }
+}
+
+impl From<Error> for Failure {
+    fn from(err: Error) -> Self {
+        Failure::Client(err)
+    }
+}
+
+fn output<T: Serialize>(output: T) -> Result<String, Failure> {
+    Ok(serde_json::to_string_pretty(&output).expect("API outputs serialize to JSON"))
+}
+
+async fn run(
+    client: &SugarFungeClient,
+    group: &str,
+    command: &str,
+    mut flags: Flags,
+) -> Result<String, Failure> {
+    match (group, command) {
+        ("account", "create") => {
+            flags.finish()?;
+            output(client.create_account().await?)
+        }
+        ("account", "fund") => {
+            let input = FundAccountInput {
+                seed: flags.seed()?,
+                to: flags.account("to")?,
+                amount: flags.whole_amount("amount")?,
+            };
+            flags.finish()?;
+            output(client.fund_account(&input).await?)
+        }
+        ("account", "exists") => {
+            let input = AccountExistsInput {
+                account: flags.account("account")?,
+            };
+            flags.finish()?;
+            output(client.account_exists(&input).await?)
+        }
+        ("account", "seeded") => {
+            let input = SeededAccountInput {
+                seed: flags.seed()?,
+            };
+            flags.finish()?;
+            output(client.seeded_account(&input).await?)
+        }
+        ("account", "balance") => {
+            let input = AccountBalanceInput {
+                account: flags.account("account")?,
+            };
+            flags.finish()?;
+            output(client.account_balance(&input).await?)
+        }
+        ("class", "create") => {
+            let input = CreateClassInput {
+                seed: flags.seed()?,
+                owner: flags.account("owner")?,
+                class_id: flags.class_id()?,
+                metadata: flags.metadata()?,
+            };
+            flags.finish()?;
+            output(client.create_class(&input).await?)
+        }
+        ("class", "info") => {
+            let input = ClassInfoInput {
+                class_id: flags.class_id()?,
+            };
+            flags.finish()?;
+            output(client.class_info(&input).await?)
+        }
+        ("asset", "create") => {
+            let input = CreateInput {
+                seed: flags.seed()?,
+                class_id: flags.class_id()?,
+                asset_id: flags.asset_id()?,
+                metadata: flags.metadata()?,
+            };
+            flags.finish()?;
+            output(client.asset_create(&input).await?)
+        }
+        ("asset", "info") => {
+            let input = AssetInfoInput {
+                class_id: flags.class_id()?,
+                asset_id: flags.asset_id()?,
+            };
+            flags.finish()?;
+            output(client.asset_info(&input).await?)
+        }
+        ("asset", "update-metadata") => {
+            let input = UpdateMetadataInput {
+                seed: flags.seed()?,
+                class_id: flags.class_id()?,
+                asset_id: flags.asset_id()?,
+                metadata: flags.document("metadata")?,
+            };
+            flags.finish()?;
+            output(client.asset_update_metadata(&input).await?)
+        }
+        ("asset", "mint") => {
+            let input = MintInput {
+                seed: flags.seed()?,
+                to: flags.account("to")?,
+                class_id: flags.class_id()?,
+                asset_id: flags.asset_id()?,
+                amount: flags.amount()?,
+            };
+            flags.finish()?;
+            output(client.asset_mint(&input).await?)
+        }
+        ("asset", "burn") => {
+            let input = BurnInput {
+                seed: flags.seed()?,
+                from: flags.account("from")?,
+                class_id: flags.class_id()?,
+                asset_id: flags.asset_id()?,
+                amount: flags.amount()?,
+            };
+            flags.finish()?;
+            output(client.asset_burn(&input).await?)
+        }
+        ("asset", "balance") => {
+            let input = AssetBalanceInput {
+                account: flags.account("account")?,
+                class_id: flags.class_id()?,
+                asset_id: flags.asset_id()?,
+            };
+            flags.finish()?;
+            output(client.asset_balance(&input).await?)
+        }
+        ("asset", "balances") => {
+            let input = AssetBalancesInput {
+                account: flags.account("account")?,
+                class_id: flags.optional_u64("class-id")?.map(ClassId::from),
+            };
+            flags.finish()?;
+            output(client.asset_balances(&input).await?)
+        }
+        ("asset", "transfer-from") => {
+            let input = TransferFromInput {
+                seed: flags.seed()?,
+                from: flags.account("from")?,
+                to: flags.account("to")?,
+                class_id: flags.class_id()?,
+                asset_id: flags.asset_id()?,
+                amount: flags.amount()?,
+            };
+            flags.finish()?;
+            output(client.asset_transfer_from(&input).await?)
+        }
+        ("bag", "register") => {
+            let input = bag::RegisterInput {
+                seed: flags.seed()?,
+                class_id: flags.class_id()?,
+                metadata: flags.metadata()?,
+            };
+            flags.finish()?;
+            output(client.bag_register(&input).await?)
+        }
+        ("bag", "create") => {
+            let input = bag::CreateInput {
+                seed: flags.seed()?,
+                class_id: flags.class_id()?,
+                owners: flags
+                    .string_list("owners")?
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+                shares: flags
+                    .u64_list("shares")?
+                    .into_iter()
+                    .map(whole_tokens)
+                    .collect(),
+            };
+            flags.finish()?;
+            output(client.bag_create(&input).await?)
+        }
+        ("bag", "sweep") => {
+            let input = bag::SweepInput {
+                seed: flags.seed()?,
+                bag: flags.account("bag")?,
+                to: flags.account("to")?,
+            };
+            flags.finish()?;
+            output(client.bag_sweep(&input).await?)
+        }
+        ("bag", "deposit") => {
+            let input = bag::DepositInput {
+                seed: flags.seed()?,
+                bag: flags.account("bag")?,
+                class_ids: flags
+                    .u64_list("class-ids")?
+                    .into_iter()
+                    .map(ClassId::from)
+                    .collect(),
+                asset_ids: flags
+                    .u64_lists("asset-ids")?
+                    .into_iter()
+                    .map(|ids| ids.into_iter().map(AssetId::from).collect())
+                    .collect(),
+                amounts: flags
+                    .u64_lists("amounts")?
+                    .into_iter()
+                    .map(|amounts| {
+                        amounts
+                            .into_iter()
+                            .map(|amount| Balance::from(amount as u128))
+                            .collect()
+                    })
+                    .collect(),
+            };
+            flags.finish()?;
+            output(client.bag_deposit(&input).await?)
+        }
+        ("bundle", "register") => {
+            let input = RegisterBundleInput {
+                seed: flags.seed()?,
+                class_id: flags.class_id()?,
+                asset_id: flags.asset_id()?,
+                schema: flags.document("schema")?,
+                metadata: flags.metadata()?,
+            };
+            flags.finish()?;
+            output(client.bundle_register(&input).await?)
+        }
+        ("bundle", "mint") => {
+            let input = MintBundleInput {
+                seed: flags.seed()?,
+                from: flags.account("from")?,
+                to: flags.account("to")?,
+                bundle_id: BundleId::from(flags.string("bundle-id")?),
+                amount: flags.amount()?,
+            };
+            flags.finish()?;
+            output(client.bundle_mint(&input).await?)
+        }
+        ("bundle", "burn") => {
+            let input = BurnBundleInput {
+                seed: flags.seed()?,
+                from: flags.account("from")?,
+                to: flags.account("to")?,
+                bundle_id: BundleId::from(flags.string("bundle-id")?),
+                amount: flags.amount()?,
+            };
+            flags.finish()?;
+            output(client.bundle_burn(&input).await?)
+        }
+        ("market", "create") => {
+            let input = CreateMarketInput {
+                seed: flags.seed()?,
+                market_id: flags.market_id("market-id")?,
+            };
+            flags.finish()?;
+            output(client.create_market(&input).await?)
+        }
+        ("market", "create-rate") => {
+            let input = CreateMarketRateInput {
+                seed: flags.seed()?,
+                market_id: flags.market_id("market-id")?,
+                market_rate_id: flags.market_id("market-rate-id")?,
+                rates: flags.document("rates")?,
+            };
+            flags.finish()?;
+            output(client.create_market_rate(&input).await?)
+        }
+        ("market", "deposit") => {
+            let input = DepositAssetsInput {
+                seed: flags.seed()?,
+                market_id: flags.market_id("market-id")?,
+                market_rate_id: flags.market_id("market-rate-id")?,
+                amount: flags.amount()?,
+            };
+            flags.finish()?;
+            output(client.deposit_assets(&input).await?)
+        }
+        ("market", "exchange") => {
+            let input = ExchangeAssetsInput {
+                seed: flags.seed()?,
+                market_id: flags.market_id("market-id")?,
+                market_rate_id: flags.market_id("market-rate-id")?,
+                amount: flags.amount()?,
+            };
+            flags.finish()?;
+            output(client.exchange_assets(&input).await?)
+        }
+        ("validator", "add") => {
+            let input = AddValidatorInput {
+                seed: flags.seed()?,
+                validator_id: ValidatorId::from(flags.string("validator-id")?),
+            };
+            flags.finish()?;
+            output(client.add_validator(&input).await?)
+        }
+        ("validator", "remove") => {
+            let input = RemoveValidatorInput {
+                seed: flags.seed()?,
+                validator_id: ValidatorId::from(flags.string("validator-id")?),
+            };
+            flags.finish()?;
+            output(client.remove_validator(&input).await?)
+        }
+        ("validator", "list") => {
+            flags.finish()?;
+            output(client.validators().await?)
+        }
+        ("scenario", "run") => {
+            let scenario = Scenario::load(&flags.string("file")?).map_err(UsageError)?;
+            flags.finish()?;
+            let report = client.run_scenario(&scenario).await;
+            let passed = report.passed;
+            let report = output(report)?;
+            if passed {
+                Ok(report)
+            } else {
+                Err(Failure::ScenarioFailed(report))
+            }
+        }
+        _ => Err(Failure::Usage(UsageError(format!(
+            "Unknown command: {} {}",
+            group, command
+        )))),
+    }
+}
+
+fn main() -> ExitCode {
+    let mut raw_args = std::env::args().skip(1).peekable();
+    if matches!(
+        raw_args.peek().map(String::as_str),
+        None | Some("help" | "--help" | "-h")
+    ) {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    let args = match Args::parse(raw_args) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    let client = SugarFungeClient::new(args.url.as_deref().unwrap_or(DEFAULT_URL))
+        .with_node_url(args.node_url.as_deref().unwrap_or(DEFAULT_NODE_URL));
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("Failed to start the tokio runtime");
+    match runtime.block_on(run(&client, &args.group, &args.command, args.flags)) {
+        Ok(output) => {
+            println!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(Failure::Usage(err)) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            ExitCode::from(2)
+        }
+        Err(Failure::ScenarioFailed(report)) => {
+            println!("{}", report);
+            ExitCode::from(5)
+        }
+        Err(Failure::Client(err)) => {
+            eprintln!("{}", err);
+            ExitCode::from(match err {
+                Error::Api { .. } | Error::Refused(_) => 1,
+                Error::Http(_) | Error::Chain(_) => 3,
+                Error::Decode(_) => 4,
+            })
+        }
+    }
+}
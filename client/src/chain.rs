@@ -0,0 +1,54 @@
+//! Chain storage the API doesn't serve, read from the node over subxt.
+
+use subxt::{dynamic, utils::AccountId32, OnlineClient, PolkadotConfig};
+use sugarfunge_api_types::primitives::ValidatorId;
+
+use crate::{Error, Result};
+
+pub type Node = OnlineClient<PolkadotConfig>;
+
+fn chain_error(err: impl std::fmt::Display) -> Error {
+    Error::Chain(err.to_string())
+}
+
+pub async fn connect(node_url: &str) -> Result<Node> {
+    Node::from_url(node_url).await.map_err(chain_error)
+}
+
+/// Decodes the value stored at `pallet::entry`, or `None` if it's unset.
+async fn fetch<T: subxt::ext::scale_decode::DecodeAsType>(
+    node: &Node,
+    pallet: &str,
+    entry: &str,
+) -> Result<Option<T>> {
+    let storage = node.storage().at_latest().await.map_err(chain_error)?;
+    let value = storage
+        .fetch(&dynamic::storage_root(pallet, entry))
+        .await
+        .map_err(chain_error)?;
+    value
+        .map(|value| value.as_type::<T>().map_err(chain_error))
+        .transpose()
+}
+
+/// `Session::Validators`, as SS58 addresses.
+pub async fn validators(node: &Node) -> Result<Vec<ValidatorId>> {
+    let validators = fetch::<Vec<AccountId32>>(node, "Session", "Validators").await?;
+    Ok(validators
+        .unwrap_or_default()
+        .into_iter()
+        .map(|validator| ValidatorId::from(validator.to_string()))
+        .collect())
+}
+
+/// `Session::CurrentIndex`
+pub async fn session_index(node: &Node) -> Result<Option<u32>> {
+    fetch(node, "Session", "CurrentIndex").await
+}
+
+/// Index of `Staking::ActiveEra`, failing on runtimes without staking.
+pub async fn active_era(node: &Node) -> Result<Option<u32>> {
+    // `ActiveEraInfo { index, start }`
+    let era = fetch::<(u32, Option<u64>)>(node, "Staking", "ActiveEra").await?;
+    Ok(era.map(|(index, _start)| index))
+}
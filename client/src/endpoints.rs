@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+use sugarfunge_api_types::{
+    account::*, asset::*, bag, bundle::*, market::*, primitives::ValidatorId, validator::*,
+};
+
+use crate::{chain, Endpoint, Error, Pending, Result, SugarFungeClient};
+
+/// Current validator set, read from the node's `Session` storage. Session
+/// and era are optional since not every runtime exposes them.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ValidatorsOutput {
+    pub validators: Vec<ValidatorId>,
+    #[serde(default)]
+    pub session_index: Option<u32>,
+    #[serde(default)]
+    pub active_era: Option<u32>,
+}
+
+macro_rules! endpoint {
+    ($name:ident, $method:ident => $path:literal: $input:ty => $output:ty) => {
+        #[doc = concat!("`", $path, "`")]
+        pub struct $name;
+
+        impl Endpoint for $name {
+            type Input = $input;
+            type Output = $output;
+            const PATH: &'static str = $path;
+            const METHOD: &'static str = stringify!($method);
+        }
+    };
+}
+
+macro_rules! endpoints {
+    ($($name:ident, $method:ident => $path:literal: $input:ty => $output:ty;)*) => {
+        $(endpoint!($name, $method => $path: $input => $output);)*
+
+        impl SugarFungeClient {
+            $(
+                #[doc = concat!("`", $path, "`")]
+                pub async fn $method(&self, input: &$input) -> Result<$output> {
+                    self.call::<$name>(input).await
+                }
+            )*
+        }
+    };
+}
+
+endpoint!(CreateAccount, create_account => "account/create": () => CreateAccountOutput);
+
+/// The validator set. The API has no such route, so the client reads it from
+/// chain storage; the path only names it for the console and scenarios.
+pub struct Validators;
+
+impl Endpoint for Validators {
+    type Input = ();
+    type Output = ValidatorsOutput;
+    const PATH: &'static str = "validator/validators";
+    const METHOD: &'static str = "validators";
+
+    fn request<'a>(client: &'a SugarFungeClient, _input: &'a ()) -> Pending<'a, ValidatorsOutput> {
+        Box::pin(client.validators())
+    }
+}
+
+/// `validator/remove_validator`
+pub struct RemoveValidator;
+
+impl Endpoint for RemoveValidator {
+    type Input = RemoveValidatorInput;
+    type Output = RemoveValidatorOutput;
+    const PATH: &'static str = "validator/remove_validator";
+    const METHOD: &'static str = "remove_validator";
+
+    fn request<'a>(
+        client: &'a SugarFungeClient,
+        input: &'a RemoveValidatorInput,
+    ) -> Pending<'a, RemoveValidatorOutput> {
+        Box::pin(client.remove_validator(input))
+    }
+}
+
+impl SugarFungeClient {
+    /// `account/create`
+    pub async fn create_account(&self) -> Result<CreateAccountOutput> {
+        self.call::<CreateAccount>(&()).await
+    }
+
+    /// `Session::Validators`, with the session index and active era when the
+    /// runtime has them.
+    pub async fn validators(&self) -> Result<ValidatorsOutput> {
+        let node = chain::connect(self.node_url()).await?;
+        Ok(ValidatorsOutput {
+            validators: chain::validators(&node).await?,
+            session_index: chain::session_index(&node).await.ok().flatten(),
+            active_era: chain::active_era(&node).await.ok().flatten(),
+        })
+    }
+
+    /// `validator/remove_validator`, refused when it would remove the last
+    /// validator in the set and stop block production. When the set can't be
+    /// read the removal is sent anyway, for the API to judge.
+    pub async fn remove_validator(
+        &self,
+        input: &RemoveValidatorInput,
+    ) -> Result<RemoveValidatorOutput> {
+        if let Ok(set) = self.validators().await {
+            check_remove_validator(&set.validators, &input.validator_id)?;
+        }
+        self.post(RemoveValidator::PATH, input).await
+    }
+}
+
+/// Refuses removing `validator_id` when no other validator would remain.
+fn check_remove_validator(set: &[ValidatorId], validator_id: &ValidatorId) -> Result<()> {
+    if !set.is_empty() && set.iter().all(|validator| validator == validator_id) {
+        return Err(Error::Refused(format!(
+            "{} is the last validator in the set",
+            validator_id.as_str()
+        )));
+    }
+    Ok(())
+}
+
+endpoints! {
+    FundAccount, fund_account => "account/fund": FundAccountInput => FundAccountOutput;
+    AccountExists, account_exists => "account/exists": AccountExistsInput => AccountExistsOutput;
+    SeededAccount, seeded_account => "account/seeded": SeededAccountInput => SeededAccountOutput;
+    AccountBalance, account_balance => "account/balance": AccountBalanceInput => AccountBalanceOutput;
+
+    CreateClass, create_class => "asset/create_class": CreateClassInput => CreateClassOutput;
+    ClassInfo, class_info => "asset/class_info": ClassInfoInput => ClassInfoOutput;
+
+    CreateAsset, asset_create => "asset/create": CreateInput => CreateOutput;
+    AssetInfo, asset_info => "asset/info": AssetInfoInput => AssetInfoOutput;
+    UpdateAssetMetadata, asset_update_metadata
+        => "asset/update_metadata": UpdateMetadataInput => UpdateMetadataOutput;
+    MintAsset, asset_mint => "asset/mint": MintInput => MintOutput;
+    BurnAsset, asset_burn => "asset/burn": BurnInput => BurnOutput;
+    AssetBalance, asset_balance => "asset/balance": AssetBalanceInput => AssetBalanceOutput;
+    AssetBalances, asset_balances => "asset/balances": AssetBalancesInput => AssetBalancesOutput;
+    TransferFrom, asset_transfer_from
+        => "asset/transfer_from": TransferFromInput => TransferFromOutput;
+
+    RegisterBag, bag_register => "bag/register": bag::RegisterInput => bag::RegisterOutput;
+    CreateBag, bag_create => "bag/create": bag::CreateInput => bag::CreateOutput;
+    SweepBag, bag_sweep => "bag/sweep": bag::SweepInput => bag::SweepOutput;
+    DepositBag, bag_deposit => "bag/deposit": bag::DepositInput => bag::DepositOutput;
+
+    RegisterBundle, bundle_register
+        => "bundle/register": RegisterBundleInput => RegisterBundleOutput;
+    MintBundle, bundle_mint => "bundle/mint": MintBundleInput => MintBundleOutput;
+    BurnBundle, bundle_burn => "bundle/burn": BurnBundleInput => BurnBundleOutput;
+
+    CreateMarket, create_market => "market/create_market": CreateMarketInput => CreateMarketOutput;
+    CreateMarketRate, create_market_rate
+        => "market/create_market_rate": CreateMarketRateInput => CreateMarketRateOutput;
+    DepositAssets, deposit_assets
+        => "market/deposit_assets": DepositAssetsInput => DepositAssetsOutput;
+    ExchangeAssets, exchange_assets
+        => "market/exchange_assets": ExchangeAssetsInput => ExchangeAssetsOutput;
+
+    AddValidator, add_validator
+        => "validator/add_validator": AddValidatorInput => AddValidatorOutput;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(ids: &[&str]) -> Vec<ValidatorId> {
+        ids.iter()
+            .map(|id| ValidatorId::from(id.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn refuses_removing_the_last_validator() {
+        let alice = ValidatorId::from("alice".to_string());
+        assert!(matches!(
+            check_remove_validator(&ids(&["alice"]), &alice),
+            Err(Error::Refused(_))
+        ));
+        assert!(check_remove_validator(&ids(&["alice", "bob"]), &alice).is_ok());
+        assert!(check_remove_validator(&ids(&["bob"]), &alice).is_ok());
+        assert!(check_remove_validator(&[], &alice).is_ok());
+    }
+}
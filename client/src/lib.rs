@@ -0,0 +1,215 @@
+//! Typed async client for the SugarFunge API.
+//!
+//! Every endpoint is a marker type implementing [`Endpoint`], and
+//! [`SugarFungeClient`] has one method per endpoint, e.g.
+//! [`SugarFungeClient::asset_mint`].
+
+use std::{future::Future, pin::Pin};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+pub mod chain;
+pub mod endpoints;
+pub mod scenario;
+pub mod units;
+
+pub use endpoints::*;
+pub use sugarfunge_api_types as types;
+
+pub const DEFAULT_URL: &str = "http://127.0.0.1:4000";
+
+/// Websocket RPC of the node, for what the API doesn't serve, such as the
+/// validator set.
+pub const DEFAULT_NODE_URL: &str = "ws://127.0.0.1:9944";
+
+/// Response of a request that hasn't arrived yet.
+pub type Pending<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// One SugarFunge API operation.
+pub trait Endpoint: Send + Sync + 'static {
+    type Input: Serialize + Send + Sync + 'static;
+    type Output: Serialize + DeserializeOwned + Send + Sync + 'static;
+    const PATH: &'static str;
+    /// Name of the `SugarFungeClient` method calling the endpoint. It takes
+    /// `&Input`, except for endpoints whose input is `()`.
+    const METHOD: &'static str;
+
+    /// Posts `input` to the API. Endpoints with their own method, like the
+    /// validator set read from chain storage, send through it instead.
+    fn request<'a>(
+        client: &'a SugarFungeClient,
+        input: &'a Self::Input,
+    ) -> Pending<'a, Self::Output> {
+        Box::pin(client.post(Self::PATH, input))
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// The request never got an answer, e.g. the API isn't running.
+    Http(reqwest::Error),
+    /// The API rejected the request with an error status.
+    Api {
+        status: u16,
+        message: serde_json::Value,
+        description: String,
+    },
+    /// The response didn't match the endpoint's output type.
+    Decode(reqwest::Error),
+    /// The client didn't send the request, because the API would accept it but
+    /// it would break the chain.
+    Refused(String),
+    /// Reading chain storage from the node failed.
+    Chain(String),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Http(err) => write!(f, "Request failed: {}", err),
+            Error::Api {
+                status,
+                message,
+                description,
+            } => write!(f, "{} ({}): {}", description, status, message),
+            Error::Decode(err) => write!(f, "Invalid response: {}", err),
+            Error::Refused(reason) => write!(f, "Refused: {}", reason),
+            Error::Chain(err) => write!(f, "Chain query failed: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Http(err) | Error::Decode(err) => Some(err),
+            Error::Api { .. } | Error::Refused(_) | Error::Chain(_) => None,
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Error body returned by the API along with an error status.
+#[derive(Deserialize)]
+struct ApiError {
+    message: serde_json::Value,
+    description: String,
+}
+
+/// Turns an error response into `Error::Api`, keeping bodies that aren't the
+/// API's JSON error, such as a proxy's HTML page, as the raw text.
+fn api_error(status: reqwest::StatusCode, body: &str) -> Error {
+    match serde_json::from_str::<ApiError>(body) {
+        Ok(api_error) => Error::Api {
+            status: status.as_u16(),
+            message: api_error.message,
+            description: api_error.description,
+        },
+        Err(_) => Error::Api {
+            status: status.as_u16(),
+            message: serde_json::Value::String(body.to_string()),
+            description: status.canonical_reason().unwrap_or("Error").to_string(),
+        },
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SugarFungeClient {
+    http: reqwest::Client,
+    url: String,
+    node_url: String,
+}
+
+impl Default for SugarFungeClient {
+    fn default() -> Self {
+        Self::new(DEFAULT_URL)
+    }
+}
+
+impl SugarFungeClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            url: url.into().trim_end_matches('/').to_string(),
+            node_url: DEFAULT_NODE_URL.to_string(),
+        }
+    }
+
+    pub fn with_node_url(mut self, node_url: impl Into<String>) -> Self {
+        self.node_url = node_url.into();
+        self
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn node_url(&self) -> &str {
+        &self.node_url
+    }
+
+    pub async fn call<E: Endpoint>(&self, input: &E::Input) -> Result<E::Output> {
+        E::request(self, input).await
+    }
+
+    /// Posts `input` to `path`, for callers that want the raw JSON response.
+    pub async fn post<I, O>(&self, path: &str, input: &I) -> Result<O>
+    where
+        I: Serialize + ?Sized,
+        O: DeserializeOwned,
+    {
+        let res = self
+            .http
+            .post(format!("{}/{}", self.url, path))
+            .json(input)
+            .send()
+            .await
+            .map_err(Error::Http)?;
+        let status = res.status();
+        if status.is_client_error() || status.is_server_error() {
+            let body = res.text().await.map_err(Error::Http)?;
+            return Err(api_error(status, &body));
+        }
+        res.json().await.map_err(Error::Decode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_error_reads_json_body() {
+        let body = r#"{"message":{"code":1},"description":"Invalid seed"}"#;
+        match api_error(reqwest::StatusCode::BAD_REQUEST, body) {
+            Error::Api {
+                status,
+                message,
+                description,
+            } => {
+                assert_eq!(status, 400);
+                assert_eq!(message, serde_json::json!({ "code": 1 }));
+                assert_eq!(description, "Invalid seed");
+            }
+            err => panic!("unexpected error: {}", err),
+        }
+    }
+
+    #[test]
+    fn api_error_keeps_raw_body() {
+        let body = "<html>Bad Gateway</html>";
+        match api_error(reqwest::StatusCode::BAD_GATEWAY, body) {
+            Error::Api {
+                status,
+                message,
+                description,
+            } => {
+                assert_eq!(status, 502);
+                assert_eq!(message, serde_json::Value::String(body.to_string()));
+                assert_eq!(description, "Bad Gateway");
+            }
+            err => panic!("unexpected error: {}", err),
+        }
+    }
+}
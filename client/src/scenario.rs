@@ -0,0 +1,492 @@
+//! Scripted end-to-end runs against the API, e.g. "create a class, mint to
+//! Bob, exchange on a market, assert Bob's balances".
+//!
+//! A scenario is a list of steps named after the endpoint markers. Inputs are
+//! JSON-like maps; any string of the form `"$step.field.0.field"` is replaced
+//! by that value from the output of the earlier step with id `step`:
+//! ```ron
+//! (
+//!     name: "Mint to Bob",
+//!     steps: [
+//!         (id: "bob", step: CreateAccount),
+//!         (step: MintAsset({"seed": "//Alice", "to": "$bob.account", "class_id": 7, "asset_id": 1, "amount": 10})),
+//!         (step: AssertBalance({"account": "$bob.account", "class_id": 7, "asset_id": 1, "op": "Equal", "amount": 10})),
+//!     ],
+//! )
+//! ```
+//!
+//! [`SugarFungeClient::run_scenario`] runs one directly; apps that queue their
+//! own requests drive a [`ScenarioRun`] with their [`StepSender`] instead.
+
+use std::{collections::HashMap, future::Future, pin::Pin};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use sugarfunge_api_types::{
+    asset::{AssetBalanceInput, AssetBalanceOutput},
+    market::AmountOp,
+    primitives::{Account, AssetId, ClassId},
+};
+
+use crate::{endpoints::*, Endpoint, SugarFungeClient};
+
+pub const SCENARIO_EXTENSION: &str = ".scenario.ron";
+
+pub fn amount_op_holds(op: &AmountOp, value: u128, amount: u128) -> bool {
+    match op {
+        AmountOp::Equal => value == amount,
+        AmountOp::LessThan => value < amount,
+        AmountOp::LessEqualThan => value <= amount,
+        AmountOp::GreaterThan => value > amount,
+        AmountOp::GreaterEqualThan => value >= amount,
+    }
+}
+
+/// A `.scenario.ron` file.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Scenario {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub steps: Vec<ScenarioStep>,
+}
+
+impl Scenario {
+    pub fn from_ron(contents: &str) -> Result<Self, String> {
+        ron::from_str(contents).map_err(|err| err.to_string())
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        std::fs::read_to_string(path)
+            .map_err(|err| format!("{}: {}", path, err))
+            .and_then(|contents| {
+                Self::from_ron(&contents).map_err(|err| format!("{}: {}", path, err))
+            })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScenarioStep {
+    /// Lets later steps reference this step's output. Optional.
+    #[serde(default)]
+    pub id: String,
+    pub step: Step,
+}
+
+/// Passes when the account's balance of the asset satisfies `op amount`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BalanceAssertion {
+    pub account: String,
+    pub class_id: u64,
+    pub asset_id: u64,
+    pub op: AmountOp,
+    pub amount: u128,
+}
+
+macro_rules! steps {
+    ($($name:ident,)*) => {
+        #[derive(Serialize, Deserialize, Debug, Clone)]
+        pub enum Step {
+            CreateAccount,
+            Validators,
+            $($name(Value),)*
+            AssertBalance(Value),
+        }
+
+        impl Step {
+            pub fn name(&self) -> &'static str {
+                match self {
+                    Step::CreateAccount => "CreateAccount",
+                    Step::Validators => "Validators",
+                    $(Step::$name(_) => stringify!($name),)*
+                    Step::AssertBalance(_) => "AssertBalance",
+                }
+            }
+
+            /// Sends the step's request, with references to earlier outputs
+            /// resolved.
+            pub fn send<S: StepSender>(
+                &self,
+                sender: &mut S,
+                outputs: &HashMap<String, Value>,
+            ) -> Result<S::Pending, String> {
+                match self {
+                    Step::CreateAccount => Ok(sender.send::<CreateAccount>(())),
+                    Step::Validators => Ok(sender.send::<Validators>(())),
+                    $(Step::$name(input) => send::<$name, S>(sender, resolve(input, outputs)?),)*
+                    Step::AssertBalance(input) => {
+                        let assertion = assertion(resolve(input, outputs)?)?;
+                        Ok(sender.send::<AssetBalance>(AssetBalanceInput {
+                            account: Account::from(assertion.account),
+                            class_id: ClassId::from(assertion.class_id),
+                            asset_id: AssetId::from(assertion.asset_id),
+                        }))
+                    }
+                }
+            }
+
+            /// The step's output given its request's `response`, which only
+            /// `AssertBalance` can still fail.
+            pub fn finish(
+                &self,
+                outputs: &HashMap<String, Value>,
+                response: Value,
+            ) -> Result<Value, String> {
+                match self {
+                    Step::AssertBalance(input) => {
+                        check_balance(assertion(resolve(input, outputs)?)?, response)
+                    }
+                    _ => Ok(response),
+                }
+            }
+        }
+    };
+}
+
+steps! {
+    FundAccount,
+    AccountExists,
+    SeededAccount,
+    AccountBalance,
+    CreateClass,
+    ClassInfo,
+    CreateAsset,
+    AssetInfo,
+    UpdateAssetMetadata,
+    MintAsset,
+    BurnAsset,
+    AssetBalance,
+    AssetBalances,
+    TransferFrom,
+    RegisterBag,
+    CreateBag,
+    SweepBag,
+    DepositBag,
+    RegisterBundle,
+    MintBundle,
+    BurnBundle,
+    CreateMarket,
+    CreateMarketRate,
+    DepositAssets,
+    ExchangeAssets,
+    AddValidator,
+    RemoveValidator,
+}
+
+/// Sends the request of a scenario step and hands back its pending response,
+/// the endpoint's output as JSON or the error it failed with.
+pub trait StepSender {
+    type Pending;
+
+    fn send<E: Endpoint>(&mut self, input: E::Input) -> Self::Pending;
+}
+
+impl<'a> StepSender for &'a SugarFungeClient {
+    type Pending = Pin<Box<dyn Future<Output = Result<Value, String>> + Send + 'a>>;
+
+    fn send<E: Endpoint>(&mut self, input: E::Input) -> Self::Pending {
+        let client = *self;
+        Box::pin(async move {
+            let output = client
+                .call::<E>(&input)
+                .await
+                .map_err(|err| err.to_string())?;
+            serde_json::to_value(output).map_err(|err| err.to_string())
+        })
+    }
+}
+
+/// Checks `input` against the endpoint's input type before sending it.
+fn send<E, S>(sender: &mut S, input: Value) -> Result<S::Pending, String>
+where
+    E: Endpoint,
+    E::Input: DeserializeOwned,
+    S: StepSender,
+{
+    let input: E::Input =
+        serde_json::from_value(input).map_err(|err| format!("Invalid input: {}", err))?;
+    Ok(sender.send::<E>(input))
+}
+
+fn assertion(input: Value) -> Result<BalanceAssertion, String> {
+    serde_json::from_value(input).map_err(|err| format!("Invalid assertion: {}", err))
+}
+
+fn check_balance(assertion: BalanceAssertion, response: Value) -> Result<Value, String> {
+    let output: AssetBalanceOutput =
+        serde_json::from_value(response).map_err(|err| format!("Invalid response: {}", err))?;
+    let balance = u128::from(output.amount);
+    if amount_op_holds(&assertion.op, balance, assertion.amount) {
+        Ok(serde_json::json!({ "amount": balance.to_string() }))
+    } else {
+        Err(format!(
+            "Balance of {}/{} is {}, expected {:?} {}",
+            assertion.class_id, assertion.asset_id, balance, assertion.op, assertion.amount
+        ))
+    }
+}
+
+/// Replaces `"$step.path"` strings with values from earlier outputs.
+fn resolve(input: &Value, outputs: &HashMap<String, Value>) -> Result<Value, String> {
+    match input {
+        Value::String(text) => match text.strip_prefix('$') {
+            Some(reference) => lookup(reference, outputs),
+            None => Ok(input.clone()),
+        },
+        Value::Array(values) => values
+            .iter()
+            .map(|value| resolve(value, outputs))
+            .collect::<Result<_, _>>()
+            .map(Value::Array),
+        Value::Object(map) => map
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), resolve(value, outputs)?)))
+            .collect::<Result<_, String>>()
+            .map(Value::Object),
+        _ => Ok(input.clone()),
+    }
+}
+
+fn lookup(reference: &str, outputs: &HashMap<String, Value>) -> Result<Value, String> {
+    let mut path = reference.split('.');
+    let step = path.next().unwrap_or_default();
+    let mut value = outputs
+        .get(step)
+        .ok_or_else(|| format!("${}: no earlier step with id {:?}", reference, step))?;
+    for segment in path {
+        value = match value {
+            Value::Array(values) => segment
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| values.get(index)),
+            Value::Object(map) => map.get(segment),
+            _ => None,
+        }
+        .ok_or_else(|| format!("${}: output has no {:?}", reference, segment))?;
+    }
+    Ok(value.clone())
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum StepStatus {
+    Passed,
+    Failed,
+    /// Not run because an earlier step failed.
+    Skipped,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StepReport {
+    pub id: String,
+    pub step: String,
+    pub status: StepStatus,
+    /// The step's output when passed, the error when failed.
+    pub result: Value,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScenarioReport {
+    pub scenario: String,
+    pub passed: bool,
+    pub steps: Vec<StepReport>,
+}
+
+/// A scenario being run one step at a time: `send_next` sends a step's
+/// request and `finish_step` records its response, stopping at the first
+/// failure.
+#[derive(Debug, Clone)]
+pub struct ScenarioRun {
+    scenario: Scenario,
+    outputs: HashMap<String, Value>,
+    report: ScenarioReport,
+}
+
+impl ScenarioRun {
+    pub fn new(scenario: &Scenario) -> Self {
+        Self {
+            scenario: scenario.clone(),
+            outputs: HashMap::new(),
+            report: ScenarioReport {
+                scenario: scenario.name.clone(),
+                passed: true,
+                steps: vec![],
+            },
+        }
+    }
+
+    /// Sends the request of the next step, or returns `None` once every step
+    /// is reported. Steps that can't be sent fail without a request.
+    pub fn send_next<S: StepSender>(&mut self, sender: &mut S) -> Option<S::Pending> {
+        loop {
+            let step = self.scenario.steps.get(self.report.steps.len())?;
+            if !self.report.passed {
+                self.record(StepStatus::Skipped, Value::Null);
+                continue;
+            }
+            match step.step.send(sender, &self.outputs) {
+                Ok(pending) => return Some(pending),
+                Err(err) => self.fail(err),
+            }
+        }
+    }
+
+    /// Records the response of the step `send_next` last sent.
+    pub fn finish_step(&mut self, response: Result<Value, String>) {
+        let Some(step) = self.scenario.steps.get(self.report.steps.len()) else {
+            return;
+        };
+        match response.and_then(|response| step.step.finish(&self.outputs, response)) {
+            Ok(output) => {
+                if !step.id.is_empty() {
+                    self.outputs.insert(step.id.clone(), output.clone());
+                }
+                self.record(StepStatus::Passed, output);
+            }
+            Err(err) => self.fail(err),
+        }
+    }
+
+    fn fail(&mut self, err: String) {
+        self.report.passed = false;
+        self.record(StepStatus::Failed, Value::String(err));
+    }
+
+    fn record(&mut self, status: StepStatus, result: Value) {
+        let step = &self.scenario.steps[self.report.steps.len()];
+        self.report.steps.push(StepReport {
+            id: step.id.clone(),
+            step: step.step.name().to_string(),
+            status,
+            result,
+        });
+    }
+
+    pub fn report(&self) -> &ScenarioReport {
+        &self.report
+    }
+
+    pub fn into_report(self) -> ScenarioReport {
+        self.report
+    }
+}
+
+impl SugarFungeClient {
+    /// Runs the steps in order, stopping at the first failure.
+    pub async fn run_scenario(&self, scenario: &Scenario) -> ScenarioReport {
+        let mut run = ScenarioRun::new(scenario);
+        let mut sender = self;
+        while let Some(pending) = run.send_next(&mut sender) {
+            let response = pending.await;
+            run.finish_step(response);
+        }
+        run.into_report()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn outputs() -> HashMap<String, Value> {
+        HashMap::from([(
+            "bob".to_string(),
+            json!({ "account": "5Bob", "balances": [{ "amount": 3 }, { "amount": 4 }] }),
+        )])
+    }
+
+    #[test]
+    fn lookup_follows_fields_and_indices() {
+        let outputs = outputs();
+        assert_eq!(lookup("bob.account", &outputs), Ok(json!("5Bob")));
+        assert_eq!(lookup("bob.balances.1.amount", &outputs), Ok(json!(4)));
+        assert_eq!(lookup("bob", &outputs), Ok(outputs["bob"].clone()));
+    }
+
+    #[test]
+    fn lookup_reports_missing_references() {
+        let outputs = outputs();
+        assert_eq!(
+            lookup("alice.account", &outputs),
+            Err("$alice.account: no earlier step with id \"alice\"".to_string())
+        );
+        assert_eq!(
+            lookup("bob.balances.2", &outputs),
+            Err("$bob.balances.2: output has no \"2\"".to_string())
+        );
+        assert_eq!(
+            lookup("bob.account.name", &outputs),
+            Err("$bob.account.name: output has no \"name\"".to_string())
+        );
+    }
+
+    #[test]
+    fn resolve_replaces_nested_references() {
+        let input = json!({
+            "to": "$bob.account",
+            "amounts": ["$bob.balances.0.amount", 5],
+            "seed": "//Alice",
+        });
+        assert_eq!(
+            resolve(&input, &outputs()),
+            Ok(json!({ "to": "5Bob", "amounts": [3, 5], "seed": "//Alice" }))
+        );
+        assert!(resolve(&json!(["$carol.account"]), &outputs()).is_err());
+    }
+
+    /// Records the path and input of every request.
+    #[derive(Default)]
+    struct Recorder(Vec<(&'static str, Value)>);
+
+    impl StepSender for Recorder {
+        type Pending = ();
+
+        fn send<E: Endpoint>(&mut self, input: E::Input) {
+            self.0
+                .push((E::PATH, serde_json::to_value(&input).unwrap()));
+        }
+    }
+
+    #[test]
+    fn run_resolves_steps_and_skips_after_failure() {
+        let scenario = Scenario::from_ron(
+            r#"(
+                name: "Mint to Bob",
+                steps: [
+                    (id: "bob", step: CreateAccount),
+                    (step: AssertBalance({"account": "$bob.account", "class_id": 7, "asset_id": 1, "op": "GreaterThan", "amount": 10})),
+                    (step: CreateAccount),
+                ],
+            )"#,
+        )
+        .unwrap();
+        let mut run = ScenarioRun::new(&scenario);
+        let mut sender = Recorder::default();
+
+        assert!(run.send_next(&mut sender).is_some());
+        run.finish_step(Ok(json!({ "seed": "//Bob", "account": "5Bob" })));
+        assert!(run.send_next(&mut sender).is_some());
+        assert_eq!(sender.0[1].0, AssetBalance::PATH);
+        assert_eq!(sender.0[1].1["account"], json!("5Bob"));
+        run.finish_step(Ok(json!({ "amount": 10 })));
+        assert!(run.send_next(&mut sender).is_none());
+
+        let report = run.into_report();
+        assert!(!report.passed);
+        assert_eq!(sender.0.len(), 2);
+        let statuses: Vec<_> = report
+            .steps
+            .iter()
+            .map(|step| step.status.clone())
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![StepStatus::Passed, StepStatus::Failed, StepStatus::Skipped]
+        );
+        assert_eq!(
+            report.steps[1].result,
+            json!("Balance of 7/1 is 10, expected GreaterThan 10")
+        );
+    }
+}
@@ -0,0 +1,44 @@
+//! Whole tokens and the balance units the chain counts in.
+//!
+//! Account funding and bag shares are sent in units of 10^-18 of a token, so
+//! forms that take whole tokens scale them here. Asset amounts are not scaled.
+
+use sugarfunge_api_types::primitives::Balance;
+
+pub const TOKEN_DECIMALS: u32 = 18;
+
+/// Balance units in one whole token.
+pub const TOKEN_UNIT: u128 = u128::pow(10, TOKEN_DECIMALS);
+
+/// `amount` whole tokens in balance units.
+pub fn whole_tokens(amount: u64) -> Balance {
+    Balance::from(amount as u128 * TOKEN_UNIT)
+}
+
+/// Inverse of `whole_tokens`, or `None` if `amount` isn't a whole number of
+/// tokens.
+pub fn to_whole_tokens(amount: Balance) -> Option<u128> {
+    let units = u128::from(amount);
+    (units % TOKEN_UNIT == 0).then_some(units / TOKEN_UNIT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn whole_tokens_round_trip() {
+        assert_eq!(u128::from(whole_tokens(3)), 3_000_000_000_000_000_000);
+        assert_eq!(to_whole_tokens(whole_tokens(3)), Some(3));
+        assert_eq!(
+            to_whole_tokens(whole_tokens(u64::MAX)),
+            Some(u64::MAX as u128)
+        );
+    }
+
+    #[test]
+    fn partial_tokens_are_not_whole() {
+        assert_eq!(to_whole_tokens(Balance::from(TOKEN_UNIT + 1)), None);
+        assert_eq!(to_whole_tokens(Balance::from(0)), Some(0));
+    }
+}
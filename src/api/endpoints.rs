@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+
+pub use sugarfunge_client::endpoints::*;
+
+use super::{ApiCompleted, ApiFailed, ApiRequest, EndpointPlugin};
+
+macro_rules! endpoint_events {
+    ($($name:ident, $request:ident, $completed:ident, $failed:ident;)*) => {
+        $(
+            pub type $request = ApiRequest<$name>;
+            pub type $completed = ApiCompleted<$name>;
+            pub type $failed = ApiFailed<$name>;
+        )*
+
+        /// Registers the request, completed and failed events of every endpoint.
+        pub struct ApiPlugin;
+
+        impl Plugin for ApiPlugin {
+            fn build(&self, app: &mut App) {
+                $(app.add_plugin(EndpointPlugin::<$name>::default());)*
+            }
+        }
+    };
+}
+
+endpoint_events! {
+    CreateAccount, CreateAccountRequest, CreateAccountCompleted, CreateAccountFailed;
+    FundAccount, FundAccountRequest, FundAccountCompleted, FundAccountFailed;
+    AccountExists, AccountExistsRequest, AccountExistsCompleted, AccountExistsFailed;
+    SeededAccount, SeededAccountRequest, SeededAccountCompleted, SeededAccountFailed;
+    AccountBalance, AccountBalanceRequest, AccountBalanceCompleted, AccountBalanceFailed;
+
+    CreateClass, CreateClassRequest, CreateClassCompleted, CreateClassFailed;
+    ClassInfo, ClassInfoRequest, ClassInfoCompleted, ClassInfoFailed;
+
+    CreateAsset, CreateAssetRequest, CreateAssetCompleted, CreateAssetFailed;
+    AssetInfo, AssetInfoRequest, AssetInfoCompleted, AssetInfoFailed;
+    UpdateAssetMetadata, UpdateAssetMetadataRequest, UpdateAssetMetadataCompleted,
+        UpdateAssetMetadataFailed;
+    MintAsset, MintAssetRequest, MintAssetCompleted, MintAssetFailed;
+    BurnAsset, BurnAssetRequest, BurnAssetCompleted, BurnAssetFailed;
+    AssetBalance, AssetBalanceRequest, AssetBalanceCompleted, AssetBalanceFailed;
+    AssetBalances, AssetBalancesRequest, AssetBalancesCompleted, AssetBalancesFailed;
+    TransferFrom, TransferFromRequest, TransferFromCompleted, TransferFromFailed;
+
+    RegisterBag, RegisterBagRequest, RegisterBagCompleted, RegisterBagFailed;
+    CreateBag, CreateBagRequest, CreateBagCompleted, CreateBagFailed;
+    SweepBag, SweepBagRequest, SweepBagCompleted, SweepBagFailed;
+    DepositBag, DepositBagRequest, DepositBagCompleted, DepositBagFailed;
+
+    RegisterBundle, RegisterBundleRequest, RegisterBundleCompleted, RegisterBundleFailed;
+    MintBundle, MintBundleRequest, MintBundleCompleted, MintBundleFailed;
+    BurnBundle, BurnBundleRequest, BurnBundleCompleted, BurnBundleFailed;
+
+    CreateMarket, CreateMarketRequest, CreateMarketCompleted, CreateMarketFailed;
+    CreateMarketRate, CreateMarketRateRequest, CreateMarketRateCompleted, CreateMarketRateFailed;
+    DepositAssets, DepositAssetsRequest, DepositAssetsCompleted, DepositAssetsFailed;
+    ExchangeAssets, ExchangeAssetsRequest, ExchangeAssetsCompleted, ExchangeAssetsFailed;
+
+    AddValidator, AddValidatorRequest, AddValidatorCompleted, AddValidatorFailed;
+    RemoveValidator, RemoveValidatorRequest, RemoveValidatorCompleted, RemoveValidatorFailed;
+    Validators, ValidatorsRequest, ValidatorsCompleted, ValidatorsFailed;
+}
@@ -0,0 +1,324 @@
+//! Event-driven access to the SugarFunge API.
+//!
+//! Game systems send an `ApiRequest<E>` (e.g. `MintAssetRequest`) and later
+//! receive an `ApiCompleted<E>` or `ApiFailed<E>` carrying the same
+//! `RequestId`. The debug UI talks to the chain through this layer too.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use crossbeam::channel::{self, Receiver, Sender};
+use sugarfunge_client::SugarFungeClient;
+
+use crate::prelude::*;
+
+pub mod endpoints;
+
+pub use endpoints::*;
+pub use sugarfunge_client::{Endpoint, Error as ApiError};
+
+/// Correlates an `ApiRequest` with its `ApiCompleted` or `ApiFailed` event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RequestId(pub u64);
+
+impl RequestId {
+    pub fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(1);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl std::fmt::Display for RequestId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+pub struct ApiRequest<E: Endpoint> {
+    pub id: RequestId,
+    pub input: E::Input,
+}
+
+impl<E: Endpoint> ApiRequest<E> {
+    pub fn new(input: E::Input) -> Self {
+        Self {
+            id: RequestId::next(),
+            input,
+        }
+    }
+}
+
+/// Shared so any number of systems can read the same response.
+pub struct ApiCompleted<E: Endpoint> {
+    pub id: RequestId,
+    pub input: Arc<E::Input>,
+    pub output: Arc<E::Output>,
+}
+
+pub struct ApiFailed<E: Endpoint> {
+    pub id: RequestId,
+    pub input: Arc<E::Input>,
+    pub error: Arc<ApiError>,
+}
+
+type ApiResult<E> = (
+    RequestId,
+    <E as Endpoint>::Input,
+    Result<<E as Endpoint>::Output, ApiError>,
+);
+
+#[derive(Resource)]
+pub struct ApiChannel<E: Endpoint> {
+    tx: Sender<ApiResult<E>>,
+    rx: Receiver<ApiResult<E>>,
+}
+
+impl<E: Endpoint> Default for ApiChannel<E> {
+    fn default() -> Self {
+        let (tx, rx) = channel::unbounded();
+        Self { tx, rx }
+    }
+}
+
+/// The client every request goes through. Exports read its URL, so copied
+/// requests target the same API as the app.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ApiClient(pub SugarFungeClient);
+
+/// Takes ownership of the requests, so other systems can't read
+/// `ApiRequest` events; they should watch the responses instead.
+pub fn send_api_requests<E: Endpoint>(
+    mut requests: ResMut<Events<ApiRequest<E>>>,
+    channel: Res<ApiChannel<E>>,
+    client: Res<ApiClient>,
+    tokio_runtime: Res<TokioRuntime>,
+) {
+    for request in requests.drain() {
+        let tx = channel.tx.clone();
+        let client = client.0.clone();
+        tokio_runtime.runtime.spawn(async move {
+            let result = client.call::<E>(&request.input).await;
+            tx.send((request.id, request.input, result)).ok();
+        });
+    }
+}
+
+pub fn receive_api_responses<E: Endpoint>(
+    channel: Res<ApiChannel<E>>,
+    mut completed: EventWriter<ApiCompleted<E>>,
+    mut failed: EventWriter<ApiFailed<E>>,
+) {
+    for (id, input, result) in channel.rx.try_iter() {
+        match result {
+            Ok(output) => completed.send(ApiCompleted {
+                id,
+                input: Arc::new(input),
+                output: Arc::new(output),
+            }),
+            Err(error) => {
+                error!("Request {} {} error: {}", id, E::PATH, error);
+                failed.send(ApiFailed {
+                    id,
+                    input: Arc::new(input),
+                    error: Arc::new(error),
+                });
+            }
+        }
+    }
+}
+
+/// Raw JSON output of an API call, or the error it failed with.
+pub type RawResponse = Result<serde_json::Value, String>;
+
+/// Senders of the responses `send_raw_request` callers are waiting for.
+#[derive(Resource, Default)]
+pub struct RawResponses(HashMap<RequestId, Sender<RawResponse>>);
+
+/// Sends an `ApiRequest<E>` once `commands` are applied and hands back its
+/// response, for callers that keep the raw output to report it, such as
+/// behavior tree nodes and scenario steps.
+pub fn send_raw_request<E: Endpoint>(
+    commands: &mut Commands,
+    input: E::Input,
+) -> Receiver<RawResponse> {
+    let (response_tx, response_rx) = channel::bounded(1);
+    let request = ApiRequest::<E>::new(input);
+    commands.add(move |world: &mut World| {
+        world
+            .resource_mut::<RawResponses>()
+            .0
+            .insert(request.id, response_tx);
+        world.send_event(request);
+    });
+    response_rx
+}
+
+pub fn forward_raw_responses<E: Endpoint>(
+    mut responses: ResMut<RawResponses>,
+    mut completed: EventReader<ApiCompleted<E>>,
+    mut failed: EventReader<ApiFailed<E>>,
+) {
+    for completed in completed.iter() {
+        if let Some(response_tx) = responses.0.remove(&completed.id) {
+            let response = serde_json::to_value(&*completed.output).map_err(|err| err.to_string());
+            response_tx.send(response).ok();
+        }
+    }
+    for failed in failed.iter() {
+        if let Some(response_tx) = responses.0.remove(&failed.id) {
+            response_tx.send(Err(failed.error.to_string())).ok();
+        }
+    }
+}
+
+/// How many responses `ApiLog` keeps.
+const API_LOG_LEN: usize = 200;
+
+/// One response in the `ApiLog`.
+#[derive(Debug, Clone)]
+pub struct ApiLogEntry {
+    pub id: RequestId,
+    pub path: &'static str,
+    pub input: serde_json::Value,
+    /// The output, or the error the request failed with.
+    pub result: Result<serde_json::Value, String>,
+}
+
+/// The latest responses of every endpoint, oldest first, for the debug UI's
+/// network log.
+#[derive(Resource, Debug, Default)]
+pub struct ApiLog {
+    pub entries: VecDeque<ApiLogEntry>,
+}
+
+impl ApiLog {
+    pub fn push(&mut self, entry: ApiLogEntry) {
+        if self.entries.len() >= API_LOG_LEN {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+}
+
+pub fn log_api_responses<E: Endpoint>(
+    mut log: ResMut<ApiLog>,
+    mut completed: EventReader<ApiCompleted<E>>,
+    mut failed: EventReader<ApiFailed<E>>,
+) {
+    let json = |value: serde_json::Result<serde_json::Value>| {
+        value.unwrap_or_else(|err| serde_json::Value::String(err.to_string()))
+    };
+    for completed in completed.iter() {
+        log.push(ApiLogEntry {
+            id: completed.id,
+            path: E::PATH,
+            input: json(serde_json::to_value(&*completed.input)),
+            result: Ok(json(serde_json::to_value(&*completed.output))),
+        });
+    }
+    for failed in failed.iter() {
+        log.push(ApiLogEntry {
+            id: failed.id,
+            path: E::PATH,
+            input: json(serde_json::to_value(&*failed.input)),
+            result: Err(failed.error.to_string()),
+        });
+    }
+}
+
+/// Events of one endpoint, for systems that both send and receive.
+#[derive(SystemParam)]
+pub struct ApiEvents<'w, 's, E: Endpoint> {
+    pub requests: EventWriter<'w, ApiRequest<E>>,
+    pub completed: EventReader<'w, 's, ApiCompleted<E>>,
+    pub failed: EventReader<'w, 's, ApiFailed<E>>,
+}
+
+/// Outbox of a form that sends one request at a time, such as a debug UI
+/// panel: requests queued with `send` go out on the next `poll`, which also
+/// returns the response once it arrives.
+pub struct ApiCall<E: Endpoint> {
+    outbox: Vec<ApiRequest<E>>,
+    pending: Option<RequestId>,
+}
+
+impl<E: Endpoint> Default for ApiCall<E> {
+    fn default() -> Self {
+        Self {
+            outbox: vec![],
+            pending: None,
+        }
+    }
+}
+
+impl<E: Endpoint> ApiCall<E> {
+    pub fn send(&mut self, input: E::Input) -> RequestId {
+        let request = ApiRequest::new(input);
+        let id = request.id;
+        self.outbox.push(request);
+        self.pending = Some(id);
+        id
+    }
+
+    pub fn pending(&self) -> Option<RequestId> {
+        self.pending
+    }
+
+    pub fn poll(
+        &mut self,
+        events: &mut ApiEvents<E>,
+    ) -> Option<Result<Arc<E::Output>, Arc<ApiError>>> {
+        events.requests.send_batch(self.outbox.drain(..));
+        let pending = self.pending?;
+        let mut response = None;
+        for completed in events.completed.iter().filter(|event| event.id == pending) {
+            response = Some(Ok(completed.output.clone()));
+        }
+        for failed in events.failed.iter().filter(|event| event.id == pending) {
+            response = Some(Err(failed.error.clone()));
+        }
+        if response.is_some() {
+            self.pending = None;
+        }
+        response
+    }
+}
+
+pub struct EndpointPlugin<E: Endpoint>(PhantomData<E>);
+
+impl<E: Endpoint> Default for EndpointPlugin<E> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<E: Endpoint> Plugin for EndpointPlugin<E> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ApiChannel<E>>()
+            .init_resource::<RawResponses>()
+            .init_resource::<ApiLog>()
+            .init_resource::<ApiClient>()
+            .add_event::<ApiRequest<E>>()
+            .add_event::<ApiCompleted<E>>()
+            .add_event::<ApiFailed<E>>()
+            .add_system(receive_api_responses::<E>.in_base_set(CoreSet::PreUpdate))
+            .add_system(
+                forward_raw_responses::<E>
+                    .in_base_set(CoreSet::PreUpdate)
+                    .after(receive_api_responses::<E>),
+            )
+            .add_system(
+                log_api_responses::<E>
+                    .in_base_set(CoreSet::PreUpdate)
+                    .after(receive_api_responses::<E>),
+            )
+            .add_system(send_api_requests::<E>.in_base_set(CoreSet::PostUpdate));
+    }
+}
@@ -0,0 +1,172 @@
+//! Text documents in the asset folder, such as behavior trees, missions,
+//! scenarios and market rate templates.
+//!
+//! They are read through the `AssetServer`'s IO rather than `std::fs`, so the
+//! web build fetches them from the served `assets/` folder like any other
+//! asset.
+
+use std::{marker::PhantomData, path::Path};
+
+use bevy::{prelude::*, tasks::IoTaskPool};
+use crossbeam::channel::{self, Receiver, TryRecvError};
+use serde::de::DeserializeOwned;
+
+#[cfg(target_arch = "wasm32")]
+include!(concat!(env!("OUT_DIR"), "/asset_files.rs"));
+
+/// Folder the asset server reads from on native builds, which documents are
+/// saved to.
+#[cfg(not(target_arch = "wasm32"))]
+pub const ASSET_FOLDER: &str = "assets";
+
+/// Receives the text of a file requested with `read_asset_file`.
+pub type AssetFileReceiver = Receiver<Result<String, String>>;
+
+#[cfg(not(target_arch = "wasm32"))]
+fn folder_files(asset_server: &AssetServer, dir: &str) -> Vec<String> {
+    asset_server
+        .asset_io()
+        .read_directory(Path::new(dir))
+        .map(|paths| {
+            paths
+                .map(|path| path.to_string_lossy().replace('\\', "/"))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The web build can't list folders, so it lists the files it was built with.
+#[cfg(target_arch = "wasm32")]
+fn folder_files(_asset_server: &AssetServer, dir: &str) -> Vec<String> {
+    ASSET_FILES
+        .iter()
+        .filter(|path| Path::new(path).parent() == Some(Path::new(dir)))
+        .map(|path| path.to_string())
+        .collect()
+}
+
+/// Files in `dir` ending in `extension`, sorted, as paths relative to the
+/// asset folder.
+pub fn list_asset_files(asset_server: &AssetServer, dir: &str, extension: &str) -> Vec<String> {
+    let mut files = folder_files(asset_server, dir);
+    files.retain(|path| path.ends_with(extension));
+    files.sort();
+    files
+}
+
+/// Reads `path`, relative to the asset folder, as text, for loaders that
+/// read several files in one task.
+pub async fn read_asset_text(asset_server: &AssetServer, path: &str) -> Result<String, String> {
+    asset_server
+        .asset_io()
+        .load_path(Path::new(path))
+        .await
+        .map_err(|err| err.to_string())
+        .and_then(|bytes| String::from_utf8(bytes).map_err(|err| err.to_string()))
+        .map_err(|err| format!("{}: {}", path, err))
+}
+
+/// Reads `path`, relative to the asset folder, as text.
+pub fn read_asset_file(asset_server: &AssetServer, path: &str) -> AssetFileReceiver {
+    let (tx, rx) = channel::bounded(1);
+    let asset_server = asset_server.clone();
+    let path = path.to_string();
+    IoTaskPool::get()
+        .spawn(async move {
+            tx.send(read_asset_text(&asset_server, &path).await).ok();
+        })
+        .detach();
+    rx
+}
+
+/// The text of `file`, read from `path`, or `None` while it's still being
+/// read.
+pub fn poll_asset_file(path: &str, file: &AssetFileReceiver) -> Option<Result<String, String>> {
+    match file.try_recv() {
+        Ok(result) => Some(result),
+        Err(TryRecvError::Empty) => None,
+        Err(TryRecvError::Disconnected) => Some(Err(format!("{}: read dropped", path))),
+    }
+}
+
+/// The RON documents of a folder being read, such as the missions.
+pub struct RonAssets<T> {
+    extension: &'static str,
+    loading: Vec<(String, AssetFileReceiver)>,
+    document: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for RonAssets<T> {
+    fn default() -> Self {
+        Self {
+            extension: "",
+            loading: vec![],
+            document: PhantomData,
+        }
+    }
+}
+
+/// Starts reading the files in `dir` ending in `extension`, both relative to
+/// the asset folder.
+pub fn load_ron_assets<T>(
+    asset_server: &AssetServer,
+    dir: &str,
+    extension: &'static str,
+) -> RonAssets<T> {
+    RonAssets {
+        extension,
+        loading: list_asset_files(asset_server, dir, extension)
+            .into_iter()
+            .map(|path| {
+                let file = read_asset_file(asset_server, &path);
+                (path, file)
+            })
+            .collect(),
+        document: PhantomData,
+    }
+}
+
+impl<T: DeserializeOwned> RonAssets<T> {
+    /// Whether every file has been received.
+    pub fn is_empty(&self) -> bool {
+        self.loading.is_empty()
+    }
+
+    /// The documents read since the last call, by file name without the
+    /// extension. Errors name the file.
+    pub fn receive(&mut self) -> Vec<Result<(String, T), String>> {
+        let mut received = vec![];
+        let extension = self.extension;
+        self.loading.retain(|(path, file)| {
+            let Some(result) = poll_asset_file(path, file) else {
+                return true;
+            };
+            let id = Path::new(path)
+                .file_name()
+                .map(|name| {
+                    name.to_string_lossy()
+                        .trim_end_matches(extension)
+                        .to_string()
+                })
+                .unwrap_or_default();
+            received.push(result.and_then(|contents| {
+                ron::from_str(&contents)
+                    .map(|document| (id, document))
+                    .map_err(|err| format!("{}: {}", path, err))
+            }));
+            false
+        });
+        received
+    }
+}
+
+/// Saves `contents` to `path`, relative to the asset folder, creating missing
+/// folders.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_asset_file(path: &str, contents: &str) -> Result<(), String> {
+    let path = Path::new(ASSET_FOLDER).join(path);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    std::fs::write(&path, contents).map_err(|err| format!("{}: {}", path.display(), err))
+}
@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+use crossbeam::channel::Receiver;
+use sugarfunge_api_types::{
+    account::FundAccountInput,
+    asset::{AssetBalanceInput, AssetBalanceOutput, MintInput, TransferFromInput},
+    market::{DepositAssetsInput, DepositAssetsOutput, ExchangeAssetsInput, ExchangeAssetsOutput},
+    primitives::{Account, AssetId, Balance, ClassId, MarketId, Seed},
+};
+pub use sugarfunge_client::scenario::amount_op_holds;
+use sugarfunge_client::units::whole_tokens;
+
+use crate::api::{
+    send_raw_request, AssetBalance, DepositAssets, ExchangeAssets, FundAccount, MintAsset,
+    RawResponse, TransferFrom,
+};
+
+use super::{document::Behavior, BehaviorStatus};
+
+/// Sends the API call for an action behavior, or `None` for behaviors that
+/// don't call the API.
+pub fn start_action(behavior: &Behavior, commands: &mut Commands) -> Option<Receiver<RawResponse>> {
+    let receiver = match behavior {
+        Behavior::FundAccount(fund) => send_raw_request::<FundAccount>(
+            commands,
+            FundAccountInput {
+                seed: Seed::from(fund.seed.clone()),
+                to: Account::from(fund.to.clone()),
+                amount: whole_tokens(fund.amount),
+            },
+        ),
+        Behavior::MintAsset(mint) => send_raw_request::<MintAsset>(
+            commands,
+            MintInput {
+                seed: Seed::from(mint.seed.clone()),
+                to: Account::from(mint.to.clone()),
+                class_id: ClassId::from(mint.class_id),
+                asset_id: AssetId::from(mint.asset_id),
+                amount: Balance::from(mint.amount as u128),
+            },
+        ),
+        Behavior::TransferFrom(transfer) => send_raw_request::<TransferFrom>(
+            commands,
+            TransferFromInput {
+                seed: Seed::from(transfer.seed.clone()),
+                from: Account::from(transfer.from.clone()),
+                to: Account::from(transfer.to.clone()),
+                class_id: ClassId::from(transfer.class_id),
+                asset_id: AssetId::from(transfer.asset_id),
+                amount: Balance::from(transfer.amount as u128),
+            },
+        ),
+        Behavior::DepositAssets(deposit) => send_raw_request::<DepositAssets>(
+            commands,
+            DepositAssetsInput {
+                seed: Seed::from(deposit.seed.clone()),
+                market_id: MarketId::from(deposit.market_id),
+                market_rate_id: MarketId::from(deposit.market_rate_id),
+                amount: Balance::from(deposit.amount as u128),
+            },
+        ),
+        Behavior::ExchangeAssets(exchange) => send_raw_request::<ExchangeAssets>(
+            commands,
+            ExchangeAssetsInput {
+                seed: Seed::from(exchange.seed.clone()),
+                market_id: MarketId::from(exchange.market_id),
+                market_rate_id: MarketId::from(exchange.market_rate_id),
+                amount: Balance::from(exchange.amount as u128),
+            },
+        ),
+        Behavior::CheckBalance(check) => send_raw_request::<AssetBalance>(
+            commands,
+            AssetBalanceInput {
+                account: Account::from(check.account.clone()),
+                class_id: ClassId::from(check.class_id),
+                asset_id: AssetId::from(check.asset_id),
+            },
+        ),
+        _ => return None,
+    };
+    Some(receiver)
+}
+
+/// Turns an action's API response into its node status and a short result
+/// description. Calls that move assets reach the economy through
+/// `api_economy_events` like any other request.
+pub fn finish_action(behavior: &Behavior, response: RawResponse) -> (BehaviorStatus, String) {
+    let value = match response {
+        Ok(value) => value,
+        Err(err) => return (BehaviorStatus::Failure, err),
+    };
+    let result = value.to_string();
+    match behavior {
+        Behavior::DepositAssets(_) => match serde_json::from_value::<DepositAssetsOutput>(value) {
+            Ok(deposit) if deposit.success => {}
+            _ => return (BehaviorStatus::Failure, result),
+        },
+        Behavior::ExchangeAssets(_) => {
+            match serde_json::from_value::<ExchangeAssetsOutput>(value) {
+                Ok(exchange) if exchange.success => {}
+                _ => return (BehaviorStatus::Failure, result),
+            }
+        }
+        Behavior::CheckBalance(check) => {
+            let Ok(balance) = serde_json::from_value::<AssetBalanceOutput>(value) else {
+                return (BehaviorStatus::Failure, result);
+            };
+            let amount = u128::from(balance.amount);
+            let status = if amount_op_holds(&check.op, amount, check.amount as u128) {
+                BehaviorStatus::Success
+            } else {
+                BehaviorStatus::Failure
+            };
+            return (
+                status,
+                format!("balance {} {:?} {}", amount, check.op, check.amount),
+            );
+        }
+        _ => {}
+    }
+    (BehaviorStatus::Success, result)
+}
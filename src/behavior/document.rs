@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+use sugarfunge_api_types::market::AmountOp;
+
+/// A `.bht.ron` file: `(root: ("name", Behavior, [children]))`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BehaviorDocument {
+    pub root: BehaviorNode,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BehaviorNode(
+    pub String,
+    pub Behavior,
+    #[serde(default)] pub Vec<BehaviorNode>,
+);
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum Behavior {
+    Debug(DebugBehavior),
+    Delay(DelayBehavior),
+    Sequencer(()),
+    Selector(SelectorBehavior),
+    All(()),
+    Any(()),
+    Repeater(RepeaterBehavior),
+    Inverter(()),
+    Quest(QuestBehavior),
+    FundAccount(FundAccountBehavior),
+    MintAsset(MintAssetBehavior),
+    TransferFrom(TransferFromBehavior),
+    DepositAssets(MarketAssetsBehavior),
+    ExchangeAssets(MarketAssetsBehavior),
+    CheckBalance(CheckBalanceBehavior),
+}
+
+impl Behavior {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Behavior::Debug(_) => "Debug",
+            Behavior::Delay(_) => "Delay",
+            Behavior::Sequencer(_) => "Sequencer",
+            Behavior::Selector(_) => "Selector",
+            Behavior::All(_) => "All",
+            Behavior::Any(_) => "Any",
+            Behavior::Repeater(_) => "Repeater",
+            Behavior::Inverter(_) => "Inverter",
+            Behavior::Quest(_) => "Quest",
+            Behavior::FundAccount(_) => "FundAccount",
+            Behavior::MintAsset(_) => "MintAsset",
+            Behavior::TransferFrom(_) => "TransferFrom",
+            Behavior::DepositAssets(_) => "DepositAssets",
+            Behavior::ExchangeAssets(_) => "ExchangeAssets",
+            Behavior::CheckBalance(_) => "CheckBalance",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct DebugBehavior {
+    pub message: String,
+    pub duration: f32,
+    pub fail: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct DelayBehavior {
+    pub duration: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct SelectorBehavior {
+    pub random: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub enum Repeat {
+    #[default]
+    Forever,
+    Times(u64),
+    UntilFail,
+    UntilSucceed,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct RepeaterBehavior {
+    pub repeat: Repeat,
+}
+
+/// Runs the tree in another `.bht.ron` file, with `asset` relative to the
+/// asset folder.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct QuestBehavior {
+    pub asset: String,
+    /// Read so simula trees keep loading, but without effect: the subtree is
+    /// loaded with the tree and kept until the tree is despawned.
+    pub unload: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct FundAccountBehavior {
+    pub seed: String,
+    pub to: String,
+    /// Whole tokens, scaled like the debug UI fund form.
+    pub amount: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct MintAssetBehavior {
+    pub seed: String,
+    pub to: String,
+    pub class_id: u64,
+    pub asset_id: u64,
+    pub amount: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct TransferFromBehavior {
+    pub seed: String,
+    pub from: String,
+    pub to: String,
+    pub class_id: u64,
+    pub asset_id: u64,
+    pub amount: u64,
+}
+
+/// Shared by `DepositAssets` and `ExchangeAssets`, which take the same input.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct MarketAssetsBehavior {
+    pub seed: String,
+    pub market_id: u64,
+    pub market_rate_id: u64,
+    pub amount: u64,
+}
+
+/// Succeeds when `account`'s balance of the asset satisfies `op amount`, the
+/// same check a `RateAction::Has` rate makes.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct CheckBalanceBehavior {
+    pub account: String,
+    pub class_id: u64,
+    pub asset_id: u64,
+    pub op: AmountOp,
+    pub amount: u64,
+}
+
+impl Default for CheckBalanceBehavior {
+    fn default() -> Self {
+        Self {
+            account: String::default(),
+            class_id: 0,
+            asset_id: 0,
+            op: AmountOp::GreaterEqualThan,
+            amount: 0,
+        }
+    }
+}
@@ -0,0 +1,578 @@
+use std::collections::HashMap;
+
+use bevy::{prelude::*, tasks::IoTaskPool};
+use crossbeam::channel::{self, Receiver, TryRecvError};
+use rand::seq::SliceRandom;
+
+use crate::{api::RawResponse, asset_files::read_asset_text};
+
+pub mod actions;
+pub mod document;
+pub mod ui;
+pub mod viewer;
+
+use document::{Behavior, BehaviorDocument, BehaviorNode, Repeat};
+
+/// Relative to the asset folder.
+pub const BEHAVIORS_DIR: &str = "behaviors";
+pub const BEHAVIOR_EXTENSION: &str = ".bht.ron";
+/// Guards against quests that include themselves.
+const MAX_QUEST_DEPTH: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BehaviorStatus {
+    Running,
+    Success,
+    Failure,
+}
+
+/// Quest subtrees by asset path, or why they couldn't be loaded.
+pub type QuestSubtrees = HashMap<String, Result<BehaviorNode, String>>;
+
+async fn load_document(asset_server: &AssetServer, path: &str) -> Result<BehaviorDocument, String> {
+    let contents = read_asset_text(asset_server, path).await?;
+    ron::from_str(&contents).map_err(|err| format!("{}: {}", path, err))
+}
+
+fn quest_assets(node: &BehaviorNode, assets: &mut Vec<String>) {
+    let BehaviorNode(_, behavior, children) = node;
+    if let Behavior::Quest(quest) = behavior {
+        assets.push(quest.asset.clone());
+    }
+    for child in children {
+        quest_assets(child, assets);
+    }
+}
+
+/// Loads the subtree of every quest reachable from `root`, each file once.
+async fn load_quests(asset_server: &AssetServer, root: &BehaviorNode) -> QuestSubtrees {
+    let mut subtrees = QuestSubtrees::new();
+    let mut queue = vec![];
+    quest_assets(root, &mut queue);
+    while let Some(asset) = queue.pop() {
+        if subtrees.contains_key(&asset) {
+            continue;
+        }
+        let subtree = load_document(asset_server, &asset)
+            .await
+            .map(|document| document.root);
+        if let Ok(subtree) = &subtree {
+            quest_assets(subtree, &mut queue);
+        }
+        subtrees.insert(asset, subtree);
+    }
+    subtrees
+}
+
+#[derive(Debug)]
+pub struct BehaviorTreeNode {
+    pub name: String,
+    pub behavior: Behavior,
+    pub children: Vec<usize>,
+    /// `None` until the node is first ticked after a reset.
+    pub status: Option<BehaviorStatus>,
+    /// Last debug message, API result or error, kept across resets.
+    pub result: Option<String>,
+    elapsed: f32,
+    cursor: usize,
+    count: u64,
+    order: Vec<usize>,
+    pending: Option<Receiver<RawResponse>>,
+}
+
+impl BehaviorTreeNode {
+    fn reset(&mut self) {
+        self.status = None;
+        self.elapsed = 0.0;
+        self.cursor = 0;
+        self.count = 0;
+        self.order.clear();
+        self.pending = None;
+    }
+}
+
+pub struct BehaviorContext<'a, 'w, 's> {
+    /// Sends the API requests of action nodes.
+    pub commands: &'a mut Commands<'w, 's>,
+    pub delta: f32,
+}
+
+/// A behavior tree flattened into `nodes`, with the root at index 0.
+#[derive(Component, Debug)]
+pub struct BehaviorTree {
+    pub path: String,
+    pub nodes: Vec<BehaviorTreeNode>,
+    pub paused: bool,
+    /// Tick once while paused, then clear.
+    pub step: bool,
+}
+
+impl BehaviorTree {
+    /// Loads the tree at `path`, relative to the asset folder, along with the
+    /// subtrees of its quests.
+    pub fn load(asset_server: &AssetServer, path: &str) -> Receiver<Result<Self, String>> {
+        let (tx, rx) = channel::bounded(1);
+        let asset_server = asset_server.clone();
+        let path = path.to_string();
+        IoTaskPool::get()
+            .spawn(async move {
+                let tree = match load_document(&asset_server, &path).await {
+                    Ok(document) => {
+                        let subtrees = load_quests(&asset_server, &document.root).await;
+                        Ok(Self::with_quests(&path, document, &subtrees))
+                    }
+                    Err(err) => Err(err),
+                };
+                tx.send(tree).ok();
+            })
+            .detach();
+        rx
+    }
+
+    /// A tree without quests, such as one generated in code.
+    pub fn from_document(path: &str, document: BehaviorDocument) -> Self {
+        Self::with_quests(path, document, &QuestSubtrees::new())
+    }
+
+    pub fn with_quests(path: &str, document: BehaviorDocument, subtrees: &QuestSubtrees) -> Self {
+        let mut tree = Self {
+            path: path.to_string(),
+            nodes: vec![],
+            paused: false,
+            step: false,
+        };
+        tree.add_node(document.root, subtrees, 0);
+        tree
+    }
+
+    fn add_node(&mut self, node: BehaviorNode, subtrees: &QuestSubtrees, depth: usize) -> usize {
+        let BehaviorNode(name, behavior, mut children) = node;
+        let mut result = None;
+        if let Behavior::Quest(quest) = &behavior {
+            match subtrees.get(&quest.asset) {
+                _ if depth >= MAX_QUEST_DEPTH => {
+                    result = Some(format!("Quest nested deeper than {}", MAX_QUEST_DEPTH));
+                }
+                Some(Ok(subtree)) => children = vec![subtree.clone()],
+                Some(Err(err)) => result = Some(err.clone()),
+                None => result = Some(format!("{}: not loaded", quest.asset)),
+            }
+        }
+        let index = self.nodes.len();
+        self.nodes.push(BehaviorTreeNode {
+            name,
+            behavior,
+            children: vec![],
+            status: None,
+            result,
+            elapsed: 0.0,
+            cursor: 0,
+            count: 0,
+            order: vec![],
+            pending: None,
+        });
+        let depth = if matches!(self.nodes[index].behavior, Behavior::Quest(_)) {
+            depth + 1
+        } else {
+            depth
+        };
+        for child in children {
+            let child = self.add_node(child, subtrees, depth);
+            self.nodes[index].children.push(child);
+        }
+        index
+    }
+
+    pub fn status(&self) -> Option<BehaviorStatus> {
+        self.nodes.first().and_then(|root| root.status)
+    }
+
+    pub fn finished(&self) -> bool {
+        matches!(
+            self.status(),
+            Some(BehaviorStatus::Success) | Some(BehaviorStatus::Failure)
+        )
+    }
+
+    pub fn reset(&mut self) {
+        if !self.nodes.is_empty() {
+            reset_node(&mut self.nodes, 0);
+        }
+    }
+
+    pub fn tick(&mut self, ctx: &mut BehaviorContext) -> BehaviorStatus {
+        if self.nodes.is_empty() {
+            return BehaviorStatus::Failure;
+        }
+        tick_node(&mut self.nodes, 0, ctx)
+    }
+}
+
+fn reset_node(nodes: &mut [BehaviorTreeNode], index: usize) {
+    nodes[index].reset();
+    for child in nodes[index].children.clone() {
+        reset_node(nodes, child);
+    }
+}
+
+fn start_node(nodes: &mut [BehaviorTreeNode], index: usize, ctx: &mut BehaviorContext) {
+    let node = &mut nodes[index];
+    match &node.behavior {
+        Behavior::Debug(behavior) => {
+            if !behavior.message.is_empty() {
+                info!("{}: {}", node.name, behavior.message);
+                node.result = Some(behavior.message.clone());
+            }
+        }
+        Behavior::Selector(selector) => {
+            node.order = (0..node.children.len()).collect();
+            if selector.random {
+                node.order.shuffle(&mut rand::thread_rng());
+            }
+        }
+        behavior => node.pending = actions::start_action(behavior, ctx.commands),
+    }
+}
+
+fn tick_node(
+    nodes: &mut [BehaviorTreeNode],
+    index: usize,
+    ctx: &mut BehaviorContext,
+) -> BehaviorStatus {
+    match nodes[index].status {
+        Some(BehaviorStatus::Running) => {}
+        Some(status) => return status,
+        None => {
+            nodes[index].status = Some(BehaviorStatus::Running);
+            start_node(nodes, index, ctx);
+        }
+    }
+    let children = nodes[index].children.clone();
+    let status = match &nodes[index].behavior {
+        Behavior::Sequencer(()) => loop {
+            let Some(&child) = children.get(nodes[index].cursor) else {
+                break BehaviorStatus::Success;
+            };
+            match tick_node(nodes, child, ctx) {
+                BehaviorStatus::Success => nodes[index].cursor += 1,
+                status => break status,
+            }
+        },
+        Behavior::Selector(_) => loop {
+            let Some(&order) = nodes[index].order.get(nodes[index].cursor) else {
+                break BehaviorStatus::Failure;
+            };
+            match tick_node(nodes, children[order], ctx) {
+                BehaviorStatus::Failure => nodes[index].cursor += 1,
+                status => break status,
+            }
+        },
+        Behavior::All(()) => {
+            let statuses: Vec<BehaviorStatus> = children
+                .iter()
+                .map(|child| tick_node(nodes, *child, ctx))
+                .collect();
+            if statuses.contains(&BehaviorStatus::Failure) {
+                BehaviorStatus::Failure
+            } else if statuses.contains(&BehaviorStatus::Running) {
+                BehaviorStatus::Running
+            } else {
+                BehaviorStatus::Success
+            }
+        }
+        Behavior::Any(()) => {
+            let statuses: Vec<BehaviorStatus> = children
+                .iter()
+                .map(|child| tick_node(nodes, *child, ctx))
+                .collect();
+            if statuses.contains(&BehaviorStatus::Success) {
+                BehaviorStatus::Success
+            } else if statuses.contains(&BehaviorStatus::Running) {
+                BehaviorStatus::Running
+            } else {
+                BehaviorStatus::Failure
+            }
+        }
+        Behavior::Repeater(repeater) => {
+            let repeat = repeater.repeat.clone();
+            match children.first() {
+                None => BehaviorStatus::Success,
+                Some(&child) => match tick_node(nodes, child, ctx) {
+                    BehaviorStatus::Running => BehaviorStatus::Running,
+                    status => {
+                        nodes[index].count += 1;
+                        let done = match repeat {
+                            Repeat::Forever => false,
+                            Repeat::Times(times) => nodes[index].count >= times,
+                            Repeat::UntilFail => status == BehaviorStatus::Failure,
+                            Repeat::UntilSucceed => status == BehaviorStatus::Success,
+                        };
+                        if done {
+                            BehaviorStatus::Success
+                        } else {
+                            // One iteration per tick, so instant children can't spin.
+                            reset_node(nodes, child);
+                            BehaviorStatus::Running
+                        }
+                    }
+                },
+            }
+        }
+        Behavior::Quest(_) => match children.first() {
+            None => BehaviorStatus::Failure,
+            Some(&child) => tick_node(nodes, child, ctx),
+        },
+        Behavior::Inverter(()) => match children.first() {
+            None => BehaviorStatus::Failure,
+            Some(&child) => match tick_node(nodes, child, ctx) {
+                BehaviorStatus::Success => BehaviorStatus::Failure,
+                BehaviorStatus::Failure => BehaviorStatus::Success,
+                BehaviorStatus::Running => BehaviorStatus::Running,
+            },
+        },
+        Behavior::Delay(delay) => {
+            let duration = delay.duration;
+            nodes[index].elapsed += ctx.delta;
+            if nodes[index].elapsed >= duration {
+                BehaviorStatus::Success
+            } else {
+                BehaviorStatus::Running
+            }
+        }
+        Behavior::Debug(debug) => {
+            let (duration, fail) = (debug.duration, debug.fail);
+            nodes[index].elapsed += ctx.delta;
+            if nodes[index].elapsed < duration {
+                BehaviorStatus::Running
+            } else if fail {
+                BehaviorStatus::Failure
+            } else {
+                BehaviorStatus::Success
+            }
+        }
+        _ => {
+            let response = match &nodes[index].pending {
+                Some(pending) => match pending.try_recv() {
+                    Ok(response) => Some(response),
+                    Err(TryRecvError::Empty) => None,
+                    Err(TryRecvError::Disconnected) => Some(Err("Request dropped".to_string())),
+                },
+                None => Some(Err("Request not sent".to_string())),
+            };
+            match response {
+                None => BehaviorStatus::Running,
+                Some(response) => {
+                    nodes[index].pending = None;
+                    let (status, result) = actions::finish_action(&nodes[index].behavior, response);
+                    nodes[index].result = Some(result);
+                    status
+                }
+            }
+        }
+    };
+    nodes[index].status = Some(status);
+    status
+}
+
+pub fn run_behavior_trees(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut trees: Query<&mut BehaviorTree>,
+) {
+    let mut ctx = BehaviorContext {
+        commands: &mut commands,
+        delta: time.delta_seconds(),
+    };
+    for mut tree in trees.iter_mut() {
+        if tree.finished() || (tree.paused && !tree.step) {
+            continue;
+        }
+        tree.step = false;
+        tree.tick(&mut ctx);
+    }
+}
+
+pub struct BehaviorPlugin;
+
+impl Plugin for BehaviorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ui::BehaviorUi>()
+            .init_resource::<viewer::BehaviorViewer>()
+            .add_system(run_behavior_trees)
+            .add_system(ui::behavior_ui)
+            .add_system(viewer::behavior_viewer_ui.after(ui::behavior_ui));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::CommandQueue;
+
+    use super::*;
+    use BehaviorStatus::*;
+
+    fn tree(root: &str) -> BehaviorTree {
+        let document = ron::from_str(&format!("(root: {})", root)).unwrap();
+        BehaviorTree::from_document("test.bht.ron", document)
+    }
+
+    /// Ticks `tree` once per `delta`, returning the status after each tick.
+    fn run(tree: &mut BehaviorTree, deltas: &[f32]) -> Vec<BehaviorStatus> {
+        let world = World::new();
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        deltas
+            .iter()
+            .map(|delta| {
+                tree.tick(&mut BehaviorContext {
+                    commands: &mut commands,
+                    delta: *delta,
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn composites_propagate_their_children() {
+        let mut sequencer = tree(
+            r#"("seq", Sequencer(()), [("a", Debug(())), ("b", Debug((fail: true))), ("c", Debug(()))])"#,
+        );
+        assert_eq!(run(&mut sequencer, &[0.0]), [Failure]);
+        assert_eq!(sequencer.nodes[3].status, None);
+
+        let mut selector = tree(
+            r#"("sel", Selector(()), [("a", Debug((fail: true))), ("b", Debug(())), ("c", Debug(()))])"#,
+        );
+        assert_eq!(run(&mut selector, &[0.0]), [Success]);
+        assert_eq!(selector.nodes[3].status, None);
+
+        let mut inverter = tree(r#"("not", Inverter(()), [("a", Debug((fail: true)))])"#);
+        assert_eq!(run(&mut inverter, &[0.0]), [Success]);
+
+        let mut all =
+            tree(r#"("all", All(()), [("a", Debug((duration: 1.0))), ("b", Debug(()))])"#);
+        assert_eq!(run(&mut all, &[0.5, 0.5]), [Running, Success]);
+
+        let mut any = tree(
+            r#"("any", Any(()), [("a", Debug((fail: true))), ("b", Debug((duration: 1.0)))])"#,
+        );
+        assert_eq!(run(&mut any, &[0.5, 0.5]), [Running, Success]);
+
+        let mut failing = tree(r#"("any", Any(()), [("a", Debug((fail: true)))])"#);
+        assert_eq!(run(&mut failing, &[0.0]), [Failure]);
+    }
+
+    #[test]
+    fn delay_waits_for_its_duration() {
+        let mut delay = tree(r#"("wait", Delay((duration: 1.0)))"#);
+        assert_eq!(
+            run(&mut delay, &[0.4, 0.4, 0.4]),
+            [Running, Running, Success]
+        );
+    }
+
+    #[test]
+    fn repeater_runs_one_iteration_per_tick() {
+        let mut times = tree(r#"("rep", Repeater((repeat: Times(3))), [("a", Debug(()))])"#);
+        assert_eq!(run(&mut times, &[0.0; 3]), [Running, Running, Success]);
+
+        let mut until_fail =
+            tree(r#"("rep", Repeater((repeat: UntilFail)), [("a", Debug((fail: true)))])"#);
+        assert_eq!(run(&mut until_fail, &[0.0]), [Success]);
+    }
+
+    #[test]
+    fn reset_starts_the_tree_over() {
+        let mut times = tree(r#"("rep", Repeater((repeat: Times(2))), [("a", Debug(()))])"#);
+        assert_eq!(run(&mut times, &[0.0; 2]), [Running, Success]);
+        times.reset();
+        assert_eq!(times.status(), None);
+        assert!(times.nodes.iter().all(|node| node.status.is_none()));
+        assert_eq!(run(&mut times, &[0.0; 2]), [Running, Success]);
+    }
+
+    #[test]
+    fn paused_trees_only_tick_when_stepped() {
+        let mut world = World::new();
+        world.insert_resource(Time::default());
+        let entity = world
+            .spawn(tree(
+                r#"("rep", Repeater((repeat: Times(2))), [("a", Debug(()))])"#,
+            ))
+            .id();
+        world.get_mut::<BehaviorTree>(entity).unwrap().paused = true;
+        let mut schedule = Schedule::default();
+        schedule.add_system(run_behavior_trees);
+        let status = |world: &World| world.get::<BehaviorTree>(entity).unwrap().status();
+
+        schedule.run(&mut world);
+        assert_eq!(status(&world), None);
+
+        world.get_mut::<BehaviorTree>(entity).unwrap().step = true;
+        schedule.run(&mut world);
+        assert_eq!(status(&world), Some(Running));
+        assert!(!world.get::<BehaviorTree>(entity).unwrap().step);
+        schedule.run(&mut world);
+        assert_eq!(status(&world), Some(Running));
+
+        world.get_mut::<BehaviorTree>(entity).unwrap().paused = false;
+        schedule.run(&mut world);
+        assert_eq!(status(&world), Some(Success));
+    }
+
+    #[test]
+    fn quests_run_their_loaded_subtree() {
+        let document: BehaviorDocument = ron::from_str(
+            r#"(root: ("seq", Sequencer(()), [
+                ("quest", Quest((asset: "behaviors/sub.bht.ron", unload: true))),
+                ("missing", Quest((asset: "behaviors/missing.bht.ron"))),
+            ]))"#,
+        )
+        .unwrap();
+        let subtree: BehaviorDocument =
+            ron::from_str(r#"(root: ("sub", Debug((message: "in the subtree"))))"#).unwrap();
+        let subtrees =
+            QuestSubtrees::from([("behaviors/sub.bht.ron".to_string(), Ok(subtree.root))]);
+        let mut quests = BehaviorTree::with_quests("test.bht.ron", document, &subtrees);
+
+        let quest = &quests.nodes[1];
+        assert_eq!(quests.nodes[quest.children[0]].name, "sub");
+        let missing = &quests.nodes[3];
+        assert!(missing.children.is_empty());
+        assert_eq!(
+            missing.result.as_deref(),
+            Some("behaviors/missing.bht.ron: not loaded")
+        );
+
+        assert_eq!(run(&mut quests, &[0.0]), [Failure]);
+        assert_eq!(quests.nodes[2].status, Some(Success));
+        assert_eq!(quests.nodes[3].status, Some(Failure));
+    }
+
+    #[test]
+    fn quests_that_include_themselves_stop_nesting() {
+        let quest = r#"(root: ("loop", Quest((asset: "behaviors/loop.bht.ron"))))"#;
+        let document: BehaviorDocument = ron::from_str(quest).unwrap();
+        let subtrees = QuestSubtrees::from([(
+            "behaviors/loop.bht.ron".to_string(),
+            Ok(document.root.clone()),
+        )]);
+        let looping = BehaviorTree::with_quests("loop.bht.ron", document, &subtrees);
+        assert_eq!(looping.nodes.len(), MAX_QUEST_DEPTH + 1);
+        assert!(looping.nodes[MAX_QUEST_DEPTH]
+            .result
+            .as_deref()
+            .unwrap()
+            .starts_with("Quest nested deeper"));
+    }
+
+    #[test]
+    fn asset_trees_parse() {
+        for contents in [
+            include_str!("../../assets/behaviors/debug_any_subtree.bht.ron"),
+            include_str!("../../assets/behaviors/market_workflow.bht.ron"),
+        ] {
+            ron::from_str::<BehaviorDocument>(contents).unwrap();
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use crossbeam::channel::{Receiver, TryRecvError};
+
+use crate::asset_files::list_asset_files;
+
+use super::{
+    viewer::BehaviorViewer, BehaviorStatus, BehaviorTree, BEHAVIORS_DIR, BEHAVIOR_EXTENSION,
+};
+
+#[derive(Resource, Debug, Clone)]
+pub struct BehaviorUi {
+    /// Relative to the asset folder.
+    pub path: String,
+    pub files: Vec<String>,
+    pub status: Option<String>,
+    /// Spawns run trees paused, so the viewer can step them from the first tick.
+    pub start_paused: bool,
+    loading: Option<Receiver<Result<BehaviorTree, String>>>,
+}
+
+impl Default for BehaviorUi {
+    fn default() -> Self {
+        Self {
+            path: format!("{}/market_workflow{}", BEHAVIORS_DIR, BEHAVIOR_EXTENSION),
+            files: vec![],
+            status: None,
+            start_paused: false,
+            loading: None,
+        }
+    }
+}
+
+pub fn list_behaviors(asset_server: &AssetServer) -> Vec<String> {
+    list_asset_files(asset_server, BEHAVIORS_DIR, BEHAVIOR_EXTENSION)
+}
+
+/// The tree `loading` resolved to, if it has.
+fn poll_loading(
+    loading: &mut Option<Receiver<Result<BehaviorTree, String>>>,
+) -> Option<Result<BehaviorTree, String>> {
+    let result = match loading.as_ref()?.try_recv() {
+        Ok(result) => result,
+        Err(TryRecvError::Empty) => return None,
+        Err(TryRecvError::Disconnected) => Err("Load dropped".to_string()),
+    };
+    *loading = None;
+    Some(result)
+}
+
+pub fn status_label(status: Option<BehaviorStatus>) -> &'static str {
+    match status {
+        None => "Idle",
+        Some(BehaviorStatus::Running) => "Running",
+        Some(BehaviorStatus::Success) => "Success",
+        Some(BehaviorStatus::Failure) => "Failure",
+    }
+}
+
+pub fn behavior_ui(
+    mut commands: Commands,
+    mut egui_contexts: EguiContexts,
+    asset_server: Res<AssetServer>,
+    mut behavior: ResMut<BehaviorUi>,
+    mut viewer: ResMut<BehaviorViewer>,
+    mut trees: Query<(Entity, &mut BehaviorTree)>,
+) {
+    if let Some(result) = poll_loading(&mut behavior.loading) {
+        behavior.status = Some(match result {
+            Ok(mut tree) => {
+                tree.paused = behavior.start_paused;
+                let status = format!("Started {}", tree.path);
+                let name = Name::new(format!("Behavior: {}", tree.path));
+                viewer.selected = Some(commands.spawn(tree).insert(name).id());
+                status
+            }
+            Err(err) => format!("Load failed: {}", err),
+        });
+    }
+    egui::Window::new("Behavior Trees")
+        .default_open(false)
+        .show(egui_contexts.ctx_mut(), |ui| {
+            if ui.button("Refresh").clicked() {
+                behavior.files = list_behaviors(&asset_server);
+            }
+            for path in behavior.files.clone() {
+                if ui.selectable_label(behavior.path == path, &path).clicked() {
+                    behavior.path = path;
+                }
+            }
+            ui.label("Path (in the asset folder)");
+            ui.text_edit_singleline(&mut behavior.path);
+            ui.horizontal(|ui| {
+                if behavior.loading.is_some() {
+                    ui.add(egui::Spinner::default());
+                } else if ui.button("Run").clicked() {
+                    behavior.loading = Some(BehaviorTree::load(&asset_server, &behavior.path));
+                }
+                ui.checkbox(&mut behavior.start_paused, "Start paused");
+            });
+            if let Some(status) = &behavior.status {
+                ui.label(status);
+            }
+            ui.separator();
+            for (entity, mut tree) in trees.iter_mut() {
+                ui.horizontal(|ui| {
+                    ui.label(&tree.path);
+                    ui.label(status_label(tree.status()));
+                    if ui.button("Reset").clicked() {
+                        tree.reset();
+                    }
+                    if ui.button("Stop").clicked() {
+                        commands.entity(entity).despawn();
+                    }
+                });
+            }
+        });
+}
@@ -0,0 +1,112 @@
+use super::{ui::status_label, BehaviorStatus, BehaviorTree};
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+const RESULT_PREVIEW: usize = 80;
+
+/// Inspects the running trees, whether started from the Behavior Trees window
+/// or by the simulation; it doesn't load trees itself.
+#[derive(Resource, Debug, Default, Clone)]
+pub struct BehaviorViewer {
+    pub selected: Option<Entity>,
+}
+
+fn status_color(status: Option<BehaviorStatus>) -> egui::Color32 {
+    match status {
+        None => egui::Color32::GRAY,
+        Some(BehaviorStatus::Running) => egui::Color32::YELLOW,
+        Some(BehaviorStatus::Success) => egui::Color32::GREEN,
+        Some(BehaviorStatus::Failure) => egui::Color32::RED,
+    }
+}
+
+fn result_ui(ui: &mut egui::Ui, result: &Option<String>) {
+    if let Some(result) = result {
+        let preview: String = result.chars().take(RESULT_PREVIEW).collect();
+        let preview = if preview.len() < result.len() {
+            format!("{}…", preview)
+        } else {
+            preview
+        };
+        ui.label(egui::RichText::new(preview).small().weak())
+            .on_hover_text(result);
+    }
+}
+
+fn node_ui(ui: &mut egui::Ui, entity: Entity, tree: &BehaviorTree, index: usize) {
+    let node = &tree.nodes[index];
+    let text = egui::RichText::new(format!(
+        "{} [{}] {}",
+        node.name,
+        node.behavior.kind(),
+        status_label(node.status)
+    ))
+    .color(status_color(node.status));
+    if node.children.is_empty() {
+        ui.label(text);
+        result_ui(ui, &node.result);
+        return;
+    }
+    egui::CollapsingHeader::new(text)
+        .id_source((entity, index))
+        .default_open(true)
+        .show(ui, |ui| {
+            result_ui(ui, &node.result);
+            for child in node.children.iter() {
+                node_ui(ui, entity, tree, *child);
+            }
+        });
+}
+
+pub fn behavior_viewer_ui(
+    mut commands: Commands,
+    mut egui_contexts: EguiContexts,
+    mut viewer: ResMut<BehaviorViewer>,
+    mut trees: Query<(Entity, &mut BehaviorTree)>,
+) {
+    egui::Window::new("Behavior Tree Viewer")
+        .default_open(false)
+        .show(egui_contexts.ctx_mut(), |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for (entity, tree) in trees.iter() {
+                    let selected = viewer.selected == Some(entity);
+                    if ui.selectable_label(selected, &tree.path).clicked() {
+                        viewer.selected = Some(entity);
+                    }
+                }
+            });
+            let Some(entity) = viewer.selected else {
+                ui.label("No tree selected, run one from Behavior Trees");
+                return;
+            };
+            let Ok((_, mut tree)) = trees.get_mut(entity) else {
+                viewer.selected = None;
+                return;
+            };
+            ui.horizontal(|ui| {
+                let pause = if tree.paused { "Resume" } else { "Pause" };
+                if ui.button(pause).clicked() {
+                    tree.paused = !tree.paused;
+                }
+                if ui
+                    .add_enabled(tree.paused && !tree.finished(), egui::Button::new("Step"))
+                    .clicked()
+                {
+                    tree.step = true;
+                }
+                if ui.button("Reset").clicked() {
+                    tree.reset();
+                }
+                if ui.button("Stop").clicked() {
+                    commands.entity(entity).despawn();
+                    viewer.selected = None;
+                }
+            });
+            ui.separator();
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                if !tree.nodes.is_empty() {
+                    node_ui(ui, entity, &tree, 0);
+                }
+            });
+        });
+}
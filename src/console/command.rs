@@ -0,0 +1,960 @@
+//! Parsing of console lines into API requests.
+//!
+//! A line is a command name followed by `field=value` arguments, or bare
+//! values filling the command's fields in order:
+//! `mint class=1 asset=2 amount=10 to=@bob signer=@alice`.
+
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use sugarfunge_api_types::{
+    account::*,
+    asset::{
+        AssetBalanceInput, AssetBalancesInput, AssetInfoInput, BurnInput, ClassInfoInput,
+        CreateClassInput, CreateInput, MintInput, TransferFromInput, UpdateMetadataInput,
+    },
+    bag::SweepInput,
+    bundle::*,
+    market::*,
+    primitives::{Account, AssetId, Balance, BundleId, ClassId, MarketId, Seed, ValidatorId},
+    validator::*,
+};
+use sugarfunge_client::units::{TOKEN_DECIMALS, TOKEN_UNIT};
+
+use crate::{
+    api::*,
+    debug_ui::links::{AddressBookEntry, DebugUiLinks},
+};
+
+/// Development seeds `signer=@alice` and friends stand for.
+const DEV_SEEDS: &[&str] = &["Alice", "Bob", "Charlie", "Dave", "Eve", "Ferdie"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    /// An account, or `@name` from the address book.
+    Account,
+    /// A seed, or `@alice` for the `//Alice` development seed.
+    Seed,
+    Id,
+    /// A plain integer amount, as the debug UI forms send it.
+    Amount,
+    /// Whole tokens with up to `TOKEN_DECIMALS` decimals, e.g. `10.5`, like
+    /// the debug UI fund form.
+    Tokens,
+    Json,
+    Text,
+}
+
+#[derive(Debug)]
+pub struct Field {
+    pub name: &'static str,
+    pub kind: FieldKind,
+    pub optional: bool,
+}
+
+const fn required(name: &'static str, kind: FieldKind) -> Field {
+    Field {
+        name,
+        kind,
+        optional: false,
+    }
+}
+
+const fn optional(name: &'static str, kind: FieldKind) -> Field {
+    Field {
+        name,
+        kind,
+        optional: true,
+    }
+}
+
+#[derive(Debug)]
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub fields: &'static [Field],
+}
+
+impl CommandSpec {
+    fn field(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// `mint class asset amount to signer`, with optional fields in brackets.
+    pub fn usage(&self) -> String {
+        let mut usage = self.name.to_string();
+        for field in self.fields {
+            if field.optional {
+                usage.push_str(&format!(" [{}]", field.name));
+            } else {
+                usage.push_str(&format!(" {}", field.name));
+            }
+        }
+        usage
+    }
+}
+
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "help",
+        help: "List the commands",
+        fields: &[],
+    },
+    CommandSpec {
+        name: "create-account",
+        help: "Create a new account",
+        fields: &[],
+    },
+    CommandSpec {
+        name: "fund",
+        help: "Fund an account",
+        fields: &[
+            required("to", FieldKind::Account),
+            required("amount", FieldKind::Tokens),
+            required("signer", FieldKind::Seed),
+        ],
+    },
+    CommandSpec {
+        name: "exists",
+        help: "Check that an account exists",
+        fields: &[required("account", FieldKind::Account)],
+    },
+    CommandSpec {
+        name: "seeded",
+        help: "Account of a seed",
+        fields: &[required("seed", FieldKind::Seed)],
+    },
+    CommandSpec {
+        name: "account-balance",
+        help: "Native balance of an account",
+        fields: &[required("account", FieldKind::Account)],
+    },
+    CommandSpec {
+        name: "create-class",
+        help: "Create an asset class",
+        fields: &[
+            required("class", FieldKind::Id),
+            required("owner", FieldKind::Account),
+            required("signer", FieldKind::Seed),
+            optional("metadata", FieldKind::Json),
+        ],
+    },
+    CommandSpec {
+        name: "class-info",
+        help: "Owner and metadata of a class",
+        fields: &[required("class", FieldKind::Id)],
+    },
+    CommandSpec {
+        name: "create-asset",
+        help: "Create an asset in a class",
+        fields: &[
+            required("class", FieldKind::Id),
+            required("asset", FieldKind::Id),
+            required("signer", FieldKind::Seed),
+            optional("metadata", FieldKind::Json),
+        ],
+    },
+    CommandSpec {
+        name: "asset-info",
+        help: "Metadata of an asset",
+        fields: &[
+            required("class", FieldKind::Id),
+            required("asset", FieldKind::Id),
+        ],
+    },
+    CommandSpec {
+        name: "update-metadata",
+        help: "Replace the metadata of an asset",
+        fields: &[
+            required("class", FieldKind::Id),
+            required("asset", FieldKind::Id),
+            required("metadata", FieldKind::Json),
+            required("signer", FieldKind::Seed),
+        ],
+    },
+    CommandSpec {
+        name: "mint",
+        help: "Mint an asset to an account",
+        fields: &[
+            required("class", FieldKind::Id),
+            required("asset", FieldKind::Id),
+            required("amount", FieldKind::Amount),
+            required("to", FieldKind::Account),
+            required("signer", FieldKind::Seed),
+        ],
+    },
+    CommandSpec {
+        name: "burn",
+        help: "Burn an asset from an account",
+        fields: &[
+            required("class", FieldKind::Id),
+            required("asset", FieldKind::Id),
+            required("amount", FieldKind::Amount),
+            required("from", FieldKind::Account),
+            required("signer", FieldKind::Seed),
+        ],
+    },
+    CommandSpec {
+        name: "transfer",
+        help: "Transfer an asset between accounts",
+        fields: &[
+            required("class", FieldKind::Id),
+            required("asset", FieldKind::Id),
+            required("amount", FieldKind::Amount),
+            required("from", FieldKind::Account),
+            required("to", FieldKind::Account),
+            required("signer", FieldKind::Seed),
+        ],
+    },
+    CommandSpec {
+        name: "balance",
+        help: "Balance of one asset, or every asset of an account",
+        fields: &[
+            required("account", FieldKind::Account),
+            optional("class", FieldKind::Id),
+            optional("asset", FieldKind::Id),
+        ],
+    },
+    CommandSpec {
+        name: "sweep-bag",
+        help: "Move a bag's contents to an account",
+        fields: &[
+            required("bag", FieldKind::Account),
+            required("to", FieldKind::Account),
+            required("signer", FieldKind::Seed),
+        ],
+    },
+    CommandSpec {
+        name: "mint-bundle",
+        help: "Mint a bundle",
+        fields: &[
+            required("bundle", FieldKind::Text),
+            required("amount", FieldKind::Amount),
+            required("from", FieldKind::Account),
+            required("to", FieldKind::Account),
+            required("signer", FieldKind::Seed),
+        ],
+    },
+    CommandSpec {
+        name: "burn-bundle",
+        help: "Burn a bundle",
+        fields: &[
+            required("bundle", FieldKind::Text),
+            required("amount", FieldKind::Amount),
+            required("from", FieldKind::Account),
+            required("to", FieldKind::Account),
+            required("signer", FieldKind::Seed),
+        ],
+    },
+    CommandSpec {
+        name: "create-market",
+        help: "Create a market",
+        fields: &[
+            required("market", FieldKind::Id),
+            required("signer", FieldKind::Seed),
+        ],
+    },
+    CommandSpec {
+        name: "deposit",
+        help: "Deposit assets into a market rate",
+        fields: &[
+            required("market", FieldKind::Id),
+            required("rate", FieldKind::Id),
+            required("amount", FieldKind::Amount),
+            required("signer", FieldKind::Seed),
+        ],
+    },
+    CommandSpec {
+        name: "exchange",
+        help: "Exchange assets through a market rate",
+        fields: &[
+            required("market", FieldKind::Id),
+            required("rate", FieldKind::Id),
+            required("amount", FieldKind::Amount),
+            required("signer", FieldKind::Seed),
+        ],
+    },
+    CommandSpec {
+        name: "validators",
+        help: "Current validator set",
+        fields: &[],
+    },
+    CommandSpec {
+        name: "add-validator",
+        help: "Add a validator",
+        fields: &[
+            required("validator", FieldKind::Text),
+            required("signer", FieldKind::Seed),
+        ],
+    },
+    CommandSpec {
+        name: "remove-validator",
+        help: "Remove a validator",
+        fields: &[
+            required("validator", FieldKind::Text),
+            required("signer", FieldKind::Seed),
+        ],
+    },
+    CommandSpec {
+        name: "call",
+        help: "Any endpoint by path with a JSON input, e.g. call bag/create {...}",
+        fields: &[
+            required("endpoint", FieldKind::Text),
+            optional("input", FieldKind::Json),
+        ],
+    },
+];
+
+pub fn command_spec(name: &str) -> Option<&'static CommandSpec> {
+    COMMANDS.iter().find(|spec| spec.name == name)
+}
+
+/// Sends the request it was built for.
+pub type SendRequest = Box<dyn FnOnce(&mut World) + Send + Sync>;
+
+/// A parsed line, ready to send.
+pub struct ConsoleRequest {
+    pub id: RequestId,
+    pub path: &'static str,
+    pub send: SendRequest,
+}
+
+pub enum Parsed {
+    /// Blank lines and `#` comments.
+    Nothing,
+    Help,
+    Request(ConsoleRequest),
+}
+
+fn send<E: Endpoint>(input: E::Input) -> Result<Parsed, String> {
+    let request = ApiRequest::<E>::new(input);
+    Ok(Parsed::Request(ConsoleRequest {
+        id: request.id,
+        path: E::PATH,
+        send: Box::new(move |world: &mut World| world.send_event(request)),
+    }))
+}
+
+/// Lowercase with dashes for spaces, so "Account 1" is `@account-1`.
+pub fn handle(name: &str) -> String {
+    name.trim()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn address_book_entry<'a>(links: &'a DebugUiLinks, name: &str) -> Option<&'a AddressBookEntry> {
+    links
+        .address_book
+        .iter()
+        .find(|entry| handle(&entry.name) == handle(name))
+}
+
+/// Splits on whitespace outside quotes and JSON brackets, so
+/// `metadata={"name": "Gold"}` stays one token.
+pub fn tokenize(line: &str) -> Result<Vec<String>, String> {
+    let mut tokens = vec![];
+    let mut token = String::new();
+    let mut depth = 0usize;
+    let mut quoted = false;
+    let mut escaped = false;
+    for c in line.chars() {
+        if quoted {
+            token.push(c);
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => quoted = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => quoted = true,
+            '{' | '[' => depth += 1,
+            '}' | ']' => depth = depth.saturating_sub(1),
+            c if c.is_whitespace() && depth == 0 => {
+                if !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token));
+                }
+                continue;
+            }
+            _ => {}
+        }
+        token.push(c);
+    }
+    if quoted {
+        return Err("Unterminated quote".to_string());
+    }
+    if depth > 0 {
+        return Err("Unclosed bracket".to_string());
+    }
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// `name=value` when `name` looks like a field name.
+fn split_named(token: &str) -> Option<(&str, &str)> {
+    let (name, value) = token.split_once('=')?;
+    (!name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+        .then_some((name, value))
+}
+
+/// Parses a plain integer amount.
+pub fn parse_amount(value: &str) -> Result<Balance, String> {
+    value
+        .parse::<u128>()
+        .map(Balance::from)
+        .map_err(|_| format!("`{}` is not an amount", value))
+}
+
+/// Parses whole tokens, e.g. `10.5`, into balance units.
+pub fn parse_tokens(value: &str) -> Result<Balance, String> {
+    let invalid = || format!("`{}` is not an amount", value);
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    if whole.is_empty() && fraction.is_empty()
+        || !whole
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+    if fraction.len() > TOKEN_DECIMALS as usize {
+        return Err(format!(
+            "`{}` has more than {} decimals",
+            value, TOKEN_DECIMALS
+        ));
+    }
+    let whole: u128 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().map_err(|_| invalid())?
+    };
+    let fraction: u128 = format!("{:0<width$}", fraction, width = TOKEN_DECIMALS as usize)
+        .parse()
+        .map_err(|_| invalid())?;
+    whole
+        .checked_mul(TOKEN_UNIT)
+        .and_then(|units| units.checked_add(fraction))
+        .map(Balance::from)
+        .ok_or_else(|| format!("`{}` is too large", value))
+}
+
+/// Arguments of one command line, resolved as each getter asks for them.
+struct Args<'a> {
+    spec: &'static CommandSpec,
+    values: Vec<(&'static str, String)>,
+    links: &'a DebugUiLinks,
+}
+
+impl<'a> Args<'a> {
+    fn parse(
+        spec: &'static CommandSpec,
+        tokens: &[String],
+        links: &'a DebugUiLinks,
+    ) -> Result<Self, String> {
+        let mut values: Vec<(&'static str, String)> = vec![];
+        let mut positional = vec![];
+        for token in tokens {
+            match split_named(token) {
+                Some((name, value)) => {
+                    let field = spec.field(name).ok_or_else(|| {
+                        format!(
+                            "`{}` has no field `{}`; usage: {}",
+                            spec.name,
+                            name,
+                            spec.usage()
+                        )
+                    })?;
+                    if values.iter().any(|(other, _)| *other == field.name) {
+                        return Err(format!("`{}=` given more than once", name));
+                    }
+                    values.push((field.name, value.to_string()));
+                }
+                None => positional.push(token.clone()),
+            }
+        }
+        let mut free = spec
+            .fields
+            .iter()
+            .filter(|field| !values.iter().any(|(name, _)| *name == field.name))
+            .collect::<Vec<_>>()
+            .into_iter();
+        for value in positional {
+            let field = free
+                .next()
+                .ok_or_else(|| format!("Unexpected `{}`; usage: {}", value, spec.usage()))?;
+            values.push((field.name, value));
+        }
+        Ok(Self {
+            spec,
+            values,
+            links,
+        })
+    }
+
+    fn raw(&self, name: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|(other, _)| *other == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn missing(&self, name: &str) -> String {
+        format!("Missing `{}=`; usage: {}", name, self.spec.usage())
+    }
+
+    fn string(&self, name: &str) -> Result<String, String> {
+        self.raw(name)
+            .map(unquote)
+            .ok_or_else(|| self.missing(name))
+    }
+
+    fn account(&self, name: &str) -> Result<Account, String> {
+        let value = self.string(name)?;
+        match value.strip_prefix('@') {
+            Some(handle) => address_book_entry(self.links, handle)
+                .map(|entry| entry.account.clone())
+                .ok_or_else(|| format!("No address book entry named @{}", handle)),
+            None => Ok(Account::from(value)),
+        }
+    }
+
+    fn seed(&self, name: &str) -> Result<Seed, String> {
+        let value = self.string(name)?;
+        match value.strip_prefix('@') {
+            Some(handle) => DEV_SEEDS
+                .iter()
+                .find(|dev| dev.eq_ignore_ascii_case(handle))
+                .map(|dev| Seed::from(format!("//{}", dev)))
+                .ok_or_else(|| format!("No development seed named @{}", handle)),
+            None => Ok(Seed::from(value)),
+        }
+    }
+
+    fn optional_u64(&self, name: &str) -> Result<Option<u64>, String> {
+        self.raw(name)
+            .map(|value| {
+                unquote(value)
+                    .parse()
+                    .map_err(|_| format!("`{}=` expects a number, got `{}`", name, value))
+            })
+            .transpose()
+    }
+
+    fn u64(&self, name: &str) -> Result<u64, String> {
+        self.optional_u64(name)?.ok_or_else(|| self.missing(name))
+    }
+
+    fn class_id(&self) -> Result<ClassId, String> {
+        self.u64("class").map(ClassId::from)
+    }
+
+    fn asset_id(&self) -> Result<AssetId, String> {
+        self.u64("asset").map(AssetId::from)
+    }
+
+    fn market_id(&self, name: &str) -> Result<MarketId, String> {
+        self.u64(name).map(MarketId::from)
+    }
+
+    fn amount(&self) -> Result<Balance, String> {
+        parse_amount(&self.string("amount")?)
+    }
+
+    fn tokens(&self) -> Result<Balance, String> {
+        parse_tokens(&self.string("amount")?)
+    }
+
+    /// Defaults to `{}` like the debug UI metadata fields.
+    fn metadata(&self) -> Result<serde_json::Value, String> {
+        match self.raw("metadata") {
+            Some(metadata) => serde_json::from_str(metadata)
+                .map_err(|err| format!("Invalid `metadata=`: {}", err)),
+            None => Ok(serde_json::json!({})),
+        }
+    }
+}
+
+fn unquote(value: &str) -> String {
+    match value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    {
+        Some(inner) => inner.replace("\\\"", "\""),
+        None => value.to_string(),
+    }
+}
+
+fn from_json<T: DeserializeOwned>(input: Option<&str>) -> Result<T, String> {
+    serde_json::from_str(input.unwrap_or("null")).map_err(|err| format!("Invalid input: {}", err))
+}
+
+macro_rules! console_endpoints {
+    ($($name:ident),* $(,)?) => {
+        /// Paths `call` accepts.
+        pub const ENDPOINT_PATHS: &[&str] = &[$(<$name as Endpoint>::PATH),*];
+
+        fn call(path: &str, input: Option<&str>) -> Result<Parsed, String> {
+            $(
+                if path == <$name as Endpoint>::PATH {
+                    return send::<$name>(from_json(input)?);
+                }
+            )*
+            Err(format!("Unknown endpoint `{}`", path))
+        }
+
+        /// Records the responses of every endpoint in the console.
+        pub(super) fn add_response_systems(app: &mut App) {
+            $(app.add_system(super::console_responses::<$name>);)*
+        }
+    };
+}
+
+console_endpoints! {
+    CreateAccount, FundAccount, AccountExists, SeededAccount, AccountBalance,
+    CreateClass, ClassInfo,
+    CreateAsset, AssetInfo, UpdateAssetMetadata, MintAsset, BurnAsset, AssetBalance,
+    AssetBalances, TransferFrom,
+    RegisterBag, CreateBag, SweepBag, DepositBag,
+    RegisterBundle, MintBundle, BurnBundle,
+    CreateMarket, CreateMarketRate, DepositAssets, ExchangeAssets,
+    AddValidator, RemoveValidator, Validators,
+}
+
+pub fn parse(line: &str, links: &DebugUiLinks) -> Result<Parsed, String> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(Parsed::Nothing);
+    }
+    let tokens = tokenize(line)?;
+    let spec = command_spec(&tokens[0])
+        .ok_or_else(|| format!("Unknown command `{}`; try `help`", tokens[0]))?;
+    let args = Args::parse(spec, &tokens[1..], links)?;
+    match spec.name {
+        "help" => Ok(Parsed::Help),
+        "create-account" => send::<CreateAccount>(()),
+        "fund" => send::<FundAccount>(FundAccountInput {
+            seed: args.seed("signer")?,
+            to: args.account("to")?,
+            amount: args.tokens()?,
+        }),
+        "exists" => send::<AccountExists>(AccountExistsInput {
+            account: args.account("account")?,
+        }),
+        "seeded" => send::<SeededAccount>(SeededAccountInput {
+            seed: args.seed("seed")?,
+        }),
+        "account-balance" => send::<AccountBalance>(AccountBalanceInput {
+            account: args.account("account")?,
+        }),
+        "create-class" => send::<CreateClass>(CreateClassInput {
+            seed: args.seed("signer")?,
+            owner: args.account("owner")?,
+            class_id: args.class_id()?,
+            metadata: args.metadata()?,
+        }),
+        "class-info" => send::<ClassInfo>(ClassInfoInput {
+            class_id: args.class_id()?,
+        }),
+        "create-asset" => send::<CreateAsset>(CreateInput {
+            seed: args.seed("signer")?,
+            class_id: args.class_id()?,
+            asset_id: args.asset_id()?,
+            metadata: args.metadata()?,
+        }),
+        "asset-info" => send::<AssetInfo>(AssetInfoInput {
+            class_id: args.class_id()?,
+            asset_id: args.asset_id()?,
+        }),
+        "update-metadata" => send::<UpdateAssetMetadata>(UpdateMetadataInput {
+            seed: args.seed("signer")?,
+            class_id: args.class_id()?,
+            asset_id: args.asset_id()?,
+            metadata: args.metadata()?,
+        }),
+        "mint" => send::<MintAsset>(MintInput {
+            seed: args.seed("signer")?,
+            to: args.account("to")?,
+            class_id: args.class_id()?,
+            asset_id: args.asset_id()?,
+            amount: args.amount()?,
+        }),
+        "burn" => send::<BurnAsset>(BurnInput {
+            seed: args.seed("signer")?,
+            from: args.account("from")?,
+            class_id: args.class_id()?,
+            asset_id: args.asset_id()?,
+            amount: args.amount()?,
+        }),
+        "transfer" => send::<TransferFrom>(TransferFromInput {
+            seed: args.seed("signer")?,
+            from: args.account("from")?,
+            to: args.account("to")?,
+            class_id: args.class_id()?,
+            asset_id: args.asset_id()?,
+            amount: args.amount()?,
+        }),
+        "balance" => {
+            let account = args.account("account")?;
+            let class_id = args.optional_u64("class")?.map(ClassId::from);
+            match (class_id, args.optional_u64("asset")?.map(AssetId::from)) {
+                (Some(class_id), Some(asset_id)) => send::<AssetBalance>(AssetBalanceInput {
+                    account,
+                    class_id,
+                    asset_id,
+                }),
+                (None, Some(_)) => Err("`asset=` needs `class=`".to_string()),
+                (class_id, None) => send::<AssetBalances>(AssetBalancesInput { account, class_id }),
+            }
+        }
+        "sweep-bag" => send::<SweepBag>(SweepInput {
+            seed: args.seed("signer")?,
+            bag: args.account("bag")?,
+            to: args.account("to")?,
+        }),
+        "mint-bundle" => send::<MintBundle>(MintBundleInput {
+            seed: args.seed("signer")?,
+            from: args.account("from")?,
+            to: args.account("to")?,
+            bundle_id: BundleId::from(args.string("bundle")?),
+            amount: args.amount()?,
+        }),
+        "burn-bundle" => send::<BurnBundle>(BurnBundleInput {
+            seed: args.seed("signer")?,
+            from: args.account("from")?,
+            to: args.account("to")?,
+            bundle_id: BundleId::from(args.string("bundle")?),
+            amount: args.amount()?,
+        }),
+        "create-market" => send::<CreateMarket>(CreateMarketInput {
+            seed: args.seed("signer")?,
+            market_id: args.market_id("market")?,
+        }),
+        "deposit" => send::<DepositAssets>(DepositAssetsInput {
+            seed: args.seed("signer")?,
+            market_id: args.market_id("market")?,
+            market_rate_id: args.market_id("rate")?,
+            amount: args.amount()?,
+        }),
+        "exchange" => send::<ExchangeAssets>(ExchangeAssetsInput {
+            seed: args.seed("signer")?,
+            market_id: args.market_id("market")?,
+            market_rate_id: args.market_id("rate")?,
+            amount: args.amount()?,
+        }),
+        "validators" => send::<Validators>(()),
+        "add-validator" => send::<AddValidator>(AddValidatorInput {
+            seed: args.seed("signer")?,
+            validator_id: ValidatorId::from(args.string("validator")?),
+        }),
+        "remove-validator" => send::<RemoveValidator>(RemoveValidatorInput {
+            seed: args.seed("signer")?,
+            validator_id: ValidatorId::from(args.string("validator")?),
+        }),
+        "call" => call(&args.string("endpoint")?, args.raw("input")),
+        _ => unreachable!("every command in COMMANDS is handled"),
+    }
+}
+
+/// Tab completion of the word under the end of `line`.
+pub struct Completion {
+    /// `line` with the word completed as far as the candidates agree.
+    pub line: String,
+    pub candidates: Vec<String>,
+}
+
+pub fn complete(line: &str, links: &DebugUiLinks) -> Completion {
+    let start = line
+        .rfind(char::is_whitespace)
+        .map(|i| i + 1)
+        .unwrap_or_default();
+    let (head, word) = line.split_at(start);
+    let first = head.trim().is_empty();
+    let (prefix, partial, candidates): (&str, &str, Vec<String>) = if first {
+        let names = COMMANDS.iter().map(|spec| spec.name.to_string()).collect();
+        ("", word, names)
+    } else {
+        let spec = head.split_whitespace().next().and_then(command_spec);
+        let (name, value) = split_named(word).unwrap_or(("", word));
+        let kind = spec
+            .and_then(|spec| spec.field(name))
+            .map(|field| field.kind);
+        let value_start = word.len() - value.len();
+        if value.starts_with('@') {
+            let handles = match kind {
+                Some(FieldKind::Seed) => DEV_SEEDS.iter().map(|dev| handle_of(dev)).collect(),
+                _ => links
+                    .address_book
+                    .iter()
+                    .map(|entry| handle_of(&entry.name))
+                    .collect(),
+            };
+            (&word[..value_start], value, handles)
+        } else if spec.map(|spec| spec.name) == Some("call") && name.is_empty() {
+            let paths = ENDPOINT_PATHS.iter().map(|path| path.to_string()).collect();
+            ("", word, paths)
+        } else if name.is_empty() {
+            let given: Vec<&str> = head
+                .split_whitespace()
+                .skip(1)
+                .filter_map(|token| split_named(token).map(|(name, _)| name))
+                .collect();
+            let fields = spec
+                .map(|spec| {
+                    spec.fields
+                        .iter()
+                        .filter(|field| !given.contains(&field.name))
+                        .map(|field| format!("{}=", field.name))
+                        .collect()
+                })
+                .unwrap_or_default();
+            ("", word, fields)
+        } else {
+            ("", word, vec![])
+        }
+    };
+    let candidates: Vec<String> = candidates
+        .into_iter()
+        .filter(|candidate| candidate.starts_with(partial))
+        .collect();
+    let completed = match candidates.as_slice() {
+        [] => word.to_string(),
+        [only] if only.ends_with('=') => format!("{}{}", prefix, only),
+        [only] => format!("{}{} ", prefix, only),
+        _ => format!("{}{}", prefix, common_prefix(&candidates)),
+    };
+    Completion {
+        line: format!("{}{}", head, completed),
+        candidates,
+    }
+}
+
+fn handle_of(name: &str) -> String {
+    format!("@{}", handle(name))
+}
+
+fn common_prefix(candidates: &[String]) -> String {
+    let first = &candidates[0];
+    let mut len = first.len();
+    for candidate in &candidates[1..] {
+        len = first
+            .char_indices()
+            .zip(candidate.chars())
+            .take_while(|((_, a), b)| a == b)
+            .map(|((i, a), _)| i + a.len_utf8())
+            .last()
+            .unwrap_or_default()
+            .min(len);
+    }
+    first[..len].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn links() -> DebugUiLinks {
+        DebugUiLinks {
+            address_book: vec![AddressBookEntry {
+                name: "Bob Wallet".to_string(),
+                account: Account::from("5Bob".to_string()),
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn units(balance: Result<Balance, String>) -> Result<u128, String> {
+        balance.map(u128::from)
+    }
+
+    #[test]
+    fn amounts_are_plain_integers() {
+        assert_eq!(units(parse_amount("10")), Ok(10));
+        assert_eq!(units(parse_amount("0")), Ok(0));
+        assert!(parse_amount("10.5").is_err());
+        assert!(parse_amount("").is_err());
+        assert!(parse_amount("-1").is_err());
+        assert!(parse_amount("340282366920938463463374607431768211456").is_err());
+    }
+
+    #[test]
+    fn tokens_are_scaled() {
+        assert_eq!(units(parse_tokens("2")), Ok(2 * TOKEN_UNIT));
+        assert_eq!(
+            units(parse_tokens("10.5")),
+            Ok(10 * TOKEN_UNIT + TOKEN_UNIT / 2)
+        );
+        assert_eq!(units(parse_tokens(".5")), Ok(TOKEN_UNIT / 2));
+        assert_eq!(units(parse_tokens("0.000000000000000001")), Ok(1));
+        assert_eq!(
+            units(parse_tokens("0.0000000000000000001")),
+            Err("`0.0000000000000000001` has more than 18 decimals".to_string())
+        );
+        assert!(parse_tokens("").is_err());
+        assert!(parse_tokens(".").is_err());
+        assert!(parse_tokens("1.2.3").is_err());
+        assert!(parse_tokens("1e3").is_err());
+        assert_eq!(
+            units(parse_tokens("340282366920938463464")),
+            Err("`340282366920938463464` is too large".to_string())
+        );
+    }
+
+    #[test]
+    fn tokenize_keeps_quotes_and_json_together() {
+        assert_eq!(
+            tokenize(r#"create-class 1  metadata={"name": "Gold Coin"} owner="a b""#),
+            Ok(vec![
+                "create-class".to_string(),
+                "1".to_string(),
+                r#"metadata={"name": "Gold Coin"}"#.to_string(),
+                r#"owner="a b""#.to_string(),
+            ])
+        );
+        assert_eq!(
+            tokenize(r#"call x {"a": ["]", "\"}"]}"#),
+            Ok(vec![
+                "call".to_string(),
+                "x".to_string(),
+                r#"{"a": ["]", "\"}"]}"#.to_string(),
+            ])
+        );
+        assert_eq!(tokenize("   "), Ok(vec![]));
+        assert_eq!(
+            tokenize(r#"seeded "//Alice"#),
+            Err("Unterminated quote".to_string())
+        );
+        assert_eq!(
+            tokenize("call x {\"a\": [1}"),
+            Err("Unclosed bracket".to_string())
+        );
+    }
+
+    #[test]
+    fn complete_commands_fields_and_handles() {
+        let links = links();
+        let completion = complete("exc", &links);
+        assert_eq!(completion.line, "exchange ");
+        let completion = complete("cre", &links);
+        assert_eq!(completion.line, "create-");
+        assert_eq!(completion.candidates.len(), 4);
+        assert_eq!(
+            complete("mint class=1 as", &links).line,
+            "mint class=1 asset="
+        );
+        assert_eq!(
+            complete("mint class=1 class", &links).candidates,
+            Vec::<String>::new()
+        );
+        assert_eq!(complete("mint to=@b", &links).line, "mint to=@bob-wallet ");
+        assert_eq!(
+            complete("mint signer=@al", &links).line,
+            "mint signer=@alice "
+        );
+        assert_eq!(complete("call asset/m", &links).line, "call asset/mint ");
+    }
+}
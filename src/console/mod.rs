@@ -0,0 +1,432 @@
+//! Text command console for power users, e.g.
+//! `mint class=1 asset=2 amount=10 to=@bob signer=@alice`.
+//!
+//! Commands go out as `ApiRequest`s like the debug UI forms, and the console
+//! shows their responses. The command history can be saved as a script, and
+//! scripts can be run back one line at a time.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use serde::Serialize;
+
+use crate::{
+    api::{ApiCompleted, ApiFailed, Endpoint, RequestId},
+    debug_ui::{debug_ui, links::DebugUiLinks},
+};
+
+pub mod command;
+
+use command::{Parsed, COMMANDS};
+
+/// File the history is saved to by "Save Script", and read by "Load", on
+/// native builds.
+#[cfg(not(target_arch = "wasm32"))]
+pub const CONSOLE_SCRIPT_PATH: &str = "console_script.txt";
+
+const MAX_ENTRIES: usize = 200;
+
+#[derive(Debug)]
+pub enum ConsoleStatus {
+    /// Waiting on a request to this endpoint path.
+    Pending(&'static str),
+    /// Pretty JSON of the response.
+    Completed(String),
+    Failed(String),
+    Info(String),
+}
+
+#[derive(Debug)]
+pub struct ConsoleEntry {
+    pub command: String,
+    /// The request the command sent, if any.
+    pub request: Option<RequestId>,
+    pub status: ConsoleStatus,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct Console {
+    pub open: bool,
+    pub line: String,
+    pub entries: Vec<ConsoleEntry>,
+    /// Lines entered, oldest first.
+    pub history: Vec<String>,
+    browsing: Option<usize>,
+    candidates: Vec<String>,
+    focus_line: bool,
+    /// Script pasted or loaded into the "Run Script" editor.
+    pub script_input: String,
+    /// Lines of the running script not sent yet.
+    script_queue: VecDeque<String>,
+    /// Request of the script line being waited on.
+    script_waiting: Option<RequestId>,
+}
+
+impl Console {
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.focus_line = self.open;
+    }
+
+    fn push(&mut self, command: String, request: Option<RequestId>, status: ConsoleStatus) {
+        self.entries.push(ConsoleEntry {
+            command,
+            request,
+            status,
+        });
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+    }
+
+    /// One command per line, with a `#` comment header the parser skips.
+    pub fn script(&self) -> String {
+        let mut script = "# sf-wasm-app console script\n".to_string();
+        for line in self.history.iter() {
+            script.push_str(line);
+            script.push('\n');
+        }
+        script
+    }
+
+    /// Runs `line`, returning the request it sent, or `Err` if it couldn't be
+    /// parsed.
+    fn run(
+        &mut self,
+        line: String,
+        links: &DebugUiLinks,
+        commands: &mut Commands,
+    ) -> Result<Option<RequestId>, ()> {
+        if line.trim().is_empty() {
+            return Ok(None);
+        }
+        if self.history.last() != Some(&line) {
+            self.history.push(line.clone());
+        }
+        self.browsing = None;
+        match command::parse(&line, links) {
+            Ok(Parsed::Nothing) => Ok(None),
+            Ok(Parsed::Help) => {
+                let help = COMMANDS
+                    .iter()
+                    .map(|spec| format!("{}  —  {}", spec.usage(), spec.help))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                self.push(line, None, ConsoleStatus::Info(help));
+                Ok(None)
+            }
+            Ok(Parsed::Request(request)) => {
+                commands.add(request.send);
+                self.push(line, Some(request.id), ConsoleStatus::Pending(request.path));
+                Ok(Some(request.id))
+            }
+            Err(err) => {
+                self.push(line, None, ConsoleStatus::Failed(err));
+                Err(())
+            }
+        }
+    }
+
+    pub fn script_running(&self) -> bool {
+        !self.script_queue.is_empty() || self.script_waiting.is_some()
+    }
+
+    /// Runs `script` one line at a time, each once the previous line's
+    /// response arrived, stopping at the first failure.
+    pub fn run_script(&mut self, script: &str) {
+        self.script_queue = script
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect();
+        self.script_waiting = None;
+    }
+
+    pub fn stop_script(&mut self, reason: &str) {
+        if self.script_queue.is_empty() {
+            self.script_waiting = None;
+            return;
+        }
+        let skipped = self.script_queue.len();
+        self.script_queue.clear();
+        self.script_waiting = None;
+        self.push(
+            "# script".to_string(),
+            None,
+            ConsoleStatus::Info(format!("{}; {} line(s) not run", reason, skipped)),
+        );
+    }
+
+    /// Sends the next script line once the previous one has its response.
+    fn step_script(&mut self, links: &DebugUiLinks, commands: &mut Commands) {
+        if let Some(id) = self.script_waiting {
+            let status = self
+                .entries
+                .iter()
+                .find(|entry| entry.request == Some(id))
+                .map(|entry| &entry.status);
+            match status {
+                Some(ConsoleStatus::Pending(_)) => return,
+                Some(ConsoleStatus::Failed(_)) => {
+                    self.stop_script("Script stopped at a failed request");
+                    return;
+                }
+                _ => self.script_waiting = None,
+            }
+        }
+        let Some(line) = self.script_queue.pop_front() else {
+            return;
+        };
+        match self.run(line, links, commands) {
+            Ok(request) => self.script_waiting = request,
+            Err(()) => self.stop_script("Script stopped at an invalid line"),
+        }
+    }
+
+    /// Steps through the history with Up (`-1`) and Down (`1`).
+    fn browse(&mut self, offset: isize) {
+        if self.history.is_empty() {
+            return;
+        }
+        let last = self.history.len() - 1;
+        self.browsing = match (self.browsing, offset < 0) {
+            (None, true) => Some(last),
+            (None, false) => None,
+            (Some(i), true) => Some(i.saturating_sub(1)),
+            (Some(i), false) if i < last => Some(i + 1),
+            (Some(_), false) => None,
+        };
+        self.line = self
+            .browsing
+            .map(|i| self.history[i].clone())
+            .unwrap_or_default();
+    }
+}
+
+fn line_id() -> egui::Id {
+    egui::Id::new("console_line")
+}
+
+fn entry_ui(ui: &mut egui::Ui, entry: &ConsoleEntry) {
+    ui.label(egui::RichText::new(format!("> {}", entry.command)).monospace());
+    match &entry.status {
+        ConsoleStatus::Pending(path) => {
+            ui.horizontal(|ui| {
+                ui.add(egui::Spinner::default());
+                match entry.request {
+                    Some(id) => ui.weak(format!("{} {}", path, id)),
+                    None => ui.weak(*path),
+                };
+            });
+        }
+        ConsoleStatus::Completed(output) | ConsoleStatus::Info(output) => {
+            ui.label(egui::RichText::new(output).monospace());
+        }
+        ConsoleStatus::Failed(err) => {
+            ui.colored_label(egui::Color32::RED, err);
+        }
+    }
+}
+
+pub fn console_ui(
+    mut ctx: EguiContexts,
+    mut console: ResMut<Console>,
+    links: Res<DebugUiLinks>,
+    mut commands: Commands,
+) {
+    if !console.open {
+        return;
+    }
+    let ctx = ctx.ctx_mut();
+    let console = &mut *console;
+    let focused = ctx.memory(|memory| memory.has_focus(line_id()));
+    let (tab, up, down) = if focused {
+        ctx.input_mut(|input| {
+            (
+                input.consume_key(egui::Modifiers::NONE, egui::Key::Tab),
+                input.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                input.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+            )
+        })
+    } else {
+        (false, false, false)
+    };
+    let mut move_cursor = false;
+    if tab {
+        let completion = command::complete(&console.line, &links);
+        console.line = completion.line;
+        console.candidates = completion.candidates;
+        move_cursor = true;
+    }
+    if up || down {
+        console.browse(if up { -1 } else { 1 });
+        move_cursor = true;
+    }
+
+    let mut open = console.open;
+    egui::Window::new("Console")
+        .open(&mut open)
+        .default_width(480.0)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if ui
+                    .button("Copy Script")
+                    .on_hover_text("Copy the command history, one command per line")
+                    .clicked()
+                {
+                    ui.output_mut(|output| output.copied_text = console.script());
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                if ui
+                    .button("Save Script")
+                    .on_hover_text(CONSOLE_SCRIPT_PATH)
+                    .clicked()
+                {
+                    if let Err(err) = std::fs::write(CONSOLE_SCRIPT_PATH, console.script()) {
+                        warn!("Failed to save {}: {}", CONSOLE_SCRIPT_PATH, err);
+                    }
+                }
+                if ui.button("Clear").clicked() {
+                    console.entries.clear();
+                }
+            });
+            ui.separator();
+            egui::ScrollArea::vertical()
+                .max_height(320.0)
+                .stick_to_bottom(true)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    for entry in console.entries.iter() {
+                        entry_ui(ui, entry);
+                    }
+                });
+            ui.separator();
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut console.line)
+                    .id(line_id())
+                    .font(egui::TextStyle::Monospace)
+                    .hint_text("help")
+                    .desired_width(f32::INFINITY),
+            );
+            if response.changed() {
+                console.candidates.clear();
+            }
+            if response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter)) {
+                let line = std::mem::take(&mut console.line);
+                console.candidates.clear();
+                console.run(line, &links, &mut commands).ok();
+                console.focus_line = true;
+            }
+            if console.focus_line {
+                response.request_focus();
+                console.focus_line = false;
+            }
+            if move_cursor {
+                if let Some(mut state) = egui::text_edit::TextEditState::load(ui.ctx(), line_id()) {
+                    let end = egui::text::CCursor::new(console.line.chars().count());
+                    state.set_ccursor_range(Some(egui::text::CCursorRange::one(end)));
+                    state.store(ui.ctx(), line_id());
+                }
+            }
+            if console.candidates.len() > 1 {
+                ui.weak(console.candidates.join("  "));
+            }
+            egui::CollapsingHeader::new("Run Script").show(ui, |ui| {
+                ui.add(
+                    egui::TextEdit::multiline(&mut console.script_input)
+                        .font(egui::TextStyle::Monospace)
+                        .hint_text("One command per line; # starts a comment")
+                        .desired_rows(4)
+                        .desired_width(f32::INFINITY),
+                );
+                ui.horizontal(|ui| {
+                    if console.script_running() {
+                        ui.add(egui::Spinner::default());
+                        if ui.button("Stop").clicked() {
+                            console.stop_script("Script stopped");
+                        }
+                    } else {
+                        if ui
+                            .add_enabled(
+                                !console.script_input.trim().is_empty(),
+                                egui::Button::new("Run"),
+                            )
+                            .on_hover_text("Run each line once the previous one has a response")
+                            .clicked()
+                        {
+                            let script = console.script_input.clone();
+                            console.run_script(&script);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if ui
+                            .button("Load")
+                            .on_hover_text(CONSOLE_SCRIPT_PATH)
+                            .clicked()
+                        {
+                            match std::fs::read_to_string(CONSOLE_SCRIPT_PATH) {
+                                Ok(script) => console.script_input = script,
+                                Err(err) => {
+                                    warn!("Failed to load {}: {}", CONSOLE_SCRIPT_PATH, err)
+                                }
+                            }
+                        }
+                    }
+                });
+            });
+        });
+    console.open = open;
+}
+
+/// Sends the lines of a running script, whether or not the console is open.
+pub fn run_console_script(
+    mut console: ResMut<Console>,
+    links: Res<DebugUiLinks>,
+    mut commands: Commands,
+) {
+    if console.script_running() {
+        console.step_script(&links, &mut commands);
+    }
+}
+
+/// Fills in the console entries waiting on `E`.
+pub fn console_responses<E: Endpoint>(
+    mut console: ResMut<Console>,
+    mut completed: EventReader<ApiCompleted<E>>,
+    mut failed: EventReader<ApiFailed<E>>,
+) where
+    E::Output: Serialize,
+{
+    let responses = completed
+        .iter()
+        .map(|event| {
+            let output =
+                serde_json::to_string_pretty(&*event.output).unwrap_or_else(|err| err.to_string());
+            (event.id, ConsoleStatus::Completed(output))
+        })
+        .chain(
+            failed
+                .iter()
+                .map(|event| (event.id, ConsoleStatus::Failed(event.error.to_string()))),
+        )
+        .collect::<Vec<_>>();
+    for (id, status) in responses {
+        if let Some(entry) = console.entries.iter_mut().find(|entry| {
+            entry.request == Some(id) && matches!(entry.status, ConsoleStatus::Pending(_))
+        }) {
+            entry.status = status;
+        }
+    }
+}
+
+pub struct ConsolePlugin;
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Console>()
+            .add_system(console_ui.after(debug_ui))
+            .add_system(run_console_script.after(console_ui));
+        command::add_response_systems(app);
+    }
+}
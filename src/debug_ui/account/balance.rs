@@ -1,56 +1,21 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use crossbeam::channel;
-use sugarfunge_api_types::{
-    account::{AccountBalanceInput, AccountBalanceOutput},
-    primitives::Account,
-};
+use serde::{Deserialize, Serialize};
+use sugarfunge_api_types::{account::AccountBalanceInput, primitives::Account};
 
-use crate::{prelude::*, util::request_handler};
+use crate::{
+    api::{AccountBalance, ApiEvents},
+    debug_ui::export::{form_input, RequestForm},
+    shortcuts::submit_button,
+};
 
 use super::AccountUi;
 
-#[derive(Debug)]
-pub struct AccountBalanceRequest {
-    pub input: AccountBalanceInput,
-}
-
-impl Request<AccountBalanceInput> for AccountBalanceRequest {
-    fn endpoint(&self) -> &str {
-        "account/balance"
-    }
-
-    fn input(&self) -> Option<AccountBalanceInput> {
-        Some(AccountBalanceInput {
-            account: self.input.account.clone(),
-        })
-    }
-}
-
-#[derive(Resource)]
-pub struct AccountBalanceChannel {
-    pub input_tx: InputSender<AccountBalanceRequest>,
-    pub input_rx: InputReceiver<AccountBalanceRequest>,
-    pub output_tx: OutputSender<AccountBalanceOutput>,
-    pub output_rx: OutputReceiver<AccountBalanceOutput>,
-}
-
-impl Default for AccountBalanceChannel {
-    fn default() -> Self {
-        let (input_tx, input_rx) = channel::unbounded::<AccountBalanceRequest>();
-        let (output_tx, output_rx) = channel::unbounded::<Option<AccountBalanceOutput>>();
-        Self {
-            input_tx: InputSender(input_tx),
-            input_rx: InputReceiver(input_rx),
-            output_tx: OutputSender(output_tx),
-            output_rx: OutputReceiver(output_rx),
-        }
-    }
-}
-
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AccountBalanceInputData {
     pub account: Account,
+    #[serde(skip)]
     pub loading: bool,
 }
 
@@ -63,6 +28,21 @@ impl Default for AccountBalanceInputData {
     }
 }
 
+impl RequestForm for AccountBalanceInputData {
+    type Endpoint = AccountBalance;
+
+    fn input(&self) -> Result<AccountBalanceInput, String> {
+        Ok(AccountBalanceInput {
+            account: self.account.clone(),
+        })
+    }
+
+    fn set_input(&mut self, input: AccountBalanceInput) -> Result<(), String> {
+        self.account = input.account;
+        Ok(())
+    }
+}
+
 pub fn account_balance_ui(ui: &mut egui::Ui, account: &mut ResMut<AccountUi>) {
     ui.label("Account Balance");
     ui.separator();
@@ -74,19 +54,11 @@ pub fn account_balance_ui(ui: &mut egui::Ui, account: &mut ResMut<AccountUi>) {
         ui.separator();
         ui.add(egui::Spinner::default());
     } else {
-        if ui.button("Check").clicked() {
-            account
-                .channels
-                .balance
-                .input_tx
-                .0
-                .send(AccountBalanceRequest {
-                    input: AccountBalanceInput {
-                        account: account.data.input.balance.account.clone(),
-                    },
-                })
-                .unwrap();
-            account.data.input.balance.loading = true;
+        if submit_button(ui, "Check") {
+            if let Some(input) = form_input(&account.data.input.balance) {
+                account.channels.balance.send(input);
+                account.data.input.balance.loading = true;
+            }
         }
     }
     if let Some(output) = &account.data.output.balance {
@@ -96,19 +68,13 @@ pub fn account_balance_ui(ui: &mut egui::Ui, account: &mut ResMut<AccountUi>) {
     }
 }
 
-pub fn handle_balance_response(mut account: ResMut<AccountUi>, tokio_runtime: Res<TokioRuntime>) {
-    if let Ok(balance_result) = account.channels.balance.output_rx.0.try_recv() {
-        if let Some(balance) = balance_result {
+pub fn handle_balance_response(mut account: ResMut<AccountUi>, mut api: ApiEvents<AccountBalance>) {
+    if let Some(result) = account.channels.balance.poll(&mut api) {
+        if let Ok(balance) = result {
             account.data.output.balance = Some(balance);
         }
         account.data.input.balance.loading = false;
     }
-
-    request_handler::<AccountBalanceRequest, AccountBalanceInput, AccountBalanceOutput>(
-        tokio_runtime.runtime.clone(),
-        account.channels.balance.input_rx.clone(),
-        account.channels.balance.output_tx.clone(),
-    );
 }
 
 pub struct AccountBalancePlugin;
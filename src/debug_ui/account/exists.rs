@@ -1,56 +1,21 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use crossbeam::channel;
-use sugarfunge_api_types::{
-    account::{AccountExistsInput, AccountExistsOutput},
-    primitives::Account,
-};
+use serde::{Deserialize, Serialize};
+use sugarfunge_api_types::{account::AccountExistsInput, primitives::Account};
 
-use crate::{prelude::*, util::request_handler};
+use crate::{
+    api::{AccountExists, ApiEvents},
+    debug_ui::export::{form_input, RequestForm},
+    shortcuts::submit_button,
+};
 
 use super::AccountUi;
 
-#[derive(Debug)]
-pub struct AccountExistsRequest {
-    pub input: AccountExistsInput,
-}
-
-impl Request<AccountExistsInput> for AccountExistsRequest {
-    fn endpoint(&self) -> &str {
-        "account/exists"
-    }
-
-    fn input(&self) -> Option<AccountExistsInput> {
-        Some(AccountExistsInput {
-            account: self.input.account.clone(),
-        })
-    }
-}
-
-#[derive(Resource)]
-pub struct AccountExistsChannel {
-    pub input_tx: InputSender<AccountExistsRequest>,
-    pub input_rx: InputReceiver<AccountExistsRequest>,
-    pub output_tx: OutputSender<AccountExistsOutput>,
-    pub output_rx: OutputReceiver<AccountExistsOutput>,
-}
-
-impl Default for AccountExistsChannel {
-    fn default() -> Self {
-        let (input_tx, input_rx) = channel::unbounded::<AccountExistsRequest>();
-        let (output_tx, output_rx) = channel::unbounded::<Option<AccountExistsOutput>>();
-        Self {
-            input_tx: InputSender(input_tx),
-            input_rx: InputReceiver(input_rx),
-            output_tx: OutputSender(output_tx),
-            output_rx: OutputReceiver(output_rx),
-        }
-    }
-}
-
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AccountExistsInputData {
     pub account: Account,
+    #[serde(skip)]
     pub loading: bool,
 }
 
@@ -63,6 +28,21 @@ impl Default for AccountExistsInputData {
     }
 }
 
+impl RequestForm for AccountExistsInputData {
+    type Endpoint = AccountExists;
+
+    fn input(&self) -> Result<AccountExistsInput, String> {
+        Ok(AccountExistsInput {
+            account: self.account.clone(),
+        })
+    }
+
+    fn set_input(&mut self, input: AccountExistsInput) -> Result<(), String> {
+        self.account = input.account;
+        Ok(())
+    }
+}
+
 pub fn account_exists_ui(ui: &mut egui::Ui, account: &mut ResMut<AccountUi>) {
     ui.label("Account Exists");
     ui.separator();
@@ -72,19 +52,11 @@ pub fn account_exists_ui(ui: &mut egui::Ui, account: &mut ResMut<AccountUi>) {
         ui.separator();
         ui.add(egui::Spinner::default());
     } else {
-        if ui.button("Check").clicked() {
-            account
-                .channels
-                .exists
-                .input_tx
-                .0
-                .send(AccountExistsRequest {
-                    input: AccountExistsInput {
-                        account: account.data.input.exists.account.clone(),
-                    },
-                })
-                .unwrap();
-            account.data.input.exists.loading = true;
+        if submit_button(ui, "Check") {
+            if let Some(input) = form_input(&account.data.input.exists) {
+                account.channels.exists.send(input);
+                account.data.input.exists.loading = true;
+            }
         }
     }
     if let Some(output) = &account.data.output.exists {
@@ -94,19 +66,13 @@ pub fn account_exists_ui(ui: &mut egui::Ui, account: &mut ResMut<AccountUi>) {
     }
 }
 
-pub fn handle_exists_response(mut account: ResMut<AccountUi>, tokio_runtime: Res<TokioRuntime>) {
-    if let Ok(exists_result) = account.channels.exists.output_rx.0.try_recv() {
-        if let Some(exists) = exists_result {
+pub fn handle_exists_response(mut account: ResMut<AccountUi>, mut api: ApiEvents<AccountExists>) {
+    if let Some(result) = account.channels.exists.poll(&mut api) {
+        if let Ok(exists) = result {
             account.data.output.exists = Some(exists);
         }
         account.data.input.exists.loading = false;
     }
-
-    request_handler::<AccountExistsRequest, AccountExistsInput, AccountExistsOutput>(
-        tokio_runtime.runtime.clone(),
-        account.channels.exists.input_rx.clone(),
-        account.channels.exists.output_tx.clone(),
-    );
 }
 
 pub struct AccountExistsPlugin;
@@ -1,60 +1,31 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use crossbeam::channel;
+use serde::{Deserialize, Serialize};
 use sugarfunge_api_types::{
-    account::{FundAccountInput, FundAccountOutput},
-    primitives::{Account, Balance, Seed},
+    account::FundAccountInput,
+    primitives::{Account, Seed},
 };
+use sugarfunge_client::units::whole_tokens;
 
-use crate::{prelude::*, util::request_handler};
+use crate::{
+    api::{ApiEvents, FundAccount},
+    debug_ui::{
+        export::{form_input, form_whole_amount, RequestForm},
+        links::{link_chip, DebugUiLinks, LinkValue},
+    },
+    shortcuts::submit_button,
+};
 
 use super::AccountUi;
 
-#[derive(Debug)]
-pub struct FundAccountRequest {
-    input: FundAccountInput,
-}
-
-impl Request<FundAccountInput> for FundAccountRequest {
-    fn endpoint(&self) -> &str {
-        "account/fund"
-    }
-
-    fn input(&self) -> Option<FundAccountInput> {
-        Some(FundAccountInput {
-            seed: self.input.seed.clone(),
-            to: self.input.to.clone(),
-            amount: self.input.amount,
-        })
-    }
-}
-
-#[derive(Resource)]
-pub struct FundAccountChannel {
-    pub input_tx: InputSender<FundAccountRequest>,
-    pub input_rx: InputReceiver<FundAccountRequest>,
-    pub output_tx: OutputSender<FundAccountOutput>,
-    pub output_rx: OutputReceiver<FundAccountOutput>,
-}
-
-impl Default for FundAccountChannel {
-    fn default() -> Self {
-        let (input_tx, input_rx) = channel::unbounded::<FundAccountRequest>();
-        let (output_tx, output_rx) = channel::unbounded::<Option<FundAccountOutput>>();
-        Self {
-            input_tx: InputSender(input_tx),
-            input_rx: InputReceiver(input_rx),
-            output_tx: OutputSender(output_tx),
-            output_rx: OutputReceiver(output_rx),
-        }
-    }
-}
-
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct FundAccountInputData {
+    #[serde(skip)]
     pub seed: Seed,
     pub to: Account,
     pub amount: u64,
+    #[serde(skip)]
     pub loading: bool,
 }
 
@@ -69,7 +40,34 @@ impl Default for FundAccountInputData {
     }
 }
 
-pub fn account_fund_ui(ui: &mut egui::Ui, account: &mut ResMut<AccountUi>) {
+impl RequestForm for FundAccountInputData {
+    type Endpoint = FundAccount;
+
+    fn input(&self) -> Result<FundAccountInput, String> {
+        Ok(FundAccountInput {
+            seed: self.seed.clone(),
+            to: self.to.clone(),
+            amount: whole_tokens(self.amount),
+        })
+    }
+
+    fn set_input(&mut self, input: FundAccountInput) -> Result<(), String> {
+        self.amount = form_whole_amount(input.amount)?;
+        self.seed = input.seed;
+        self.to = input.to;
+        Ok(())
+    }
+
+    fn seed(&mut self) -> Option<&mut String> {
+        Some(&mut *self.seed)
+    }
+}
+
+pub fn account_fund_ui(
+    ui: &mut egui::Ui,
+    account: &mut ResMut<AccountUi>,
+    links: &mut DebugUiLinks,
+) {
     ui.label("Fund Account");
     ui.separator();
     ui.label("Seed");
@@ -83,49 +81,31 @@ pub fn account_fund_ui(ui: &mut egui::Ui, account: &mut ResMut<AccountUi>) {
         ui.separator();
         ui.add(egui::Spinner::default());
     } else {
-        if ui.button("Fund").clicked() {
-            account
-                .channels
-                .fund
-                .input_tx
-                .0
-                .send(FundAccountRequest {
-                    input: FundAccountInput {
-                        seed: account.data.input.fund.seed.clone(),
-                        to: account.data.input.fund.to.clone(),
-                        amount: Balance::from(
-                            (account.data.input.fund.amount as u128) * (u128::pow(10, 18)),
-                        ),
-                    },
-                })
-                .unwrap();
-            account.data.input.fund.loading = true;
+        if submit_button(ui, "Fund") {
+            if let Some(input) = form_input(&account.data.input.fund) {
+                account.channels.fund.send(input);
+                account.data.input.fund.loading = true;
+            }
         }
     }
     if let Some(output) = &account.data.output.fund {
         ui.separator();
         ui.label("From");
-        ui.text_edit_singleline(&mut output.from.as_str());
+        link_chip(ui, links, LinkValue::Account(output.from.clone()));
         ui.label("To");
-        ui.text_edit_singleline(&mut output.to.as_str());
+        link_chip(ui, links, LinkValue::Account(output.to.clone()));
         ui.label("Amount");
         ui.text_edit_singleline(&mut u128::from(output.amount).to_string());
     }
 }
 
-fn handle_funded_response(mut account: ResMut<AccountUi>, tokio_runtime: Res<TokioRuntime>) {
-    if let Ok(funded_result) = account.channels.fund.output_rx.0.try_recv() {
-        if let Some(funded) = funded_result {
+fn handle_funded_response(mut account: ResMut<AccountUi>, mut api: ApiEvents<FundAccount>) {
+    if let Some(result) = account.channels.fund.poll(&mut api) {
+        if let Ok(funded) = result {
             account.data.output.fund = Some(funded);
         }
         account.data.input.fund.loading = false;
     }
-
-    request_handler::<FundAccountRequest, FundAccountInput, FundAccountOutput>(
-        tokio_runtime.runtime.clone(),
-        account.channels.fund.input_rx.clone(),
-        account.channels.fund.output_tx.clone(),
-    );
 }
 
 pub struct AccountFundPlugin;
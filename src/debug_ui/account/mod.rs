@@ -1,7 +1,18 @@
+use std::sync::Arc;
+
 use bevy::prelude::*;
 use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
 use sugarfunge_api_types::account::*;
 
+use crate::{
+    api::{AccountBalance, AccountExists, ApiCall, CreateAccount, FundAccount, SeededAccount},
+    debug_ui::{
+        export::RequestExport,
+        links::{DebugUiLinks, LinkTarget, LinkValue},
+    },
+};
+
 pub mod balance;
 pub mod create;
 pub mod exists;
@@ -15,7 +26,7 @@ pub struct AccountUi {
     pub channels: AccountChannels,
 }
 
-#[derive(Resource, Debug, Default, Eq, PartialEq)]
+#[derive(Resource, Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
 pub enum AccountActions {
     #[default]
     CreateAccount,
@@ -25,7 +36,8 @@ pub enum AccountActions {
     GetAccountExists,
 }
 
-#[derive(Resource, Debug, Default, Clone)]
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AccountInputData {
     create: create::CreateAccountInputData,
     fund: fund::FundAccountInputData,
@@ -36,29 +48,73 @@ pub struct AccountInputData {
 
 #[derive(Resource, Default, Debug)]
 pub struct AccountOutputData {
-    create: Option<CreateAccountOutput>,
-    fund: Option<FundAccountOutput>,
-    exists: Option<AccountExistsOutput>,
-    seeded: Option<SeededAccountOutput>,
-    balance: Option<AccountBalanceOutput>,
+    create: Option<Arc<CreateAccountOutput>>,
+    fund: Option<Arc<FundAccountOutput>>,
+    exists: Option<Arc<AccountExistsOutput>>,
+    seeded: Option<Arc<SeededAccountOutput>>,
+    balance: Option<Arc<AccountBalanceOutput>>,
 }
 
 #[derive(Resource, Default)]
 pub struct AccountData {
-    input: AccountInputData,
+    pub input: AccountInputData,
     output: AccountOutputData,
 }
 
 #[derive(Resource, Default)]
 pub struct AccountChannels {
-    create: create::CreateAccountChannel,
-    fund: fund::FundAccountChannel,
-    exists: exists::AccountExistsChannel,
-    seeded: seeded::SeededAccountChannel,
-    balance: balance::AccountBalanceChannel,
+    create: ApiCall<CreateAccount>,
+    fund: ApiCall<FundAccount>,
+    exists: ApiCall<AccountExists>,
+    seeded: ApiCall<SeededAccount>,
+    balance: ApiCall<AccountBalance>,
+}
+
+impl AccountUi {
+    /// Restores the selected form's defaults.
+    pub fn reset_form(&mut self) {
+        let input = &mut self.data.input;
+        match self.actions {
+            AccountActions::CreateAccount => input.create = Default::default(),
+            AccountActions::FundAccount => input.fund = Default::default(),
+            AccountActions::GetAccountBalance => input.balance = Default::default(),
+            AccountActions::GetSeededAccount => input.seeded = Default::default(),
+            AccountActions::GetAccountExists => input.exists = Default::default(),
+        }
+    }
+
+    /// The selected form, when it sends a single request.
+    pub fn request_form(&mut self) -> Option<&mut dyn RequestExport> {
+        let input = &mut self.data.input;
+        match self.actions {
+            AccountActions::CreateAccount => Some(&mut input.create),
+            AccountActions::FundAccount => Some(&mut input.fund),
+            AccountActions::GetAccountBalance => Some(&mut input.balance),
+            AccountActions::GetSeededAccount => Some(&mut input.seeded),
+            AccountActions::GetAccountExists => Some(&mut input.exists),
+        }
+    }
+
+    /// Switches to the form `target` names and fills in `value`.
+    pub fn open_link(&mut self, target: LinkTarget, value: &LinkValue) {
+        let (Some(account), input) = (value.account(), &mut self.data.input) else {
+            return;
+        };
+        match target {
+            LinkTarget::AccountBalance => {
+                self.actions = AccountActions::GetAccountBalance;
+                input.balance.account = account.clone();
+            }
+            LinkTarget::FundAccount => {
+                self.actions = AccountActions::FundAccount;
+                input.fund.to = account.clone();
+            }
+            _ => {}
+        }
+    }
 }
 
-pub fn account_ui(ui: &mut egui::Ui, account: &mut ResMut<AccountUi>) {
+pub fn account_ui(ui: &mut egui::Ui, account: &mut ResMut<AccountUi>, links: &mut DebugUiLinks) {
     ui.horizontal(|ui| {
         ui.selectable_value(
             &mut account.actions,
@@ -85,16 +141,16 @@ pub fn account_ui(ui: &mut egui::Ui, account: &mut ResMut<AccountUi>) {
     ui.separator();
     match &account.actions {
         AccountActions::CreateAccount => {
-            create::create_account_ui(ui, account);
+            create::create_account_ui(ui, account, links);
         }
         AccountActions::FundAccount => {
-            fund::account_fund_ui(ui, account);
+            fund::account_fund_ui(ui, account, links);
         }
         AccountActions::GetAccountBalance => {
             balance::account_balance_ui(ui, account);
         }
         AccountActions::GetSeededAccount => {
-            seeded::seeded_account_ui(ui, account);
+            seeded::seeded_account_ui(ui, account, links);
         }
         AccountActions::GetAccountExists => {
             exists::account_exists_ui(ui, account);
@@ -1,60 +1,29 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use crossbeam::channel;
+use serde::{Deserialize, Serialize};
 use sugarfunge_api_types::{
-    asset::{AssetBalanceInput, AssetBalanceOutput},
+    asset::AssetBalanceInput,
     primitives::{Account, AssetId, ClassId},
 };
 
-use crate::{prelude::*, util::request_handler};
+use crate::{
+    api::{ApiEvents, AssetBalance},
+    debug_ui::{
+        export::{form_input, RequestForm},
+        registry::{asset_id_ui, class_id_ui, IdRegistry},
+    },
+    shortcuts::submit_button,
+};
 
 use super::AssetUi;
 
-#[derive(Debug)]
-pub struct AssetBalanceRequest {
-    pub input: AssetBalanceInput,
-}
-
-impl Request<AssetBalanceInput> for AssetBalanceRequest {
-    fn endpoint(&self) -> &str {
-        "asset/balance"
-    }
-
-    fn input(&self) -> Option<AssetBalanceInput> {
-        Some(AssetBalanceInput {
-            class_id: self.input.class_id.clone(),
-            asset_id: self.input.asset_id.clone(),
-            account: self.input.account.clone(),
-        })
-    }
-}
-
-#[derive(Resource)]
-pub struct AssetBalanceChannel {
-    pub input_tx: InputSender<AssetBalanceRequest>,
-    pub input_rx: InputReceiver<AssetBalanceRequest>,
-    pub output_tx: OutputSender<AssetBalanceOutput>,
-    pub output_rx: OutputReceiver<AssetBalanceOutput>,
-}
-
-impl Default for AssetBalanceChannel {
-    fn default() -> Self {
-        let (input_tx, input_rx) = channel::unbounded::<AssetBalanceRequest>();
-        let (output_tx, output_rx) = channel::unbounded::<Option<AssetBalanceOutput>>();
-        Self {
-            input_tx: InputSender(input_tx),
-            input_rx: InputReceiver(input_rx),
-            output_tx: OutputSender(output_tx),
-            output_rx: OutputReceiver(output_rx),
-        }
-    }
-}
-
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AssetBalanceInputData {
     pub class_id: ClassId,
     pub asset_id: AssetId,
     pub account: Account,
+    #[serde(skip)]
     pub loading: bool,
 }
 
@@ -69,13 +38,37 @@ impl Default for AssetBalanceInputData {
     }
 }
 
-pub fn asset_balance_ui(ui: &mut egui::Ui, asset: &mut ResMut<AssetUi>) {
+impl RequestForm for AssetBalanceInputData {
+    type Endpoint = AssetBalance;
+
+    fn input(&self) -> Result<AssetBalanceInput, String> {
+        Ok(AssetBalanceInput {
+            class_id: self.class_id,
+            asset_id: self.asset_id,
+            account: self.account.clone(),
+        })
+    }
+
+    fn set_input(&mut self, input: AssetBalanceInput) -> Result<(), String> {
+        self.class_id = input.class_id;
+        self.asset_id = input.asset_id;
+        self.account = input.account;
+        Ok(())
+    }
+}
+
+pub fn asset_balance_ui(ui: &mut egui::Ui, asset: &mut ResMut<AssetUi>, registry: &IdRegistry) {
     ui.label("Asset Balance");
     ui.separator();
     ui.label("Class ID");
-    ui.add(egui::DragValue::new::<u64>(&mut *asset.data.input.balance.class_id).speed(0.1));
+    class_id_ui(ui, registry, &mut asset.data.input.balance.class_id);
     ui.label("Asset ID");
-    ui.add(egui::DragValue::new::<u64>(&mut *asset.data.input.balance.asset_id).speed(0.1));
+    asset_id_ui(
+        ui,
+        registry,
+        u64::from(asset.data.input.balance.class_id),
+        &mut asset.data.input.balance.asset_id,
+    );
     ui.label("Account");
     ui.text_edit_singleline(&mut *asset.data.input.balance.account);
     ui.separator();
@@ -83,19 +76,10 @@ pub fn asset_balance_ui(ui: &mut egui::Ui, asset: &mut ResMut<AssetUi>) {
         ui.separator();
         ui.add(egui::Spinner::default());
     } else {
-        if ui.button("Get Balance").clicked() {
-            asset
-                .channels
-                .balance
-                .input_tx
-                .send(AssetBalanceRequest {
-                    input: AssetBalanceInput {
-                        class_id: asset.data.input.balance.class_id.clone(),
-                        asset_id: asset.data.input.balance.asset_id.clone(),
-                        account: asset.data.input.balance.account.clone(),
-                    },
-                })
-                .unwrap();
+        if submit_button(ui, "Get Balance") {
+            if let Some(input) = form_input(&asset.data.input.balance) {
+                asset.channels.balance.send(input);
+            }
         }
     }
     if let Some(output) = &asset.data.output.balance {
@@ -105,19 +89,13 @@ pub fn asset_balance_ui(ui: &mut egui::Ui, asset: &mut ResMut<AssetUi>) {
     }
 }
 
-pub fn handle_balance_response(mut asset: ResMut<AssetUi>, tokio_runtime: Res<TokioRuntime>) {
-    if let Ok(balance_result) = asset.channels.balance.output_rx.0.try_recv() {
-        if let Some(balance) = balance_result {
+pub fn handle_balance_response(mut asset: ResMut<AssetUi>, mut api: ApiEvents<AssetBalance>) {
+    if let Some(result) = asset.channels.balance.poll(&mut api) {
+        if let Ok(balance) = result {
             asset.data.output.balance = Some(balance);
         }
         asset.data.input.balance.loading = false;
     }
-
-    request_handler::<AssetBalanceRequest, AssetBalanceInput, AssetBalanceOutput>(
-        tokio_runtime.runtime.clone(),
-        asset.channels.balance.input_rx.clone(),
-        asset.channels.balance.output_tx.clone(),
-    );
 }
 
 pub struct AssetBalancePlugin;
@@ -1,59 +1,30 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use crossbeam::channel;
+use serde::{Deserialize, Serialize};
 use sugarfunge_api_types::{
-    asset::{AssetBalancesInput, AssetBalancesOutput},
+    asset::AssetBalancesInput,
     primitives::{Account, ClassId},
 };
 
-use crate::{prelude::*, util::request_handler};
-
-use super::AssetUi;
-
-#[derive(Debug)]
-pub struct AssetBalancesRequest {
-    pub input: AssetBalancesInput,
-}
-
-impl Request<AssetBalancesInput> for AssetBalancesRequest {
-    fn endpoint(&self) -> &str {
-        "asset/balances"
-    }
-
-    fn input(&self) -> Option<AssetBalancesInput> {
-        Some(AssetBalancesInput {
-            class_id: self.input.class_id.clone(),
-            account: self.input.account.clone(),
-        })
-    }
-}
+use crate::{
+    api::{ApiEvents, AssetBalances},
+    debug_ui::{
+        export::{form_input, RequestForm},
+        links::{link_chip, DebugUiLinks, LinkValue},
+        registry::{class_id_ui, IdRegistry},
+    },
+    shortcuts::submit_button,
+};
 
-#[derive(Resource)]
-pub struct AssetBalancesChannel {
-    pub input_tx: InputSender<AssetBalancesRequest>,
-    pub input_rx: InputReceiver<AssetBalancesRequest>,
-    pub output_tx: OutputSender<AssetBalancesOutput>,
-    pub output_rx: OutputReceiver<AssetBalancesOutput>,
-}
+use super::{AssetActions, AssetUi};
 
-impl Default for AssetBalancesChannel {
-    fn default() -> Self {
-        let (input_tx, input_rx) = channel::unbounded::<AssetBalancesRequest>();
-        let (output_tx, output_rx) = channel::unbounded::<Option<AssetBalancesOutput>>();
-        Self {
-            input_tx: InputSender(input_tx),
-            input_rx: InputReceiver(input_rx),
-            output_tx: OutputSender(output_tx),
-            output_rx: OutputReceiver(output_rx),
-        }
-    }
-}
-
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AssetBalancesInputData {
     pub class_id: ClassId,
     pub class_id_enabled: bool,
     pub account: Account,
+    #[serde(skip)]
     pub loading: bool,
 }
 
@@ -68,7 +39,49 @@ impl Default for AssetBalancesInputData {
     }
 }
 
-pub fn asset_balances_ui(ui: &mut egui::Ui, asset: &mut ResMut<AssetUi>) {
+/// Switches to the balances tab for `account` and requests its balances.
+impl RequestForm for AssetBalancesInputData {
+    type Endpoint = AssetBalances;
+
+    fn input(&self) -> Result<AssetBalancesInput, String> {
+        Ok(AssetBalancesInput {
+            class_id: if self.class_id_enabled {
+                Some(self.class_id)
+            } else {
+                None
+            },
+            account: self.account.clone(),
+        })
+    }
+
+    fn set_input(&mut self, input: AssetBalancesInput) -> Result<(), String> {
+        self.class_id_enabled = input.class_id.is_some();
+        if let Some(class_id) = input.class_id {
+            self.class_id = class_id;
+        }
+        self.account = input.account;
+        Ok(())
+    }
+}
+
+pub fn show_asset_balances(asset: &mut AssetUi, account: Account) {
+    asset.actions = AssetActions::AssetBalances;
+    asset.data.input.balances.account = account;
+    asset.data.input.balances.class_id_enabled = false;
+    let input = AssetBalancesInput {
+        class_id: None,
+        account: asset.data.input.balances.account.clone(),
+    };
+    asset.channels.balances.send(input);
+    asset.data.input.balances.loading = true;
+}
+
+pub fn asset_balances_ui(
+    ui: &mut egui::Ui,
+    asset: &mut ResMut<AssetUi>,
+    registry: &IdRegistry,
+    links: &mut DebugUiLinks,
+) {
     ui.label("Asset Balances");
     ui.separator();
     ui.label("Account");
@@ -79,31 +92,18 @@ pub fn asset_balances_ui(ui: &mut egui::Ui, asset: &mut ResMut<AssetUi>) {
     );
     if asset.data.input.balances.class_id_enabled {
         ui.label("Class ID");
-        ui.add(egui::DragValue::new::<u64>(&mut *asset.data.input.balances.class_id).speed(0.1));
+        class_id_ui(ui, registry, &mut asset.data.input.balances.class_id);
     }
     ui.separator();
     if asset.data.input.balances.loading {
         ui.separator();
         ui.add(egui::Spinner::default());
     } else {
-        if ui.button("Get Balances").clicked() {
-            asset
-                .channels
-                .balances
-                .input_tx
-                .0
-                .send(AssetBalancesRequest {
-                    input: AssetBalancesInput {
-                        class_id: if asset.data.input.balances.class_id_enabled {
-                            Some(asset.data.input.balances.class_id)
-                        } else {
-                            None
-                        },
-                        account: asset.data.input.balances.account.clone(),
-                    },
-                })
-                .unwrap();
-            asset.data.input.balances.loading = true;
+        if submit_button(ui, "Get Balances") {
+            if let Some(input) = form_input(&asset.data.input.balances) {
+                asset.channels.balances.send(input);
+                asset.data.input.balances.loading = true;
+            }
         }
     }
     ui.separator();
@@ -111,26 +111,27 @@ pub fn asset_balances_ui(ui: &mut egui::Ui, asset: &mut ResMut<AssetUi>) {
         ui.label("Balances");
         ui.separator();
         for (i, balance) in balances_output.balances.iter().enumerate() {
-            ui.label(format!("Amount [{}]", i + 1));
+            ui.horizontal(|ui| {
+                ui.label(format!("Amount [{}]", i + 1));
+                link_chip(
+                    ui,
+                    links,
+                    LinkValue::Asset(balance.class_id, balance.asset_id),
+                );
+            });
             ui.text_edit_singleline(&mut u128::from(balance.amount).to_string());
             ui.separator();
         }
     }
 }
 
-pub fn handle_balances_response(mut asset: ResMut<AssetUi>, tokio_runtime: Res<TokioRuntime>) {
-    if let Ok(balances_result) = asset.channels.balances.output_rx.0.try_recv() {
-        if let Some(balances) = balances_result {
+pub fn handle_balances_response(mut asset: ResMut<AssetUi>, mut api: ApiEvents<AssetBalances>) {
+    if let Some(result) = asset.channels.balances.poll(&mut api) {
+        if let Ok(balances) = result {
             asset.data.output.balances = Some(balances);
         }
         asset.data.input.balances.loading = false;
     }
-
-    request_handler::<AssetBalancesRequest, AssetBalancesInput, AssetBalancesOutput>(
-        tokio_runtime.runtime.clone(),
-        asset.channels.balances.input_rx.clone(),
-        asset.channels.balances.output_tx.clone(),
-    );
 }
 
 pub struct AssetBalancesPlugin;
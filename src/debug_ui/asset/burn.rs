@@ -1,64 +1,33 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use crossbeam::channel;
+use serde::{Deserialize, Serialize};
 use sugarfunge_api_types::{
-    asset::{BurnInput, BurnOutput},
+    asset::BurnInput,
     primitives::{Account, AssetId, Balance, ClassId, Seed},
 };
 
-use crate::{prelude::*, util::request_handler};
+use crate::{
+    api::{ApiEvents, BurnAsset},
+    debug_ui::{
+        export::{form_amount, form_input, RequestForm},
+        links::{link_chip, DebugUiLinks, LinkValue},
+        registry::{asset_id_ui, class_id_ui, IdRegistry},
+    },
+    shortcuts::submit_button,
+};
 
 use super::AssetUi;
 
-#[derive(Debug)]
-pub struct AssetBurnRequest {
-    pub input: BurnInput,
-}
-
-impl Request<BurnInput> for AssetBurnRequest {
-    fn endpoint(&self) -> &str {
-        "asset/burn"
-    }
-
-    fn input(&self) -> Option<BurnInput> {
-        Some(BurnInput {
-            seed: self.input.seed.clone(),
-            class_id: self.input.class_id.clone(),
-            asset_id: self.input.asset_id.clone(),
-            amount: self.input.amount.clone(),
-            from: self.input.from.clone(),
-        })
-    }
-}
-
-#[derive(Resource)]
-pub struct AssetBurnChannel {
-    pub input_tx: InputSender<AssetBurnRequest>,
-    pub input_rx: InputReceiver<AssetBurnRequest>,
-    pub output_tx: OutputSender<BurnOutput>,
-    pub output_rx: OutputReceiver<BurnOutput>,
-}
-
-impl Default for AssetBurnChannel {
-    fn default() -> Self {
-        let (input_tx, input_rx) = channel::unbounded::<AssetBurnRequest>();
-        let (output_tx, output_rx) = channel::unbounded::<Option<BurnOutput>>();
-        Self {
-            input_tx: InputSender(input_tx),
-            input_rx: InputReceiver(input_rx),
-            output_tx: OutputSender(output_tx),
-            output_rx: OutputReceiver(output_rx),
-        }
-    }
-}
-
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AssetBurnInputData {
+    #[serde(skip)]
     pub seed: Seed,
     pub class_id: ClassId,
     pub asset_id: AssetId,
     pub amount: u64,
     pub from: Account,
+    #[serde(skip)]
     pub loading: bool,
 }
 
@@ -75,15 +44,52 @@ impl Default for AssetBurnInputData {
     }
 }
 
-pub fn asset_burn_ui(ui: &mut egui::Ui, asset: &mut ResMut<AssetUi>) {
+impl RequestForm for AssetBurnInputData {
+    type Endpoint = BurnAsset;
+
+    fn input(&self) -> Result<BurnInput, String> {
+        Ok(BurnInput {
+            seed: self.seed.clone(),
+            class_id: self.class_id,
+            asset_id: self.asset_id,
+            amount: Balance::from(self.amount as u128),
+            from: self.from.clone(),
+        })
+    }
+
+    fn set_input(&mut self, input: BurnInput) -> Result<(), String> {
+        self.amount = form_amount(input.amount)?;
+        self.seed = input.seed;
+        self.class_id = input.class_id;
+        self.asset_id = input.asset_id;
+        self.from = input.from;
+        Ok(())
+    }
+
+    fn seed(&mut self) -> Option<&mut String> {
+        Some(&mut *self.seed)
+    }
+}
+
+pub fn asset_burn_ui(
+    ui: &mut egui::Ui,
+    asset: &mut ResMut<AssetUi>,
+    registry: &IdRegistry,
+    links: &mut DebugUiLinks,
+) {
     ui.label("Burn Asset");
     ui.separator();
     ui.label("Seed");
     ui.text_edit_singleline(&mut *asset.data.input.burn.seed);
     ui.label("Class ID");
-    ui.add(egui::DragValue::new::<u64>(&mut *asset.data.input.burn.class_id).speed(0.1));
+    class_id_ui(ui, registry, &mut asset.data.input.burn.class_id);
     ui.label("Asset ID");
-    ui.add(egui::DragValue::new::<u64>(&mut *asset.data.input.burn.asset_id).speed(0.1));
+    asset_id_ui(
+        ui,
+        registry,
+        u64::from(asset.data.input.burn.class_id),
+        &mut asset.data.input.burn.asset_id,
+    );
     ui.label("Amount");
     ui.add(egui::DragValue::new::<u64>(&mut asset.data.input.burn.amount).speed(0.1));
     ui.label("From");
@@ -93,53 +99,39 @@ pub fn asset_burn_ui(ui: &mut egui::Ui, asset: &mut ResMut<AssetUi>) {
         ui.separator();
         ui.add(egui::Spinner::default());
     } else {
-        if ui.button("Burn").clicked() {
-            asset
-                .channels
-                .burn
-                .input_tx
-                .0
-                .send(AssetBurnRequest {
-                    input: BurnInput {
-                        seed: asset.data.input.burn.seed.clone(),
-                        class_id: asset.data.input.burn.class_id,
-                        asset_id: asset.data.input.burn.asset_id,
-                        amount: Balance::from(asset.data.input.burn.amount as u128),
-                        from: asset.data.input.burn.from.clone(),
-                    },
-                })
-                .unwrap();
-            asset.data.input.burn.loading = true;
+        if submit_button(ui, "Burn") {
+            if let Some(input) = form_input(&asset.data.input.burn) {
+                asset.channels.burn.send(input);
+                asset.data.input.burn.loading = true;
+            }
         }
     }
     if let Some(output) = &asset.data.output.burn {
         ui.separator();
         ui.label("From");
-        ui.text_edit_singleline(&mut output.from.as_str());
+        link_chip(ui, links, LinkValue::Account(output.from.clone()));
         ui.label("Class ID");
-        ui.text_edit_singleline(&mut u64::from(output.class_id).to_string());
+        link_chip(ui, links, LinkValue::Class(output.class_id));
         ui.label("Asset ID");
-        ui.text_edit_singleline(&mut u64::from(output.asset_id).to_string());
+        link_chip(
+            ui,
+            links,
+            LinkValue::Asset(output.class_id, output.asset_id),
+        );
         ui.label("Amount");
         ui.text_edit_singleline(&mut u128::from(output.amount).to_string());
         ui.label("Who");
-        ui.text_edit_singleline(&mut output.who.as_str());
+        link_chip(ui, links, LinkValue::Account(output.who.clone()));
     }
 }
 
-pub fn handle_burn_response(mut asset: ResMut<AssetUi>, tokio_runtime: Res<TokioRuntime>) {
-    if let Ok(burn_result) = asset.channels.burn.output_rx.0.try_recv() {
-        if let Some(burn) = burn_result {
+pub fn handle_burn_response(mut asset: ResMut<AssetUi>, mut api: ApiEvents<BurnAsset>) {
+    if let Some(result) = asset.channels.burn.poll(&mut api) {
+        if let Ok(burn) = result {
             asset.data.output.burn = Some(burn);
         }
         asset.data.input.burn.loading = false;
     }
-
-    request_handler::<AssetBurnRequest, BurnInput, BurnOutput>(
-        tokio_runtime.runtime.clone(),
-        asset.channels.burn.input_rx.clone(),
-        asset.channels.burn.output_tx.clone(),
-    );
 }
 
 pub struct AssetBurnPlugin;
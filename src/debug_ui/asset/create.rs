@@ -1,62 +1,32 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use crossbeam::channel;
+use serde::{Deserialize, Serialize};
 use sugarfunge_api_types::{
-    asset::{CreateInput, CreateOutput},
+    asset::CreateInput,
     primitives::{AssetId, ClassId, Seed},
 };
 
-use crate::{prelude::*, util::request_handler};
+use crate::{
+    api::{ApiEvents, CreateAsset},
+    debug_ui::{
+        export::{form_input, metadata_input, RequestForm},
+        links::{link_chip, DebugUiLinks, LinkValue},
+        registry::{asset_id_ui, class_id_ui, IdRegistry},
+    },
+    shortcuts::submit_button,
+};
 
 use super::AssetUi;
 
-#[derive(Debug)]
-pub struct CreateAssetRequest {
-    pub input: CreateInput,
-}
-
-impl Request<CreateInput> for CreateAssetRequest {
-    fn endpoint(&self) -> &str {
-        "asset/create"
-    }
-
-    fn input(&self) -> Option<CreateInput> {
-        Some(CreateInput {
-            seed: self.input.seed.clone(),
-            class_id: self.input.class_id.clone(),
-            metadata: self.input.metadata.clone(),
-            asset_id: self.input.asset_id.clone(),
-        })
-    }
-}
-
-#[derive(Resource)]
-pub struct CreateAssetChannel {
-    pub input_tx: InputSender<CreateAssetRequest>,
-    pub input_rx: InputReceiver<CreateAssetRequest>,
-    pub output_tx: OutputSender<CreateOutput>,
-    pub output_rx: OutputReceiver<CreateOutput>,
-}
-
-impl Default for CreateAssetChannel {
-    fn default() -> Self {
-        let (input_tx, input_rx) = channel::unbounded::<CreateAssetRequest>();
-        let (output_tx, output_rx) = channel::unbounded::<Option<CreateOutput>>();
-        Self {
-            input_tx: InputSender(input_tx),
-            input_rx: InputReceiver(input_rx),
-            output_tx: OutputSender(output_tx),
-            output_rx: OutputReceiver(output_rx),
-        }
-    }
-}
-
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CreateAssetInputData {
+    #[serde(skip)]
     pub seed: Seed,
     pub class_id: ClassId,
     pub metadata: String,
     pub asset_id: AssetId,
+    #[serde(skip)]
     pub loading: bool,
 }
 
@@ -72,63 +42,85 @@ impl Default for CreateAssetInputData {
     }
 }
 
-pub fn create_asset_ui(ui: &mut egui::Ui, asset: &mut ResMut<AssetUi>) {
+impl RequestForm for CreateAssetInputData {
+    type Endpoint = CreateAsset;
+
+    fn input(&self) -> Result<CreateInput, String> {
+        Ok(CreateInput {
+            seed: self.seed.clone(),
+            class_id: self.class_id,
+            metadata: metadata_input(&self.metadata)?,
+            asset_id: self.asset_id,
+        })
+    }
+
+    fn set_input(&mut self, input: CreateInput) -> Result<(), String> {
+        self.seed = input.seed;
+        self.class_id = input.class_id;
+        self.metadata = input.metadata.to_string();
+        self.asset_id = input.asset_id;
+        Ok(())
+    }
+
+    fn seed(&mut self) -> Option<&mut String> {
+        Some(&mut *self.seed)
+    }
+}
+
+pub fn create_asset_ui(
+    ui: &mut egui::Ui,
+    asset: &mut ResMut<AssetUi>,
+    registry: &IdRegistry,
+    links: &mut DebugUiLinks,
+) {
     ui.label("Create Asset");
     ui.separator();
     ui.label("Seed");
     ui.text_edit_singleline(&mut *asset.data.input.create.seed);
     ui.label("Class ID");
-    ui.add(egui::DragValue::new::<u64>(&mut *asset.data.input.create.class_id).speed(0.1));
+    class_id_ui(ui, registry, &mut asset.data.input.create.class_id);
     ui.label("Metadata");
     ui.text_edit_multiline(&mut asset.data.input.create.metadata);
     ui.label("Asset ID");
-    ui.add(egui::DragValue::new::<u64>(&mut *asset.data.input.create.asset_id).speed(0.1));
+    asset_id_ui(
+        ui,
+        registry,
+        u64::from(asset.data.input.create.class_id),
+        &mut asset.data.input.create.asset_id,
+    );
     if asset.data.input.create.loading {
         ui.separator();
         ui.add(egui::Spinner::default());
     } else {
-        if ui.button("Create").clicked() {
-            asset
-                .channels
-                .create
-                .input_tx
-                .0
-                .send(CreateAssetRequest {
-                    input: CreateInput {
-                        seed: asset.data.input.create.seed.clone(),
-                        class_id: asset.data.input.create.class_id,
-                        metadata: serde_json::from_str(&asset.data.input.create.metadata).unwrap(),
-                        asset_id: asset.data.input.create.asset_id.clone(),
-                    },
-                })
-                .unwrap();
-            asset.data.input.create.loading = true;
+        if submit_button(ui, "Create") {
+            if let Some(input) = form_input(&asset.data.input.create) {
+                asset.channels.create.send(input);
+                asset.data.input.create.loading = true;
+            }
         }
     }
     if let Some(output) = &asset.data.output.create {
         ui.separator();
         ui.label("Class ID");
-        ui.text_edit_singleline(&mut u64::from(output.class_id).to_string());
+        link_chip(ui, links, LinkValue::Class(output.class_id));
         ui.label("Asset ID");
-        ui.text_edit_singleline(&mut u64::from(output.asset_id).to_string());
+        link_chip(
+            ui,
+            links,
+            LinkValue::Asset(output.class_id, output.asset_id),
+        );
         ui.label("Who");
-        ui.text_edit_singleline(&mut output.who.as_str());
+        link_chip(ui, links, LinkValue::Account(output.who.clone()));
     }
 }
 
-pub fn handle_create_response(mut asset: ResMut<AssetUi>, tokio_runtime: Res<TokioRuntime>) {
-    if let Ok(created_result) = asset.channels.create.output_rx.0.try_recv() {
-        if let Some(created) = created_result {
+pub fn handle_create_response(mut asset: ResMut<AssetUi>, mut api: ApiEvents<CreateAsset>) {
+    if let Some(result) = asset.channels.create.poll(&mut api) {
+        if let Ok(created) = result {
             asset.data.output.create = Some(created);
         }
         asset.data.input.create.loading = false;
     }
-
-    request_handler::<CreateAssetRequest, CreateInput, CreateOutput>(
-        tokio_runtime.runtime.clone(),
-        asset.channels.create.input_rx.clone(),
-        asset.channels.create.output_tx.clone(),
-    );
 }
 
 pub struct CreateAssetPlugin;
@@ -1,58 +1,29 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use crossbeam::channel;
+use serde::{Deserialize, Serialize};
 use sugarfunge_api_types::{
-    asset::{AssetInfoInput, AssetInfoOutput},
+    asset::AssetInfoInput,
     primitives::{AssetId, ClassId},
 };
 
-use crate::{prelude::*, util::request_handler};
-
-use super::AssetUi;
-
-#[derive(Debug)]
-pub struct AssetInfoRequest {
-    pub input: AssetInfoInput,
-}
-
-impl Request<AssetInfoInput> for AssetInfoRequest {
-    fn endpoint(&self) -> &str {
-        "asset/info"
-    }
-
-    fn input(&self) -> Option<AssetInfoInput> {
-        Some(AssetInfoInput {
-            asset_id: self.input.asset_id.clone(),
-            class_id: self.input.class_id.clone(),
-        })
-    }
-}
+use crate::{
+    api::{ApiEvents, AssetInfo},
+    debug_ui::{
+        export::{form_input, RequestForm},
+        links::{link_chip, DebugUiLinks, LinkValue},
+        registry::{asset_id_ui, class_id_ui, IdRegistry},
+    },
+    shortcuts::submit_button,
+};
 
-#[derive(Resource)]
-pub struct AssetInfoChannel {
-    pub input_tx: InputSender<AssetInfoRequest>,
-    pub input_rx: InputReceiver<AssetInfoRequest>,
-    pub output_tx: OutputSender<AssetInfoOutput>,
-    pub output_rx: OutputReceiver<AssetInfoOutput>,
-}
+use super::{AssetActions, AssetUi};
 
-impl Default for AssetInfoChannel {
-    fn default() -> Self {
-        let (input_tx, input_rx) = channel::unbounded::<AssetInfoRequest>();
-        let (output_tx, output_rx) = channel::unbounded::<Option<AssetInfoOutput>>();
-        Self {
-            input_tx: InputSender(input_tx),
-            input_rx: InputReceiver(input_rx),
-            output_tx: OutputSender(output_tx),
-            output_rx: OutputReceiver(output_rx),
-        }
-    }
-}
-
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AssetInfoInputData {
     pub asset_id: AssetId,
     pub class_id: ClassId,
+    #[serde(skip)]
     pub loading: bool,
 }
 
@@ -66,40 +37,70 @@ impl Default for AssetInfoInputData {
     }
 }
 
-pub fn asset_info_ui(ui: &mut egui::Ui, asset: &mut ResMut<AssetUi>) {
+/// Switches to the info tab for the given asset and requests its info.
+impl RequestForm for AssetInfoInputData {
+    type Endpoint = AssetInfo;
+
+    fn input(&self) -> Result<AssetInfoInput, String> {
+        Ok(AssetInfoInput {
+            asset_id: self.asset_id,
+            class_id: self.class_id,
+        })
+    }
+
+    fn set_input(&mut self, input: AssetInfoInput) -> Result<(), String> {
+        self.asset_id = input.asset_id;
+        self.class_id = input.class_id;
+        Ok(())
+    }
+}
+
+pub fn show_asset_info(asset: &mut AssetUi, class_id: ClassId, asset_id: AssetId) {
+    asset.actions = AssetActions::AssetInfo;
+    asset.data.input.info.class_id = class_id;
+    asset.data.input.info.asset_id = asset_id;
+    asset
+        .channels
+        .info
+        .send(AssetInfoInput { asset_id, class_id });
+    asset.data.input.info.loading = true;
+}
+
+pub fn asset_info_ui(
+    ui: &mut egui::Ui,
+    asset: &mut ResMut<AssetUi>,
+    registry: &IdRegistry,
+    links: &mut DebugUiLinks,
+) {
     ui.label("Asset Info");
     ui.separator();
     ui.label("Class ID");
-    ui.add(egui::DragValue::new::<u64>(&mut *asset.data.input.info.class_id).speed(0.1));
+    class_id_ui(ui, registry, &mut asset.data.input.info.class_id);
     ui.label("Asset ID");
-    ui.add(egui::DragValue::new::<u64>(&mut *asset.data.input.info.asset_id).speed(0.1));
+    asset_id_ui(
+        ui,
+        registry,
+        u64::from(asset.data.input.info.class_id),
+        &mut asset.data.input.info.asset_id,
+    );
     if asset.data.input.info.loading {
         ui.separator();
         ui.add(egui::Spinner::default());
     } else {
-        if ui.button("Get Info").clicked() {
-            asset
-                .channels
-                .info
-                .input_tx
-                .0
-                .send(AssetInfoRequest {
-                    input: AssetInfoInput {
-                        asset_id: asset.data.input.info.asset_id,
-                        class_id: asset.data.input.info.class_id,
-                    },
-                })
-                .unwrap();
-            asset.data.input.info.loading = true;
+        if submit_button(ui, "Get Info") {
+            if let Some(input) = form_input(&asset.data.input.info) {
+                asset.channels.info.send(input);
+                asset.data.input.info.loading = true;
+            }
         }
     }
     if let Some(output) = &asset.data.output.info {
         ui.separator();
         if let Some(info) = &output.info {
             ui.label("Class ID");
-            ui.text_edit_singleline(&mut format!("{:?}", info.class_id));
+            link_chip(ui, links, LinkValue::Class(info.class_id));
             ui.label("Asset ID");
-            ui.text_edit_singleline(&mut format!("{:?}", info.asset_id));
+            link_chip(ui, links, LinkValue::Asset(info.class_id, info.asset_id));
             ui.label("Metadata");
             ui.text_edit_singleline(&mut info.metadata.to_string());
         } else {
@@ -108,19 +109,13 @@ pub fn asset_info_ui(ui: &mut egui::Ui, asset: &mut ResMut<AssetUi>) {
     }
 }
 
-pub fn handle_info_response(mut asset: ResMut<AssetUi>, tokio_runtime: Res<TokioRuntime>) {
-    if let Ok(info_result) = asset.channels.info.output_rx.0.try_recv() {
-        if let Some(info) = info_result {
+pub fn handle_info_response(mut asset: ResMut<AssetUi>, mut api: ApiEvents<AssetInfo>) {
+    if let Some(result) = asset.channels.info.poll(&mut api) {
+        if let Ok(info) = result {
             asset.data.output.info = Some(info);
         }
         asset.data.input.info.loading = false;
     }
-
-    request_handler::<AssetInfoRequest, AssetInfoInput, AssetInfoOutput>(
-        tokio_runtime.runtime.clone(),
-        asset.channels.info.input_rx.clone(),
-        asset.channels.info.output_tx.clone(),
-    );
 }
 
 pub struct AssetInfoPlugin;
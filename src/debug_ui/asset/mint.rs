@@ -1,43 +1,33 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use crossbeam::channel;
+use serde::{Deserialize, Serialize};
 use sugarfunge_api_types::{
-    asset::{MintInput, MintOutput},
+    asset::MintInput,
     primitives::{Account, AssetId, Balance, ClassId, Seed},
 };
 
-use crate::{prelude::*, util::request_handler};
+use crate::{
+    api::{ApiEvents, MintAsset},
+    debug_ui::{
+        export::{form_amount, form_input, RequestForm},
+        links::{link_chip, DebugUiLinks, LinkValue},
+        registry::{asset_id_ui, class_id_ui, IdRegistry},
+    },
+    shortcuts::submit_button,
+};
 
 use super::AssetUi;
 
-#[derive(Debug)]
-pub struct AssetMintRequest {
-    pub input: MintInput,
-}
-
-impl Request<MintInput> for AssetMintRequest {
-    fn endpoint(&self) -> &str {
-        "asset/mint"
-    }
-
-    fn input(&self) -> Option<MintInput> {
-        Some(MintInput {
-            seed: self.input.seed.clone(),
-            class_id: self.input.class_id.clone(),
-            to: self.input.to.clone(),
-            asset_id: self.input.asset_id.clone(),
-            amount: self.input.amount.clone(),
-        })
-    }
-}
-
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AssetMintInputData {
+    #[serde(skip)]
     pub seed: Seed,
     pub class_id: ClassId,
     pub asset_id: AssetId,
     pub amount: u64,
     pub to: Account,
+    #[serde(skip)]
     pub loading: bool,
 }
 
@@ -54,36 +44,52 @@ impl Default for AssetMintInputData {
     }
 }
 
-#[derive(Resource)]
-pub struct AssetMintChannel {
-    pub input_tx: InputSender<AssetMintRequest>,
-    pub input_rx: InputReceiver<AssetMintRequest>,
-    pub output_tx: OutputSender<MintOutput>,
-    pub output_rx: OutputReceiver<MintOutput>,
-}
+impl RequestForm for AssetMintInputData {
+    type Endpoint = MintAsset;
 
-impl Default for AssetMintChannel {
-    fn default() -> Self {
-        let (input_tx, input_rx) = channel::unbounded::<AssetMintRequest>();
-        let (output_tx, output_rx) = channel::unbounded::<Option<MintOutput>>();
-        Self {
-            input_tx: InputSender(input_tx),
-            input_rx: InputReceiver(input_rx),
-            output_tx: OutputSender(output_tx),
-            output_rx: OutputReceiver(output_rx),
-        }
+    fn input(&self) -> Result<MintInput, String> {
+        Ok(MintInput {
+            seed: self.seed.clone(),
+            class_id: self.class_id,
+            to: self.to.clone(),
+            asset_id: self.asset_id,
+            amount: Balance::from(self.amount as u128),
+        })
+    }
+
+    fn set_input(&mut self, input: MintInput) -> Result<(), String> {
+        self.amount = form_amount(input.amount)?;
+        self.seed = input.seed;
+        self.class_id = input.class_id;
+        self.to = input.to;
+        self.asset_id = input.asset_id;
+        Ok(())
+    }
+
+    fn seed(&mut self) -> Option<&mut String> {
+        Some(&mut *self.seed)
     }
 }
 
-pub fn asset_mint_ui(ui: &mut egui::Ui, asset: &mut ResMut<AssetUi>) {
+pub fn asset_mint_ui(
+    ui: &mut egui::Ui,
+    asset: &mut ResMut<AssetUi>,
+    registry: &IdRegistry,
+    links: &mut DebugUiLinks,
+) {
     ui.label("Mint Asset");
     ui.separator();
     ui.label("Seed");
     ui.text_edit_singleline(&mut *asset.data.input.mint.seed);
     ui.label("Class ID");
-    ui.add(egui::DragValue::new::<u64>(&mut *asset.data.input.mint.class_id).speed(0.1));
+    class_id_ui(ui, registry, &mut asset.data.input.mint.class_id);
     ui.label("Asset ID");
-    ui.add(egui::DragValue::new::<u64>(&mut *asset.data.input.mint.asset_id).speed(0.1));
+    asset_id_ui(
+        ui,
+        registry,
+        u64::from(asset.data.input.mint.class_id),
+        &mut asset.data.input.mint.asset_id,
+    );
     ui.label("Amount");
     ui.add(egui::DragValue::new::<u64>(&mut asset.data.input.mint.amount).speed(0.1));
     ui.label("To");
@@ -92,53 +98,39 @@ pub fn asset_mint_ui(ui: &mut egui::Ui, asset: &mut ResMut<AssetUi>) {
         ui.separator();
         ui.add(egui::Spinner::default());
     } else {
-        if ui.button("Mint").clicked() {
-            asset
-                .channels
-                .mint
-                .input_tx
-                .0
-                .send(AssetMintRequest {
-                    input: MintInput {
-                        seed: asset.data.input.mint.seed.clone(),
-                        class_id: asset.data.input.mint.class_id,
-                        to: asset.data.input.mint.to.clone(),
-                        asset_id: asset.data.input.mint.asset_id,
-                        amount: Balance::from(asset.data.input.mint.amount as u128),
-                    },
-                })
-                .unwrap();
-            asset.data.input.mint.loading = true;
+        if submit_button(ui, "Mint") {
+            if let Some(input) = form_input(&asset.data.input.mint) {
+                asset.channels.mint.send(input);
+                asset.data.input.mint.loading = true;
+            }
         }
     }
     if let Some(output) = &asset.data.output.mint {
         ui.separator();
         ui.label("To");
-        ui.text_edit_singleline(&mut output.to.as_str());
+        link_chip(ui, links, LinkValue::Account(output.to.clone()));
         ui.label("Class ID");
-        ui.text_edit_singleline(&mut u64::from(output.class_id).to_string());
+        link_chip(ui, links, LinkValue::Class(output.class_id));
         ui.label("Asset ID");
-        ui.text_edit_singleline(&mut u64::from(output.asset_id).to_string());
+        link_chip(
+            ui,
+            links,
+            LinkValue::Asset(output.class_id, output.asset_id),
+        );
         ui.label("Amount");
         ui.text_edit_singleline(&mut u128::from(output.amount).to_string());
         ui.label("Who");
-        ui.text_edit_singleline(&mut output.who.as_str());
+        link_chip(ui, links, LinkValue::Account(output.who.clone()));
     }
 }
 
-pub fn handle_mint_response(mut asset: ResMut<AssetUi>, tokio_runtime: Res<TokioRuntime>) {
-    if let Ok(mint_result) = asset.channels.mint.output_rx.try_recv() {
-        if let Some(mint) = mint_result {
+pub fn handle_mint_response(mut asset: ResMut<AssetUi>, mut api: ApiEvents<MintAsset>) {
+    if let Some(result) = asset.channels.mint.poll(&mut api) {
+        if let Ok(mint) = result {
             asset.data.output.mint = Some(mint);
         }
         asset.data.input.mint.loading = false;
     }
-
-    request_handler::<AssetMintRequest, MintInput, MintOutput>(
-        tokio_runtime.runtime.clone(),
-        asset.channels.mint.input_rx.clone(),
-        asset.channels.mint.output_tx.clone(),
-    );
 }
 
 pub struct AssetMintPlugin;
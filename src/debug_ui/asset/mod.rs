@@ -1,6 +1,24 @@
+use std::sync::Arc;
+
 use bevy::prelude::*;
 use bevy_egui::egui;
-use sugarfunge_api_types::asset::*;
+use serde::{Deserialize, Serialize};
+use sugarfunge_api_types::{
+    asset::*,
+    primitives::{Account, AssetId, ClassId},
+};
+
+use crate::{
+    api::{
+        ApiCall, AssetBalance, AssetBalances, AssetInfo, BurnAsset, CreateAsset, MintAsset,
+        TransferFrom, UpdateAssetMetadata,
+    },
+    debug_ui::{
+        export::RequestExport,
+        links::{DebugUiLinks, LinkTarget, LinkValue},
+        registry::IdRegistry,
+    },
+};
 
 pub mod balance;
 pub mod balances;
@@ -18,7 +36,7 @@ pub struct AssetUi {
     pub channels: AssetChannels,
 }
 
-#[derive(Resource, Debug, Default, Eq, PartialEq)]
+#[derive(Resource, Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
 pub enum AssetActions {
     #[default]
     CreateAsset,
@@ -31,7 +49,8 @@ pub enum AssetActions {
     AssetTransferFrom,
 }
 
-#[derive(Resource, Debug, Default, Clone)]
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AssetInputData {
     create: create::CreateAssetInputData,
     info: info::AssetInfoInputData,
@@ -45,14 +64,14 @@ pub struct AssetInputData {
 
 #[derive(Resource, Default, Debug)]
 pub struct AssetOutputData {
-    create: Option<CreateOutput>,
-    info: Option<AssetInfoOutput>,
-    update_metadata: Option<UpdateMetadataOutput>,
-    mint: Option<MintOutput>,
-    burn: Option<BurnOutput>,
-    balance: Option<AssetBalanceOutput>,
-    balances: Option<AssetBalancesOutput>,
-    transfer_from: Option<TransferFromOutput>,
+    create: Option<Arc<CreateOutput>>,
+    info: Option<Arc<AssetInfoOutput>>,
+    update_metadata: Option<Arc<UpdateMetadataOutput>>,
+    mint: Option<Arc<MintOutput>>,
+    burn: Option<Arc<BurnOutput>>,
+    balance: Option<Arc<AssetBalanceOutput>>,
+    balances: Option<Arc<AssetBalancesOutput>>,
+    transfer_from: Option<Arc<TransferFromOutput>>,
 }
 
 #[derive(Resource, Default)]
@@ -63,17 +82,127 @@ pub struct AssetData {
 
 #[derive(Resource, Default)]
 pub struct AssetChannels {
-    create: create::CreateAssetChannel,
-    info: info::AssetInfoChannel,
-    update_metadata: update_metadata::UpdateMetadataChannel,
-    mint: mint::AssetMintChannel,
-    burn: burn::AssetBurnChannel,
-    balance: balance::AssetBalanceChannel,
-    balances: balances::AssetBalancesChannel,
-    transfer_from: transfer_from::AssetTransferFromChannel,
+    create: ApiCall<CreateAsset>,
+    info: ApiCall<AssetInfo>,
+    update_metadata: ApiCall<UpdateAssetMetadata>,
+    mint: ApiCall<MintAsset>,
+    burn: ApiCall<BurnAsset>,
+    balance: ApiCall<AssetBalance>,
+    balances: ApiCall<AssetBalances>,
+    transfer_from: ApiCall<TransferFrom>,
+}
+
+fn fill_ids(value: &LinkValue, class_id: &mut ClassId, asset_id: &mut AssetId) {
+    if let Some(linked) = value.class_id() {
+        *class_id = linked;
+    }
+    if let Some(linked) = value.asset_id() {
+        *asset_id = linked;
+    }
+}
+
+fn fill_account(value: &LinkValue, account: &mut Account) {
+    if let Some(linked) = value.account() {
+        *account = linked.clone();
+    }
+}
+
+impl AssetUi {
+    /// Restores the selected form's defaults.
+    pub fn reset_form(&mut self) {
+        let input = &mut self.data.input;
+        match self.actions {
+            AssetActions::CreateAsset => input.create = Default::default(),
+            AssetActions::AssetInfo => input.info = Default::default(),
+            AssetActions::UpdateAssetMetadata => input.update_metadata = Default::default(),
+            AssetActions::AssetMint => input.mint = Default::default(),
+            AssetActions::AssetBurn => input.burn = Default::default(),
+            AssetActions::AssetBalance => input.balance = Default::default(),
+            AssetActions::AssetBalances => input.balances = Default::default(),
+            AssetActions::AssetTransferFrom => input.transfer_from = Default::default(),
+        }
+    }
+
+    /// The selected form, when it sends a single request.
+    pub fn request_form(&mut self) -> Option<&mut dyn RequestExport> {
+        let input = &mut self.data.input;
+        match self.actions {
+            AssetActions::CreateAsset => Some(&mut input.create),
+            AssetActions::AssetInfo => Some(&mut input.info),
+            AssetActions::UpdateAssetMetadata => Some(&mut input.update_metadata),
+            AssetActions::AssetMint => Some(&mut input.mint),
+            AssetActions::AssetBurn => Some(&mut input.burn),
+            AssetActions::AssetBalance => Some(&mut input.balance),
+            AssetActions::AssetBalances => Some(&mut input.balances),
+            AssetActions::AssetTransferFrom => Some(&mut input.transfer_from),
+        }
+    }
+
+    /// Switches to the form `target` names and fills in `value`.
+    pub fn open_link(&mut self, target: LinkTarget, value: &LinkValue) {
+        let input = &mut self.data.input;
+        match target {
+            LinkTarget::CreateAsset => {
+                self.actions = AssetActions::CreateAsset;
+                fill_ids(
+                    value,
+                    &mut input.create.class_id,
+                    &mut input.create.asset_id,
+                );
+            }
+            LinkTarget::AssetInfo => {
+                self.actions = AssetActions::AssetInfo;
+                fill_ids(value, &mut input.info.class_id, &mut input.info.asset_id);
+            }
+            LinkTarget::UpdateAssetMetadata => {
+                self.actions = AssetActions::UpdateAssetMetadata;
+                let update = &mut input.update_metadata;
+                fill_ids(value, &mut update.class_id, &mut update.asset_id);
+            }
+            LinkTarget::MintAsset => {
+                self.actions = AssetActions::AssetMint;
+                fill_ids(value, &mut input.mint.class_id, &mut input.mint.asset_id);
+                fill_account(value, &mut input.mint.to);
+            }
+            LinkTarget::BurnAsset => {
+                self.actions = AssetActions::AssetBurn;
+                fill_ids(value, &mut input.burn.class_id, &mut input.burn.asset_id);
+                fill_account(value, &mut input.burn.from);
+            }
+            LinkTarget::AssetBalance => {
+                self.actions = AssetActions::AssetBalance;
+                fill_ids(
+                    value,
+                    &mut input.balance.class_id,
+                    &mut input.balance.asset_id,
+                );
+                fill_account(value, &mut input.balance.account);
+            }
+            LinkTarget::AssetBalances => {
+                self.actions = AssetActions::AssetBalances;
+                fill_account(value, &mut input.balances.account);
+            }
+            LinkTarget::TransferFrom | LinkTarget::TransferTo => {
+                self.actions = AssetActions::AssetTransferFrom;
+                let transfer = &mut input.transfer_from;
+                fill_ids(value, &mut transfer.class_id, &mut transfer.asset_id);
+                if target == LinkTarget::TransferFrom {
+                    fill_account(value, &mut transfer.from);
+                } else {
+                    fill_account(value, &mut transfer.to);
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
-pub fn asset_ui(ui: &mut egui::Ui, asset: &mut ResMut<AssetUi>) {
+pub fn asset_ui(
+    ui: &mut egui::Ui,
+    asset: &mut ResMut<AssetUi>,
+    registry: &IdRegistry,
+    links: &mut DebugUiLinks,
+) {
     ui.horizontal(|ui| {
         ui.selectable_value(&mut asset.actions, AssetActions::CreateAsset, "Create");
         ui.selectable_value(&mut asset.actions, AssetActions::AssetInfo, "Info");
@@ -97,28 +226,28 @@ pub fn asset_ui(ui: &mut egui::Ui, asset: &mut ResMut<AssetUi>) {
     ui.separator();
     match asset.actions {
         AssetActions::CreateAsset => {
-            create::create_asset_ui(ui, asset);
+            create::create_asset_ui(ui, asset, registry, links);
         }
         AssetActions::AssetInfo => {
-            info::asset_info_ui(ui, asset);
+            info::asset_info_ui(ui, asset, registry, links);
         }
         AssetActions::UpdateAssetMetadata => {
-            update_metadata::update_asset_metadata_ui(ui, asset);
+            update_metadata::update_asset_metadata_ui(ui, asset, registry, links);
         }
         AssetActions::AssetMint => {
-            mint::asset_mint_ui(ui, asset);
+            mint::asset_mint_ui(ui, asset, registry, links);
         }
         AssetActions::AssetBurn => {
-            burn::asset_burn_ui(ui, asset);
+            burn::asset_burn_ui(ui, asset, registry, links);
         }
         AssetActions::AssetBalance => {
-            balance::asset_balance_ui(ui, asset);
+            balance::asset_balance_ui(ui, asset, registry);
         }
         AssetActions::AssetBalances => {
-            balances::asset_balances_ui(ui, asset);
+            balances::asset_balances_ui(ui, asset, registry, links);
         }
         AssetActions::AssetTransferFrom => {
-            transfer_from::asset_transfer_from_ui(ui, asset);
+            transfer_from::asset_transfer_from_ui(ui, asset, registry, links);
         }
     }
 }
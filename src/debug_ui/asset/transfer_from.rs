@@ -1,66 +1,34 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use crossbeam::channel;
+use serde::{Deserialize, Serialize};
 use sugarfunge_api_types::{
-    asset::{TransferFromInput, TransferFromOutput},
+    asset::TransferFromInput,
     primitives::{Account, AssetId, Balance, ClassId, Seed},
 };
 
-use crate::{prelude::*, util::request_handler};
+use crate::{
+    api::{ApiEvents, TransferFrom},
+    debug_ui::{
+        export::{form_amount, form_input, RequestForm},
+        links::{link_chip, DebugUiLinks, LinkValue},
+        registry::{asset_id_ui, class_id_ui, IdRegistry},
+    },
+    shortcuts::submit_button,
+};
 
 use super::AssetUi;
 
-#[derive(Debug)]
-pub struct AssetTransferFromRequest {
-    pub input: TransferFromInput,
-}
-
-impl Request<TransferFromInput> for AssetTransferFromRequest {
-    fn endpoint(&self) -> &str {
-        "asset/transfer_from"
-    }
-
-    fn input(&self) -> Option<TransferFromInput> {
-        Some(TransferFromInput {
-            seed: self.input.seed.clone(),
-            class_id: self.input.class_id.clone(),
-            asset_id: self.input.asset_id.clone(),
-            amount: self.input.amount.clone(),
-            from: self.input.from.clone(),
-            to: self.input.to.clone(),
-        })
-    }
-}
-
-#[derive(Resource)]
-pub struct AssetTransferFromChannel {
-    pub input_tx: InputSender<AssetTransferFromRequest>,
-    pub input_rx: InputReceiver<AssetTransferFromRequest>,
-    pub output_tx: OutputSender<TransferFromOutput>,
-    pub output_rx: OutputReceiver<TransferFromOutput>,
-}
-
-impl Default for AssetTransferFromChannel {
-    fn default() -> Self {
-        let (input_tx, input_rx) = channel::unbounded::<AssetTransferFromRequest>();
-        let (output_tx, output_rx) = channel::unbounded::<Option<TransferFromOutput>>();
-        Self {
-            input_tx: InputSender(input_tx),
-            input_rx: InputReceiver(input_rx),
-            output_tx: OutputSender(output_tx),
-            output_rx: OutputReceiver(output_rx),
-        }
-    }
-}
-
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AssetTransferFromInputData {
+    #[serde(skip)]
     pub seed: Seed,
     pub class_id: ClassId,
     pub asset_id: AssetId,
     pub amount: u64,
     pub from: Account,
     pub to: Account,
+    #[serde(skip)]
     pub loading: bool,
 }
 
@@ -78,15 +46,54 @@ impl Default for AssetTransferFromInputData {
     }
 }
 
-pub fn asset_transfer_from_ui(ui: &mut egui::Ui, asset: &mut ResMut<AssetUi>) {
+impl RequestForm for AssetTransferFromInputData {
+    type Endpoint = TransferFrom;
+
+    fn input(&self) -> Result<TransferFromInput, String> {
+        Ok(TransferFromInput {
+            seed: self.seed.clone(),
+            class_id: self.class_id,
+            asset_id: self.asset_id,
+            amount: Balance::from(self.amount as u128),
+            from: self.from.clone(),
+            to: self.to.clone(),
+        })
+    }
+
+    fn set_input(&mut self, input: TransferFromInput) -> Result<(), String> {
+        self.amount = form_amount(input.amount)?;
+        self.seed = input.seed;
+        self.class_id = input.class_id;
+        self.asset_id = input.asset_id;
+        self.from = input.from;
+        self.to = input.to;
+        Ok(())
+    }
+
+    fn seed(&mut self) -> Option<&mut String> {
+        Some(&mut *self.seed)
+    }
+}
+
+pub fn asset_transfer_from_ui(
+    ui: &mut egui::Ui,
+    asset: &mut ResMut<AssetUi>,
+    registry: &IdRegistry,
+    links: &mut DebugUiLinks,
+) {
     ui.label("Transfer Asset From");
     ui.separator();
     ui.label("Seed");
     ui.text_edit_singleline(&mut *asset.data.input.transfer_from.seed);
     ui.label("Class ID");
-    ui.add(egui::DragValue::new::<u64>(&mut *asset.data.input.transfer_from.class_id).speed(0.1));
+    class_id_ui(ui, registry, &mut asset.data.input.transfer_from.class_id);
     ui.label("Asset ID");
-    ui.add(egui::DragValue::new::<u64>(&mut *asset.data.input.transfer_from.asset_id).speed(0.1));
+    asset_id_ui(
+        ui,
+        registry,
+        u64::from(asset.data.input.transfer_from.class_id),
+        &mut asset.data.input.transfer_from.asset_id,
+    );
     ui.label("Amount");
     ui.add(egui::DragValue::new::<u64>(&mut asset.data.input.transfer_from.amount).speed(0.1));
     ui.label("From");
@@ -98,53 +105,40 @@ pub fn asset_transfer_from_ui(ui: &mut egui::Ui, asset: &mut ResMut<AssetUi>) {
         ui.separator();
         ui.add(egui::Spinner::default());
     } else {
-        if ui.button("Transfer").clicked() {
-            asset.channels.transfer_from.input_tx.0
-                .send(AssetTransferFromRequest {
-                    input: TransferFromInput {
-                        seed: asset.data.input.transfer_from.seed.clone(),
-                        class_id: asset.data.input.transfer_from.class_id.clone(),
-                        asset_id: asset.data.input.transfer_from.asset_id.clone(),
-                        amount: Balance::from(
-                            asset.data.input.transfer_from.amount.clone() as u128
-                        ),
-                        from: asset.data.input.transfer_from.from.clone(),
-                        to: asset.data.input.transfer_from.to.clone(),
-                    },
-                })
-                .unwrap();
+        if submit_button(ui, "Transfer") {
+            if let Some(input) = form_input(&asset.data.input.transfer_from) {
+                asset.channels.transfer_from.send(input);
+            }
         }
     }
     if let Some(output) = &asset.data.output.transfer_from {
         ui.separator();
         ui.label("From");
-        ui.text_edit_singleline(&mut output.from.as_str());
+        link_chip(ui, links, LinkValue::Account(output.from.clone()));
         ui.label("To");
-        ui.text_edit_singleline(&mut output.to.as_str());
+        link_chip(ui, links, LinkValue::Account(output.to.clone()));
         ui.label("Class ID");
-        ui.text_edit_singleline(&mut u64::from(output.class_id).to_string());
+        link_chip(ui, links, LinkValue::Class(output.class_id));
         ui.label("Asset ID");
-        ui.text_edit_singleline(&mut u64::from(output.asset_id).to_string());
+        link_chip(
+            ui,
+            links,
+            LinkValue::Asset(output.class_id, output.asset_id),
+        );
         ui.label("Amount");
         ui.text_edit_singleline(&mut u128::from(output.amount).to_string());
         ui.label("Who");
-        ui.text_edit_singleline(&mut output.who.as_str());
+        link_chip(ui, links, LinkValue::Account(output.who.clone()));
     }
 }
 
-pub fn handle_transfer_from_response(mut asset: ResMut<AssetUi>, tokio_runtime: Res<TokioRuntime>) {
-    if let Ok(transfer_from_result) = asset.channels.transfer_from.output_rx.0.try_recv() {
-        if let Some(transfer_from) = transfer_from_result {
+pub fn handle_transfer_from_response(mut asset: ResMut<AssetUi>, mut api: ApiEvents<TransferFrom>) {
+    if let Some(result) = asset.channels.transfer_from.poll(&mut api) {
+        if let Ok(transfer_from) = result {
             asset.data.output.transfer_from = Some(transfer_from);
         }
         asset.data.input.transfer_from.loading = false;
     }
-
-    request_handler::<AssetTransferFromRequest, TransferFromInput, TransferFromOutput>(
-        tokio_runtime.runtime.clone(),
-        asset.channels.transfer_from.input_rx.clone(),
-        asset.channels.transfer_from.output_tx.clone(),
-    );
 }
 
 pub struct AssetTransferFromPlugin;
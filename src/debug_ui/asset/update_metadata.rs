@@ -1,62 +1,32 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use crossbeam::channel;
+use serde::{Deserialize, Serialize};
 use sugarfunge_api_types::{
-    asset::{UpdateMetadataInput, UpdateMetadataOutput},
+    asset::UpdateMetadataInput,
     primitives::{AssetId, ClassId, Seed},
 };
 
-use crate::{prelude::*, util::request_handler};
+use crate::{
+    api::{ApiEvents, UpdateAssetMetadata},
+    debug_ui::{
+        export::{form_input, metadata_input, RequestForm},
+        links::{link_chip, DebugUiLinks, LinkValue},
+        registry::{asset_id_ui, class_id_ui, IdRegistry},
+    },
+    shortcuts::submit_button,
+};
 
 use super::AssetUi;
 
-#[derive(Debug)]
-pub struct UpdateMetadataRequest {
-    pub input: UpdateMetadataInput,
-}
-
-impl Request<UpdateMetadataInput> for UpdateMetadataRequest {
-    fn endpoint(&self) -> &str {
-        "asset/update_metadata"
-    }
-
-    fn input(&self) -> Option<UpdateMetadataInput> {
-        Some(UpdateMetadataInput {
-            seed: self.input.seed.clone(),
-            class_id: self.input.class_id.clone(),
-            metadata: self.input.metadata.clone(),
-            asset_id: self.input.asset_id.clone(),
-        })
-    }
-}
-
-#[derive(Resource)]
-pub struct UpdateMetadataChannel {
-    pub input_tx: InputSender<UpdateMetadataRequest>,
-    pub input_rx: InputReceiver<UpdateMetadataRequest>,
-    pub output_tx: OutputSender<UpdateMetadataOutput>,
-    pub output_rx: OutputReceiver<UpdateMetadataOutput>,
-}
-
-impl Default for UpdateMetadataChannel {
-    fn default() -> Self {
-        let (input_tx, input_rx) = channel::unbounded::<UpdateMetadataRequest>();
-        let (output_tx, output_rx) = channel::unbounded::<Option<UpdateMetadataOutput>>();
-        Self {
-            input_tx: InputSender(input_tx),
-            input_rx: InputReceiver(input_rx),
-            output_tx: OutputSender(output_tx),
-            output_rx: OutputReceiver(output_rx),
-        }
-    }
-}
-
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct UpdateAssetMetadataInputData {
+    #[serde(skip)]
     pub seed: Seed,
     pub class_id: ClassId,
     pub metadata: String,
     pub asset_id: AssetId,
+    #[serde(skip)]
     pub loading: bool,
 }
 
@@ -72,48 +42,75 @@ impl Default for UpdateAssetMetadataInputData {
     }
 }
 
-pub fn update_asset_metadata_ui(ui: &mut egui::Ui, asset: &mut ResMut<AssetUi>) {
+impl RequestForm for UpdateAssetMetadataInputData {
+    type Endpoint = UpdateAssetMetadata;
+
+    fn input(&self) -> Result<UpdateMetadataInput, String> {
+        Ok(UpdateMetadataInput {
+            seed: self.seed.clone(),
+            class_id: self.class_id,
+            metadata: metadata_input(&self.metadata)?,
+            asset_id: self.asset_id,
+        })
+    }
+
+    fn set_input(&mut self, input: UpdateMetadataInput) -> Result<(), String> {
+        self.seed = input.seed;
+        self.class_id = input.class_id;
+        self.metadata = input.metadata.to_string();
+        self.asset_id = input.asset_id;
+        Ok(())
+    }
+
+    fn seed(&mut self) -> Option<&mut String> {
+        Some(&mut *self.seed)
+    }
+}
+
+pub fn update_asset_metadata_ui(
+    ui: &mut egui::Ui,
+    asset: &mut ResMut<AssetUi>,
+    registry: &IdRegistry,
+    links: &mut DebugUiLinks,
+) {
     ui.label("Update Asset Metadata");
     ui.separator();
     ui.label("Seed");
     ui.text_edit_singleline(&mut *asset.data.input.update_metadata.seed);
     ui.label("Class ID");
-    ui.add(egui::DragValue::new::<u64>(&mut *asset.data.input.update_metadata.class_id).speed(0.1));
+    class_id_ui(ui, registry, &mut asset.data.input.update_metadata.class_id);
     ui.label("Asset ID");
-    ui.add(egui::DragValue::new::<u64>(&mut *asset.data.input.update_metadata.asset_id).speed(0.1));
+    asset_id_ui(
+        ui,
+        registry,
+        u64::from(asset.data.input.update_metadata.class_id),
+        &mut asset.data.input.update_metadata.asset_id,
+    );
     ui.label("Metadata");
     ui.text_edit_multiline(&mut asset.data.input.update_metadata.metadata);
     if asset.data.input.update_metadata.loading {
         ui.separator();
         ui.add(egui::Spinner::default());
     } else {
-        if ui.button("Update").clicked() {
-            asset
-                .channels
-                .update_metadata
-                .input_tx
-                .0
-                .send(UpdateMetadataRequest {
-                    input: UpdateMetadataInput {
-                        seed: asset.data.input.update_metadata.seed.clone(),
-                        class_id: asset.data.input.update_metadata.class_id,
-                        metadata: serde_json::from_str(&asset.data.input.update_metadata.metadata)
-                            .unwrap(),
-                        asset_id: asset.data.input.update_metadata.asset_id,
-                    },
-                })
-                .unwrap();
-            asset.data.input.update_metadata.loading = true;
+        if submit_button(ui, "Update") {
+            if let Some(input) = form_input(&asset.data.input.update_metadata) {
+                asset.channels.update_metadata.send(input);
+                asset.data.input.update_metadata.loading = true;
+            }
         }
     }
     if let Some(output) = &asset.data.output.update_metadata {
         ui.separator();
         ui.label("Asset ID");
-        ui.text_edit_singleline(&mut u64::from(output.asset_id).to_string());
+        link_chip(
+            ui,
+            links,
+            LinkValue::Asset(output.class_id, output.asset_id),
+        );
         ui.label("Class ID");
-        ui.text_edit_singleline(&mut u64::from(output.class_id).to_string());
+        link_chip(ui, links, LinkValue::Class(output.class_id));
         ui.label("Who");
-        ui.text_edit_singleline(&mut output.who.as_str());
+        link_chip(ui, links, LinkValue::Account(output.who.clone()));
         ui.label("Metadata");
         ui.text_edit_multiline(&mut output.metadata.to_string());
     }
@@ -121,20 +118,14 @@ pub fn update_asset_metadata_ui(ui: &mut egui::Ui, asset: &mut ResMut<AssetUi>)
 
 pub fn handle_update_metadata_response(
     mut asset: ResMut<AssetUi>,
-    tokio_runtime: Res<TokioRuntime>,
+    mut api: ApiEvents<UpdateAssetMetadata>,
 ) {
-    if let Ok(response_result) = asset.channels.update_metadata.output_rx.0.try_recv() {
-        if let Some(response) = response_result {
+    if let Some(result) = asset.channels.update_metadata.poll(&mut api) {
+        if let Ok(response) = result {
             asset.data.output.update_metadata = Some(response);
         }
         asset.data.input.update_metadata.loading = false;
     }
-
-    request_handler::<UpdateMetadataRequest, UpdateMetadataInput, UpdateMetadataOutput>(
-        tokio_runtime.runtime.clone(),
-        asset.channels.update_metadata.input_rx.clone(),
-        asset.channels.update_metadata.output_tx.clone(),
-    );
 }
 
 pub struct UpdateAssetMetadataPlugin;
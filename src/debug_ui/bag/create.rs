@@ -1,65 +1,36 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use crossbeam::channel;
+use serde::{Deserialize, Serialize};
 use sugarfunge_api_types::{
-    bag::{CreateInput, CreateOutput},
-    primitives::{Account, Balance, ClassId, Seed},
+    bag::CreateInput,
+    primitives::{Account, ClassId, Seed},
 };
+use sugarfunge_client::units::whole_tokens;
 
 use crate::{
-    prelude::*,
-    util::{request_handler, vec_u64_input_ui},
+    debug_ui::{
+        export::{form_input, form_whole_amount, RequestForm},
+        links::{link_chip, DebugUiLinks, LinkValue},
+        registry::{class_id_ui, IdRegistry},
+    },
+    shortcuts::submit_button,
+    {
+        api::{ApiEvents, CreateBag},
+        util::vec_u64_input_ui,
+    },
 };
 
 use super::BagUi;
 
-#[derive(Debug)]
-pub struct CreateBagRequest {
-    pub input: CreateInput,
-}
-
-impl Request<CreateInput> for CreateBagRequest {
-    fn endpoint(&self) -> &str {
-        "bag/create"
-    }
-
-    fn input(&self) -> Option<CreateInput> {
-        Some(CreateInput {
-            seed: self.input.seed.clone(),
-            class_id: self.input.class_id.clone(),
-            owners: self.input.owners.clone(),
-            shares: self.input.shares.clone(),
-        })
-    }
-}
-
-#[derive(Resource)]
-pub struct CreateBagChannel {
-    pub input_tx: InputSender<CreateBagRequest>,
-    pub input_rx: InputReceiver<CreateBagRequest>,
-    pub output_tx: OutputSender<CreateOutput>,
-    pub output_rx: OutputReceiver<CreateOutput>,
-}
-
-impl Default for CreateBagChannel {
-    fn default() -> Self {
-        let (input_tx, input_rx) = channel::unbounded::<CreateBagRequest>();
-        let (output_tx, output_rx) = channel::unbounded::<Option<CreateOutput>>();
-        Self {
-            input_tx: InputSender(input_tx),
-            input_rx: InputReceiver(input_rx),
-            output_tx: OutputSender(output_tx),
-            output_rx: OutputReceiver(output_rx),
-        }
-    }
-}
-
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CreateBagInputData {
+    #[serde(skip)]
     pub seed: Seed,
     pub class_id: ClassId,
     pub owners: Vec<Account>,
     pub shares: Vec<u64>,
+    #[serde(skip)]
     pub loading: bool,
 }
 
@@ -75,13 +46,51 @@ impl Default for CreateBagInputData {
     }
 }
 
-pub fn create_bag_ui(ui: &mut egui::Ui, bag: &mut ResMut<BagUi>) {
+impl RequestForm for CreateBagInputData {
+    type Endpoint = CreateBag;
+
+    fn input(&self) -> Result<CreateInput, String> {
+        Ok(CreateInput {
+            seed: self.seed.clone(),
+            class_id: self.class_id,
+            owners: self.owners.clone(),
+            shares: self
+                .shares
+                .iter()
+                .map(|share| whole_tokens(*share))
+                .collect(),
+        })
+    }
+
+    fn set_input(&mut self, input: CreateInput) -> Result<(), String> {
+        self.shares = input
+            .shares
+            .into_iter()
+            .map(form_whole_amount)
+            .collect::<Result<_, _>>()?;
+        self.seed = input.seed;
+        self.class_id = input.class_id;
+        self.owners = input.owners;
+        Ok(())
+    }
+
+    fn seed(&mut self) -> Option<&mut String> {
+        Some(&mut *self.seed)
+    }
+}
+
+pub fn create_bag_ui(
+    ui: &mut egui::Ui,
+    bag: &mut ResMut<BagUi>,
+    registry: &IdRegistry,
+    links: &mut DebugUiLinks,
+) {
     ui.label("Create Bag");
     ui.separator();
     ui.label("Seed");
     ui.text_edit_singleline(&mut *bag.data.input.create.seed);
     ui.label("Class ID");
-    ui.add(egui::DragValue::new::<u64>(&mut bag.data.input.create.class_id).speed(0.1));
+    class_id_ui(ui, registry, &mut bag.data.input.create.class_id);
     ui.label("Owners");
     if ui.button("Add Owner").clicked() {
         bag.data
@@ -110,58 +119,41 @@ pub fn create_bag_ui(ui: &mut egui::Ui, bag: &mut ResMut<BagUi>) {
         ui.separator();
         ui.add(egui::Spinner::default());
     } else {
-        if ui.button("Create").clicked() {
-            let mut shares_input = Vec::new();
-            for share in bag.data.input.create.shares.iter() {
-                shares_input.push(Balance::from((*share as u128) * (u128::pow(10, 18))));
+        if submit_button(ui, "Create") {
+            if let Some(input) = form_input(&bag.data.input.create) {
+                bag.channels.create.send(input);
+                bag.data.input.create.loading = true;
             }
-            bag.channels
-                .create
-                .input_tx
-                .0
-                .send(CreateBagRequest {
-                    input: CreateInput {
-                        seed: bag.data.input.create.seed.clone(),
-                        class_id: bag.data.input.create.class_id,
-                        owners: bag.data.input.create.owners.clone(),
-
-                        shares: shares_input,
-                    },
-                })
-                .unwrap();
-            bag.data.input.create.loading = true;
         }
     }
     if let Some(output) = &bag.data.output.create {
         ui.separator();
         ui.label("Bag");
-        ui.text_edit_singleline(&mut output.bag.as_str());
+        link_chip(ui, links, LinkValue::Bag(output.bag.clone()));
         ui.label("Class ID");
-        ui.text_edit_singleline(&mut u64::from(output.class_id).to_string());
+        link_chip(ui, links, LinkValue::Class(output.class_id));
         ui.label("Asset ID");
-        ui.text_edit_singleline(&mut u64::from(output.asset_id).to_string());
+        link_chip(
+            ui,
+            links,
+            LinkValue::Asset(output.class_id, output.asset_id),
+        );
         ui.label("Owners");
         for (i, owner) in output.owners.iter().enumerate() {
             ui.label(format!("Account [{}]", i + 1));
-            ui.text_edit_singleline(&mut owner.as_str());
+            link_chip(ui, links, LinkValue::Account(owner.clone()));
             ui.separator();
         }
     }
 }
 
-pub fn handle_create_response(mut bag: ResMut<BagUi>, tokio_runtime: Res<TokioRuntime>) {
-    if let Ok(created_result) = bag.channels.create.output_rx.0.try_recv() {
-        if let Some(created) = created_result {
+pub fn handle_create_response(mut bag: ResMut<BagUi>, mut api: ApiEvents<CreateBag>) {
+    if let Some(result) = bag.channels.create.poll(&mut api) {
+        if let Ok(created) = result {
             bag.data.output.create = Some(created);
         }
         bag.data.input.create.loading = false;
     }
-
-    request_handler::<CreateBagRequest, CreateInput, CreateOutput>(
-        tokio_runtime.runtime.clone(),
-        bag.channels.create.input_rx.clone(),
-        bag.channels.create.output_tx.clone(),
-    );
 }
 
 pub struct CreateBagPlugin;
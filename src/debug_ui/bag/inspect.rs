@@ -0,0 +1,111 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+use sugarfunge_api_types::{asset::AssetBalancesInput, primitives::Account};
+
+use crate::{
+    api::{ApiEvents, AssetBalances},
+    debug_ui::{
+        export::{form_input, RequestForm},
+        links::{link_chip, DebugUiLinks, LinkValue},
+        registry::{bag_account_ui, IdRegistry},
+    },
+    shortcuts::submit_button,
+};
+
+use super::BagUi;
+
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InspectBagInputData {
+    pub bag: Account,
+    #[serde(skip)]
+    pub loading: bool,
+}
+
+impl Default for InspectBagInputData {
+    fn default() -> Self {
+        Self {
+            bag: Account::from("".to_string()),
+            loading: false,
+        }
+    }
+}
+
+/// A bag holds its assets in its own account, so inspecting it reads that
+/// account's balances.
+impl RequestForm for InspectBagInputData {
+    type Endpoint = AssetBalances;
+
+    fn input(&self) -> Result<AssetBalancesInput, String> {
+        Ok(AssetBalancesInput {
+            class_id: None,
+            account: self.bag.clone(),
+        })
+    }
+
+    fn set_input(&mut self, input: AssetBalancesInput) -> Result<(), String> {
+        self.bag = input.account;
+        Ok(())
+    }
+}
+
+pub fn inspect_bag_ui(
+    ui: &mut egui::Ui,
+    bag: &mut ResMut<BagUi>,
+    registry: &IdRegistry,
+    links: &mut DebugUiLinks,
+) {
+    ui.label("Inspect Bag");
+    ui.separator();
+    ui.label("Bag");
+    bag_account_ui(ui, registry, &mut bag.data.input.inspect.bag);
+    if let Some(class_id) = registry.bags.get(bag.data.input.inspect.bag.as_str()) {
+        ui.label(format!("Created in class {}", class_id));
+    }
+    if bag.data.input.inspect.loading {
+        ui.separator();
+        ui.add(egui::Spinner::default());
+    } else {
+        if submit_button(ui, "Inspect") {
+            if let Some(input) = form_input(&bag.data.input.inspect) {
+                bag.channels.inspect.send(input);
+                bag.data.input.inspect.loading = true;
+            }
+        }
+    }
+    if let Some(output) = &bag.data.output.inspect {
+        ui.separator();
+        ui.label("Holdings");
+        if output.balances.is_empty() {
+            ui.label("The bag is empty");
+        }
+        for balance in output.balances.iter() {
+            ui.horizontal(|ui| {
+                link_chip(
+                    ui,
+                    links,
+                    LinkValue::Asset(balance.class_id, balance.asset_id),
+                );
+                ui.label(u128::from(balance.amount).to_string());
+            });
+        }
+    }
+}
+
+pub fn handle_inspect_response(mut bag: ResMut<BagUi>, mut api: ApiEvents<AssetBalances>) {
+    if let Some(result) = bag.channels.inspect.poll(&mut api) {
+        if let Ok(balances) = result {
+            bag.data.output.inspect = Some(balances);
+        }
+        bag.data.input.inspect.loading = false;
+    }
+}
+
+pub struct InspectBagPlugin;
+
+impl Plugin for InspectBagPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(handle_inspect_response);
+    }
+}
@@ -1,9 +1,22 @@
+use std::sync::Arc;
+
 use bevy::prelude::*;
 use bevy_egui::egui;
-use sugarfunge_api_types::bag::*;
+use serde::{Deserialize, Serialize};
+use sugarfunge_api_types::{asset::AssetBalancesOutput, bag::*};
+
+use crate::{
+    api::{ApiCall, AssetBalances, CreateBag, DepositBag, RegisterBag, SweepBag},
+    debug_ui::{
+        export::RequestExport,
+        links::{DebugUiLinks, LinkTarget, LinkValue},
+        registry::IdRegistry,
+    },
+};
 
 pub mod create;
 pub mod deposit;
+pub mod inspect;
 pub mod register;
 pub mod sweep;
 
@@ -14,65 +27,147 @@ pub struct BagUi {
     pub channels: BagChannels,
 }
 
-#[derive(Resource, Debug, Default, Eq, PartialEq)]
+#[derive(Resource, Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
 pub enum BagActions {
     #[default]
     CreateBag,
     RegisterBag,
     SweepBag,
     DepositBag,
+    InspectBag,
 }
 
-#[derive(Resource, Debug, Default, Clone)]
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct BagInputData {
     create: create::CreateBagInputData,
     register: register::RegisterBagInputData,
     sweep: sweep::SweepBagInputData,
     deposit: deposit::DepositBagInputData,
+    inspect: inspect::InspectBagInputData,
 }
 
 #[derive(Resource, Default, Debug)]
 pub struct BagOutputData {
-    create: Option<CreateOutput>,
-    register: Option<RegisterOutput>,
-    sweep: Option<SweepOutput>,
-    deposit: Option<DepositOutput>,
+    create: Option<Arc<CreateOutput>>,
+    register: Option<Arc<RegisterOutput>>,
+    sweep: Option<Arc<SweepOutput>>,
+    deposit: Option<Arc<DepositOutput>>,
+    inspect: Option<Arc<AssetBalancesOutput>>,
 }
 
 #[derive(Resource, Default)]
 pub struct BagData {
-    input: BagInputData,
+    pub input: BagInputData,
     output: BagOutputData,
 }
 
 #[derive(Resource, Default)]
 pub struct BagChannels {
-    create: create::CreateBagChannel,
-    register: register::RegisterBagChannel,
-    sweep: sweep::SweepBagChannel,
-    deposit: deposit::DepositBagChannel,
+    create: ApiCall<CreateBag>,
+    register: ApiCall<RegisterBag>,
+    sweep: ApiCall<SweepBag>,
+    deposit: ApiCall<DepositBag>,
+    inspect: ApiCall<AssetBalances>,
 }
 
-pub fn bag_ui(ui: &mut egui::Ui, bag: &mut ResMut<BagUi>) {
+impl BagUi {
+    /// Restores the selected form's defaults.
+    pub fn reset_form(&mut self) {
+        let input = &mut self.data.input;
+        match self.actions {
+            BagActions::CreateBag => input.create = Default::default(),
+            BagActions::RegisterBag => input.register = Default::default(),
+            BagActions::SweepBag => input.sweep = Default::default(),
+            BagActions::DepositBag => input.deposit = Default::default(),
+            BagActions::InspectBag => input.inspect = Default::default(),
+        }
+    }
+
+    /// The selected form, when it sends a single request.
+    pub fn request_form(&mut self) -> Option<&mut dyn RequestExport> {
+        let input = &mut self.data.input;
+        match self.actions {
+            BagActions::CreateBag => Some(&mut input.create),
+            BagActions::RegisterBag => Some(&mut input.register),
+            BagActions::SweepBag => Some(&mut input.sweep),
+            BagActions::DepositBag => Some(&mut input.deposit),
+            BagActions::InspectBag => Some(&mut input.inspect),
+        }
+    }
+
+    /// Switches to the form `target` names and fills in `value`.
+    pub fn open_link(&mut self, target: LinkTarget, value: &LinkValue) {
+        let input = &mut self.data.input;
+        match target {
+            LinkTarget::CreateBag => {
+                self.actions = BagActions::CreateBag;
+                if let Some(class_id) = value.class_id() {
+                    input.create.class_id = class_id;
+                }
+                if let Some(account) = value.account() {
+                    if !input.create.owners.contains(account) {
+                        input.create.owners.push(account.clone());
+                        input.create.shares.push(1);
+                    }
+                }
+            }
+            LinkTarget::SweepBag | LinkTarget::SweepBagTo => {
+                self.actions = BagActions::SweepBag;
+                if let Some(account) = value.account() {
+                    if target == LinkTarget::SweepBag {
+                        input.sweep.bag = account.clone();
+                    } else {
+                        input.sweep.to = account.clone();
+                    }
+                }
+            }
+            LinkTarget::DepositBag => {
+                self.actions = BagActions::DepositBag;
+                if let Some(account) = value.account() {
+                    input.deposit.bag = account.clone();
+                }
+            }
+            LinkTarget::InspectBag => {
+                self.actions = BagActions::InspectBag;
+                if let Some(account) = value.account() {
+                    input.inspect.bag = account.clone();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn bag_ui(
+    ui: &mut egui::Ui,
+    bag: &mut ResMut<BagUi>,
+    registry: &IdRegistry,
+    links: &mut DebugUiLinks,
+) {
     ui.horizontal(|ui| {
         ui.selectable_value(&mut bag.actions, BagActions::CreateBag, "Create");
         ui.selectable_value(&mut bag.actions, BagActions::RegisterBag, "Register");
         ui.selectable_value(&mut bag.actions, BagActions::SweepBag, "Sweep");
         ui.selectable_value(&mut bag.actions, BagActions::DepositBag, "Deposit");
+        ui.selectable_value(&mut bag.actions, BagActions::InspectBag, "Inspect");
     });
     ui.separator();
     match &bag.actions {
         BagActions::CreateBag => {
-            create::create_bag_ui(ui, bag);
+            create::create_bag_ui(ui, bag, registry, links);
         }
         BagActions::RegisterBag => {
-            register::register_bag_ui(ui, bag);
+            register::register_bag_ui(ui, bag, registry, links);
         }
         BagActions::SweepBag => {
-            sweep::sweep_bag_ui(ui, bag);
+            sweep::sweep_bag_ui(ui, bag, registry, links);
         }
         BagActions::DepositBag => {
-            deposit::deposit_bag_ui(ui, bag);
+            deposit::deposit_bag_ui(ui, bag, registry, links);
+        }
+        BagActions::InspectBag => {
+            inspect::inspect_bag_ui(ui, bag, registry, links);
         }
     }
 }
@@ -85,6 +180,7 @@ impl Plugin for BagPlugin {
             .add_plugin(create::CreateBagPlugin)
             .add_plugin(register::RegisterBagPlugin)
             .add_plugin(sweep::SweepBagPlugin)
-            .add_plugin(deposit::DepositBagPlugin);
+            .add_plugin(deposit::DepositBagPlugin)
+            .add_plugin(inspect::InspectBagPlugin);
     }
 }
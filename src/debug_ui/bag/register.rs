@@ -1,60 +1,31 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use crossbeam::channel;
+use serde::{Deserialize, Serialize};
 use sugarfunge_api_types::{
-    bag::{RegisterInput, RegisterOutput},
+    bag::RegisterInput,
     primitives::{ClassId, Seed},
 };
 
-use crate::{prelude::*, util::request_handler};
+use crate::{
+    api::{ApiEvents, RegisterBag},
+    debug_ui::{
+        export::{form_input, metadata_input, RequestForm},
+        links::{link_chip, DebugUiLinks, LinkValue},
+        registry::{class_id_ui, IdRegistry},
+    },
+    shortcuts::submit_button,
+};
 
 use super::BagUi;
 
-#[derive(Debug)]
-pub struct RegisterBagRequest {
-    pub input: RegisterInput,
-}
-
-impl Request<RegisterInput> for RegisterBagRequest {
-    fn endpoint(&self) -> &str {
-        "bag/register"
-    }
-
-    fn input(&self) -> Option<RegisterInput> {
-        Some(RegisterInput {
-            seed: self.input.seed.clone(),
-            class_id: self.input.class_id.clone(),
-            metadata: self.input.metadata.clone(),
-        })
-    }
-}
-
-#[derive(Resource)]
-pub struct RegisterBagChannel {
-    pub input_tx: InputSender<RegisterBagRequest>,
-    pub input_rx: InputReceiver<RegisterBagRequest>,
-    pub output_tx: OutputSender<RegisterOutput>,
-    pub output_rx: OutputReceiver<RegisterOutput>,
-}
-
-impl Default for RegisterBagChannel {
-    fn default() -> Self {
-        let (input_tx, input_rx) = channel::unbounded::<RegisterBagRequest>();
-        let (output_tx, output_rx) = channel::unbounded::<Option<RegisterOutput>>();
-        Self {
-            input_tx: InputSender(input_tx),
-            input_rx: InputReceiver(input_rx),
-            output_tx: OutputSender(output_tx),
-            output_rx: OutputReceiver(output_rx),
-        }
-    }
-}
-
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RegisterBagInputData {
+    #[serde(skip)]
     pub seed: Seed,
     pub class_id: ClassId,
     pub metadata: String,
+    #[serde(skip)]
     pub loading: bool,
 }
 
@@ -69,57 +40,70 @@ impl Default for RegisterBagInputData {
     }
 }
 
-pub fn register_bag_ui(ui: &mut egui::Ui, bag: &mut ResMut<BagUi>) {
+impl RequestForm for RegisterBagInputData {
+    type Endpoint = RegisterBag;
+
+    fn input(&self) -> Result<RegisterInput, String> {
+        Ok(RegisterInput {
+            seed: self.seed.clone(),
+            class_id: self.class_id,
+            metadata: metadata_input(&self.metadata)?,
+        })
+    }
+
+    fn set_input(&mut self, input: RegisterInput) -> Result<(), String> {
+        self.seed = input.seed;
+        self.class_id = input.class_id;
+        self.metadata = input.metadata.to_string();
+        Ok(())
+    }
+
+    fn seed(&mut self) -> Option<&mut String> {
+        Some(&mut *self.seed)
+    }
+}
+
+pub fn register_bag_ui(
+    ui: &mut egui::Ui,
+    bag: &mut ResMut<BagUi>,
+    registry: &IdRegistry,
+    links: &mut DebugUiLinks,
+) {
     ui.label("Register Bag");
     ui.separator();
     ui.label("Seed");
     ui.text_edit_singleline(&mut *bag.data.input.register.seed);
     ui.label("Class ID");
-    ui.add(egui::DragValue::new::<u64>(&mut *bag.data.input.register.class_id).speed(0.1));
+    class_id_ui(ui, registry, &mut bag.data.input.register.class_id);
     ui.label("Metadata");
     ui.text_edit_multiline(&mut bag.data.input.register.metadata);
     if bag.data.input.register.loading {
         ui.separator();
         ui.add(egui::Spinner::default());
     } else {
-        if ui.button("Register").clicked() {
-            bag.channels
-                .register
-                .input_tx
-                .0
-                .send(RegisterBagRequest {
-                    input: RegisterInput {
-                        seed: bag.data.input.register.seed.clone(),
-                        class_id: bag.data.input.register.class_id,
-                        metadata: serde_json::from_str(&bag.data.input.register.metadata).unwrap(),
-                    },
-                })
-                .unwrap();
-            bag.data.input.register.loading = true;
+        if submit_button(ui, "Register") {
+            if let Some(input) = form_input(&bag.data.input.register) {
+                bag.channels.register.send(input);
+                bag.data.input.register.loading = true;
+            }
         }
     }
     if let Some(output) = &bag.data.output.register {
         ui.separator();
         ui.label("Who");
-        ui.text_edit_singleline(&mut output.who.as_str());
+        link_chip(ui, links, LinkValue::Account(output.who.clone()));
         ui.label("Class ID");
-        ui.text_edit_singleline(&mut u64::from(output.class_id).to_string());
+        link_chip(ui, links, LinkValue::Class(output.class_id));
     }
 }
 
-pub fn handle_register_response(mut bag: ResMut<BagUi>, tokio_runtime: Res<TokioRuntime>) {
-    if let Ok(registered_result) = bag.channels.register.output_rx.0.try_recv() {
-        if let Some(registered) = registered_result {
+pub fn handle_register_response(mut bag: ResMut<BagUi>, mut api: ApiEvents<RegisterBag>) {
+    if let Some(result) = bag.channels.register.poll(&mut api) {
+        if let Ok(registered) = result {
             bag.data.output.register = Some(registered);
         }
         bag.data.input.register.loading = false;
     }
-
-    request_handler::<RegisterBagRequest, RegisterInput, RegisterOutput>(
-        tokio_runtime.runtime.clone(),
-        bag.channels.register.input_rx.clone(),
-        bag.channels.register.output_tx.clone(),
-    );
 }
 
 pub struct RegisterBagPlugin;
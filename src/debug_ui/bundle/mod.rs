@@ -1,7 +1,19 @@
+use std::sync::Arc;
+
 use bevy::prelude::*;
 use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
 use sugarfunge_api_types::bundle::*;
 
+use crate::{
+    api::{ApiCall, BurnBundle, MintBundle, RegisterBundle},
+    debug_ui::{
+        export::RequestExport,
+        links::{DebugUiLinks, LinkTarget, LinkValue},
+        registry::IdRegistry,
+    },
+};
+
 pub mod burn;
 pub mod mint;
 pub mod register;
@@ -13,7 +25,7 @@ pub struct BundleUi {
     pub channels: BundleChannels,
 }
 
-#[derive(Resource, Debug, Default, Eq, PartialEq)]
+#[derive(Resource, Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
 pub enum BundleActions {
     #[default]
     RegisterBundle,
@@ -21,7 +33,8 @@ pub enum BundleActions {
     BurnBundle,
 }
 
-#[derive(Resource, Debug, Default, Clone)]
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct BundleInputData {
     register: register::RegisterBundleInputData,
     mint: mint::MintBundleInputData,
@@ -30,25 +43,85 @@ pub struct BundleInputData {
 
 #[derive(Resource, Default, Debug)]
 pub struct BundleOutputData {
-    register: Option<RegisterBundleOutput>,
-    mint: Option<MintBundleOutput>,
-    burn: Option<BurnBundleOutput>,
+    register: Option<Arc<RegisterBundleOutput>>,
+    mint: Option<Arc<MintBundleOutput>>,
+    burn: Option<Arc<BurnBundleOutput>>,
 }
 
 #[derive(Resource, Default)]
 pub struct BundleData {
-    input: BundleInputData,
+    pub input: BundleInputData,
     output: BundleOutputData,
 }
 
 #[derive(Resource, Default)]
 pub struct BundleChannels {
-    register: register::RegisterBundleChannel,
-    mint: mint::MintBundleChannel,
-    burn: burn::BurnBundleChannel,
+    register: ApiCall<RegisterBundle>,
+    mint: ApiCall<MintBundle>,
+    burn: ApiCall<BurnBundle>,
+}
+
+impl BundleUi {
+    /// Restores the selected form's defaults.
+    pub fn reset_form(&mut self) {
+        let input = &mut self.data.input;
+        match self.actions {
+            BundleActions::RegisterBundle => input.register = Default::default(),
+            BundleActions::MintBundle => input.mint = Default::default(),
+            BundleActions::BurnBundle => input.burn = Default::default(),
+        }
+    }
+
+    /// The selected form, when it sends a single request.
+    pub fn request_form(&mut self) -> Option<&mut dyn RequestExport> {
+        let input = &mut self.data.input;
+        match self.actions {
+            BundleActions::RegisterBundle => Some(&mut input.register),
+            BundleActions::MintBundle => Some(&mut input.mint),
+            BundleActions::BurnBundle => Some(&mut input.burn),
+        }
+    }
+
+    /// Switches to the form `target` names and fills in `value`.
+    pub fn open_link(&mut self, target: LinkTarget, value: &LinkValue) {
+        let input = &mut self.data.input;
+        match target {
+            LinkTarget::RegisterBundle => {
+                self.actions = BundleActions::RegisterBundle;
+                if let (Some(class_id), Some(asset_id)) = (value.class_id(), value.asset_id()) {
+                    input.register.class_id = class_id;
+                    input.register.asset_id = asset_id;
+                }
+            }
+            LinkTarget::MintBundle => {
+                self.actions = BundleActions::MintBundle;
+                if let Some(account) = value.account() {
+                    input.mint.to = account.clone();
+                }
+                if let Some(bundle_id) = value.bundle_id() {
+                    input.mint.bundle_id = bundle_id.clone();
+                }
+            }
+            LinkTarget::BurnBundle => {
+                self.actions = BundleActions::BurnBundle;
+                if let Some(account) = value.account() {
+                    input.burn.from = account.clone();
+                }
+                if let Some(bundle_id) = value.bundle_id() {
+                    input.burn.bundle_id = bundle_id.clone();
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
-pub fn bundle_ui(ui: &mut egui::Ui, bundle: &mut ResMut<BundleUi>) {
+pub fn bundle_ui(
+    ui: &mut egui::Ui,
+    bundle: &mut ResMut<BundleUi>,
+    registry: &IdRegistry,
+    links: &mut DebugUiLinks,
+) {
     ui.horizontal(|ui| {
         ui.selectable_value(
             &mut bundle.actions,
@@ -61,13 +134,13 @@ pub fn bundle_ui(ui: &mut egui::Ui, bundle: &mut ResMut<BundleUi>) {
     ui.separator();
     match &bundle.actions {
         BundleActions::RegisterBundle => {
-            register::register_bundle_ui(ui, bundle);
+            register::register_bundle_ui(ui, bundle, registry, links);
         }
         BundleActions::MintBundle => {
-            mint::mint_bundle_ui(ui, bundle);
+            mint::mint_bundle_ui(ui, bundle, registry, links);
         }
         BundleActions::BurnBundle => {
-            burn::burn_bundle_ui(ui, bundle);
+            burn::burn_bundle_ui(ui, bundle, registry, links);
         }
     }
 }
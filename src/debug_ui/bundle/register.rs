@@ -1,63 +1,30 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use crossbeam::channel;
+use serde::{Deserialize, Serialize};
 use sugarfunge_api_types::{
-    bundle::{BundleSchema, RegisterBundleInput, RegisterBundleOutput},
-    primitives::{AssetId, Balance, ClassId, Seed},
+    bundle::{BundleSchema, RegisterBundleInput},
+    primitives::{AssetId, ClassId, Seed},
 };
 
-use crate::{prelude::*, util::*};
+use crate::{
+    debug_ui::{
+        export::{form_input, metadata_input, RequestForm},
+        links::{link_chip, DebugUiLinks, LinkValue},
+        registry::{asset_id_ui, class_id_ui, IdRegistry},
+    },
+    shortcuts::submit_button,
+    {
+        api::{ApiEvents, RegisterBundle},
+        util::*,
+    },
+};
 
 use super::BundleUi;
 
-#[derive(Debug)]
-pub struct RegisterBundleRequest {
-    pub input: RegisterBundleInput,
-}
-
-impl Request<RegisterBundleInput> for RegisterBundleRequest {
-    fn endpoint(&self) -> &str {
-        "bundle/register"
-    }
-
-    fn input(&self) -> Option<RegisterBundleInput> {
-        Some(RegisterBundleInput {
-            seed: self.input.seed.clone(),
-            class_id: self.input.class_id.clone(),
-            metadata: self.input.metadata.clone(),
-            asset_id: self.input.asset_id.clone(),
-            schema: BundleSchema {
-                class_ids: self.input.schema.class_ids.clone(),
-                asset_ids: self.input.schema.asset_ids.clone(),
-                amounts: self.input.schema.amounts.clone(),
-            },
-        })
-    }
-}
-
-#[derive(Resource)]
-pub struct RegisterBundleChannel {
-    pub input_tx: InputSender<RegisterBundleRequest>,
-    pub input_rx: InputReceiver<RegisterBundleRequest>,
-    pub output_tx: OutputSender<RegisterBundleOutput>,
-    pub output_rx: OutputReceiver<RegisterBundleOutput>,
-}
-
-impl Default for RegisterBundleChannel {
-    fn default() -> Self {
-        let (input_tx, input_rx) = channel::unbounded::<RegisterBundleRequest>();
-        let (output_tx, output_rx) = channel::unbounded::<Option<RegisterBundleOutput>>();
-        Self {
-            input_tx: InputSender(input_tx),
-            input_rx: InputReceiver(input_rx),
-            output_tx: OutputSender(output_tx),
-            output_rx: OutputReceiver(output_rx),
-        }
-    }
-}
-
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RegisterBundleInputData {
+    #[serde(skip)]
     pub seed: Seed,
     pub class_id: ClassId,
     pub metadata: String,
@@ -65,6 +32,7 @@ pub struct RegisterBundleInputData {
     pub schema_class_ids: Vec<u64>,
     pub schema_asset_ids: Vec<Vec<u64>>,
     pub schema_amounts: Vec<Vec<u64>>,
+    #[serde(skip)]
     pub loading: bool,
 }
 
@@ -83,15 +51,58 @@ impl Default for RegisterBundleInputData {
     }
 }
 
-pub fn register_bundle_ui(ui: &mut egui::Ui, bundle: &mut ResMut<BundleUi>) {
+impl RequestForm for RegisterBundleInputData {
+    type Endpoint = RegisterBundle;
+
+    fn input(&self) -> Result<RegisterBundleInput, String> {
+        Ok(RegisterBundleInput {
+            seed: self.seed.clone(),
+            class_id: self.class_id,
+            metadata: metadata_input(&self.metadata)?,
+            asset_id: self.asset_id,
+            schema: BundleSchema {
+                class_ids: transform_vec_of_u64_to_class_id(self.schema_class_ids.clone()),
+                asset_ids: transform_doublevec_of_u64_to_asset_id(self.schema_asset_ids.clone()),
+                amounts: transform_doublevec_of_u64_to_balance(self.schema_amounts.clone()),
+            },
+        })
+    }
+
+    fn set_input(&mut self, input: RegisterBundleInput) -> Result<(), String> {
+        self.schema_amounts = transform_doublevec_of_balance_to_u64(&input.schema.amounts)?;
+        self.seed = input.seed;
+        self.class_id = input.class_id;
+        self.metadata = input.metadata.to_string();
+        self.asset_id = input.asset_id;
+        self.schema_class_ids = transform_vec_of_class_id_to_u64(&input.schema.class_ids);
+        self.schema_asset_ids = transform_doublevec_of_asset_id_to_u64(&input.schema.asset_ids);
+        Ok(())
+    }
+
+    fn seed(&mut self) -> Option<&mut String> {
+        Some(&mut *self.seed)
+    }
+}
+
+pub fn register_bundle_ui(
+    ui: &mut egui::Ui,
+    bundle: &mut ResMut<BundleUi>,
+    registry: &IdRegistry,
+    links: &mut DebugUiLinks,
+) {
     ui.label("Register Bundle");
     ui.separator();
     ui.label("Seed");
     ui.text_edit_singleline(&mut *bundle.data.input.register.seed);
     ui.label("Class ID");
-    ui.add(egui::DragValue::new::<u64>(&mut *bundle.data.input.register.class_id).speed(0.1));
+    class_id_ui(ui, registry, &mut bundle.data.input.register.class_id);
     ui.label("Asset ID");
-    ui.add(egui::DragValue::new::<u64>(&mut *bundle.data.input.register.asset_id).speed(0.1));
+    asset_id_ui(
+        ui,
+        registry,
+        u64::from(bundle.data.input.register.class_id),
+        &mut bundle.data.input.register.asset_id,
+    );
     ui.label("Metadata");
     ui.text_edit_multiline(&mut bundle.data.input.register.metadata);
     ui.label("Schema Class IDs");
@@ -105,63 +116,36 @@ pub fn register_bundle_ui(ui: &mut egui::Ui, bundle: &mut ResMut<BundleUi>) {
     ui.label("Schema Amounts");
     vec_of_vec_u64_input_ui(ui, &mut bundle.data.input.register.schema_amounts, "Amount");
     ui.separator();
-    if ui.button("Register").clicked() {
-        let class_ids: Vec<ClassId> =
-            transform_vec_of_u64_to_class_id(bundle.data.input.register.schema_class_ids.clone());
-        let asset_ids: Vec<Vec<AssetId>> = transform_doublevec_of_u64_to_asset_id(
-            bundle.data.input.register.schema_asset_ids.clone(),
-        );
-        let amounts: Vec<Vec<Balance>> = transform_doublevec_of_u64_to_balance(
-            bundle.data.input.register.schema_amounts.clone(),
-        );
-        bundle
-            .channels
-            .register
-            .input_tx
-            .0
-            .send(RegisterBundleRequest {
-                input: RegisterBundleInput {
-                    seed: bundle.data.input.register.seed.clone(),
-                    class_id: bundle.data.input.register.class_id.clone(),
-                    metadata: serde_json::from_str(&bundle.data.input.register.metadata.clone())
-                        .unwrap(),
-                    asset_id: bundle.data.input.register.asset_id.clone(),
-                    schema: BundleSchema {
-                        class_ids,
-                        asset_ids,
-                        amounts,
-                    },
-                },
-            })
-            .unwrap();
-        bundle.data.input.register.loading = true;
+    if submit_button(ui, "Register") {
+        if let Some(input) = form_input(&bundle.data.input.register) {
+            bundle.channels.register.send(input);
+            bundle.data.input.register.loading = true;
+        }
     }
     if let Some(output) = &bundle.data.output.register {
         ui.separator();
         ui.label("Bundle ID");
-        ui.text_edit_singleline(&mut output.bundle_id.as_str());
+        link_chip(ui, links, LinkValue::Bundle(output.bundle_id.clone()));
         ui.label("Who");
-        ui.text_edit_singleline(&mut output.who.as_str());
+        link_chip(ui, links, LinkValue::Account(output.who.clone()));
         ui.label("Class ID");
-        ui.text_edit_singleline(&mut format!("{:?}", output.class_id));
+        link_chip(ui, links, LinkValue::Class(output.class_id));
         ui.label("Asset ID");
-        ui.text_edit_singleline(&mut format!("{:?}", output.asset_id));
+        link_chip(
+            ui,
+            links,
+            LinkValue::Asset(output.class_id, output.asset_id),
+        );
     }
 }
 
-pub fn handle_register_response(mut bundle: ResMut<BundleUi>, tokio_runtime: Res<TokioRuntime>) {
-    if let Ok(registered_result) = bundle.channels.register.output_rx.0.try_recv() {
-        if let Some(registered) = registered_result {
+pub fn handle_register_response(mut bundle: ResMut<BundleUi>, mut api: ApiEvents<RegisterBundle>) {
+    if let Some(result) = bundle.channels.register.poll(&mut api) {
+        if let Ok(registered) = result {
             bundle.data.output.register = Some(registered);
         }
         bundle.data.input.register.loading = false;
     }
-
-    request_handler::<RegisterBundleRequest, RegisterBundleInput, RegisterBundleOutput>(
-        tokio_runtime.runtime.clone(),
-        bundle.channels.register.input_rx.clone(),
-        bundle.channels.register.output_tx.clone(),
-    );
 }
 
 pub struct RegisterBundlePlugin;
@@ -1,72 +1,72 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use crossbeam::channel;
+use serde::{Deserialize, Serialize};
 use sugarfunge_api_types::{
-    asset::{CreateClassInput, CreateClassOutput},
+    asset::CreateClassInput,
     primitives::{Account, ClassId, Seed},
 };
 
-use crate::{prelude::*, util::request_handler};
+use crate::{
+    api::{ApiEvents, CreateClass},
+    debug_ui::{
+        export::{form_input, metadata_input, RequestForm},
+        links::{link_chip, DebugUiLinks, LinkValue},
+        registry::{class_id_ui, IdRegistry},
+    },
+    shortcuts::submit_button,
+};
 
 use super::ClassUi;
 
-#[derive(Debug)]
-pub struct CreateClassRequest {
-    pub input: CreateClassInput,
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CreateClassInputData {
+    #[serde(skip)]
+    pub seed: String,
+    pub class_id: u64,
+    pub metadata: String,
+    pub owner: String,
+    #[serde(skip)]
+    pub loading: bool,
 }
 
-impl Request<CreateClassInput> for CreateClassRequest {
-    fn endpoint(&self) -> &str {
-        "asset/create_class"
-    }
+impl RequestForm for CreateClassInputData {
+    type Endpoint = CreateClass;
 
-    fn input(&self) -> Option<CreateClassInput> {
-        Some(CreateClassInput {
-            seed: self.input.seed.clone(),
-            class_id: self.input.class_id.clone(),
-            metadata: self.input.metadata.clone(),
-            owner: self.input.owner.clone(),
+    fn input(&self) -> Result<CreateClassInput, String> {
+        Ok(CreateClassInput {
+            seed: Seed::from(self.seed.clone()),
+            class_id: ClassId::from(self.class_id),
+            metadata: metadata_input(&self.metadata)?,
+            owner: Account::from(self.owner.clone()),
         })
     }
-}
-
-#[derive(Resource)]
-pub struct CreateClassChannel {
-    pub input_tx: InputSender<CreateClassRequest>,
-    pub input_rx: InputReceiver<CreateClassRequest>,
-    pub output_tx: OutputSender<CreateClassOutput>,
-    pub output_rx: OutputReceiver<CreateClassOutput>,
-}
 
-impl Default for CreateClassChannel {
-    fn default() -> Self {
-        let (input_tx, input_rx) = channel::unbounded::<CreateClassRequest>();
-        let (output_tx, output_rx) = channel::unbounded::<Option<CreateClassOutput>>();
-        Self {
-            input_tx: InputSender(input_tx),
-            input_rx: InputReceiver(input_rx),
-            output_tx: OutputSender(output_tx),
-            output_rx: OutputReceiver(output_rx),
-        }
+    fn set_input(&mut self, input: CreateClassInput) -> Result<(), String> {
+        self.seed = (*input.seed).clone();
+        self.class_id = u64::from(input.class_id);
+        self.metadata = input.metadata.to_string();
+        self.owner = (*input.owner).clone();
+        Ok(())
     }
-}
 
-#[derive(Resource, Debug, Default, Clone)]
-pub struct CreateClassInputData {
-    pub seed: String,
-    pub class_id: u64,
-    pub metadata: String,
-    pub owner: String,
-    pub loading: bool,
+    fn seed(&mut self) -> Option<&mut String> {
+        Some(&mut self.seed)
+    }
 }
 
-pub fn create_class_ui(ui: &mut egui::Ui, class: &mut ResMut<ClassUi>) {
+pub fn create_class_ui(
+    ui: &mut egui::Ui,
+    class: &mut ResMut<ClassUi>,
+    registry: &IdRegistry,
+    links: &mut DebugUiLinks,
+) {
     ui.label("Create Class");
     ui.separator();
     ui.label("Seed");
     ui.text_edit_singleline(&mut class.data.input.create.seed);
     ui.label("Class ID");
-    ui.add(egui::DragValue::new::<u64>(&mut class.data.input.create.class_id).speed(0.1));
+    class_id_ui(ui, registry, &mut class.data.input.create.class_id);
     ui.label("Metadata");
     ui.text_edit_multiline(&mut class.data.input.create.metadata);
     ui.label("Owner");
@@ -75,47 +75,30 @@ pub fn create_class_ui(ui: &mut egui::Ui, class: &mut ResMut<ClassUi>) {
         ui.separator();
         ui.add(egui::Spinner::default());
     } else {
-        if ui.button("Create").clicked() {
-            class
-                .channels
-                .create
-                .input_tx
-                .0
-                .send(CreateClassRequest {
-                    input: CreateClassInput {
-                        seed: Seed::from(class.data.input.create.seed.clone()),
-                        class_id: ClassId::from(class.data.input.create.class_id.clone()),
-                        metadata: serde_json::from_str(&class.data.input.create.metadata).unwrap(),
-                        owner: Account::from(class.data.input.create.owner.clone()),
-                    },
-                })
-                .unwrap();
+        if submit_button(ui, "Create") {
+            if let Some(input) = form_input(&class.data.input.create) {
+                class.channels.create.send(input);
 
-            class.data.input.create.loading = true;
+                class.data.input.create.loading = true;
+            }
         }
     }
     if let Some(output) = &class.data.output.create {
         ui.separator();
         ui.label("Who");
-        ui.text_edit_singleline(&mut output.who.as_str());
+        link_chip(ui, links, LinkValue::Account(output.who.clone()));
         ui.label("Class ID");
-        ui.text_edit_singleline(&mut u64::from(output.class_id).to_string());
+        link_chip(ui, links, LinkValue::Class(output.class_id));
     }
 }
 
-pub fn handle_create_response(mut class: ResMut<ClassUi>, tokio_runtime: Res<TokioRuntime>) {
-    if let Ok(created_result) = class.channels.create.output_rx.0.try_recv() {
-        if let Some(created) = created_result {
+pub fn handle_create_response(mut class: ResMut<ClassUi>, mut api: ApiEvents<CreateClass>) {
+    if let Some(result) = class.channels.create.poll(&mut api) {
+        if let Ok(created) = result {
             class.data.output.create = Some(created);
         }
         class.data.input.create.loading = false;
     }
-
-    request_handler::<CreateClassRequest, CreateClassInput, CreateClassOutput>(
-        tokio_runtime.runtime.clone(),
-        class.channels.create.input_rx.clone(),
-        class.channels.create.output_tx.clone(),
-    );
 }
 
 pub struct CreateClassPlugin;
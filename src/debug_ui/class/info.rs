@@ -1,111 +1,86 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use crossbeam::channel;
-use sugarfunge_api_types::{
-    asset::{ClassInfoInput, ClassInfoOutput},
-    primitives::ClassId,
-};
+use serde::{Deserialize, Serialize};
+use sugarfunge_api_types::{asset::ClassInfoInput, primitives::ClassId};
 
-use crate::{prelude::*, util::request_handler};
+use crate::{
+    api::{ApiEvents, ClassInfo},
+    debug_ui::{
+        export::{form_input, RequestForm},
+        links::{link_chip, DebugUiLinks, LinkValue},
+        registry::{class_id_ui, IdRegistry},
+    },
+    shortcuts::submit_button,
+};
 
 use super::ClassUi;
 
-#[derive(Debug)]
-pub struct ClassInfoRequest {
-    pub input: ClassInfoInput,
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClassInfoInputData {
+    pub class_id: u64,
+    #[serde(skip)]
+    pub loading: bool,
 }
 
-impl Request<ClassInfoInput> for ClassInfoRequest {
-    fn endpoint(&self) -> &str {
-        "asset/class_info"
-    }
+impl RequestForm for ClassInfoInputData {
+    type Endpoint = ClassInfo;
 
-    fn input(&self) -> Option<ClassInfoInput> {
-        Some(ClassInfoInput {
-            class_id: self.input.class_id.clone(),
+    fn input(&self) -> Result<ClassInfoInput, String> {
+        Ok(ClassInfoInput {
+            class_id: ClassId::from(self.class_id),
         })
     }
-}
-
-#[derive(Resource)]
-pub struct ClassInfoChannel {
-    pub input_tx: InputSender<ClassInfoRequest>,
-    pub input_rx: InputReceiver<ClassInfoRequest>,
-    pub output_tx: OutputSender<ClassInfoOutput>,
-    pub output_rx: OutputReceiver<ClassInfoOutput>,
-}
 
-impl Default for ClassInfoChannel {
-    fn default() -> Self {
-        let (input_tx, input_rx) = channel::unbounded::<ClassInfoRequest>();
-        let (output_tx, output_rx) = channel::unbounded::<Option<ClassInfoOutput>>();
-        Self {
-            input_tx: InputSender(input_tx),
-            input_rx: InputReceiver(input_rx),
-            output_tx: OutputSender(output_tx),
-            output_rx: OutputReceiver(output_rx),
-        }
+    fn set_input(&mut self, input: ClassInfoInput) -> Result<(), String> {
+        self.class_id = u64::from(input.class_id);
+        Ok(())
     }
 }
 
-#[derive(Resource, Debug, Default, Clone)]
-pub struct ClassInfoInputData {
-    pub class_id: u64,
-    pub loading: bool,
-}
-
-pub fn class_info_ui(ui: &mut egui::Ui, class: &mut ResMut<ClassUi>) {
+pub fn class_info_ui(
+    ui: &mut egui::Ui,
+    class: &mut ResMut<ClassUi>,
+    registry: &IdRegistry,
+    links: &mut DebugUiLinks,
+) {
     ui.label("Class Info");
     ui.separator();
     ui.label("Class ID");
-    ui.add(egui::DragValue::new::<u64>(&mut class.data.input.info.class_id).speed(0.1));
+    class_id_ui(ui, registry, &mut class.data.input.info.class_id);
     if class.data.input.info.loading {
         ui.separator();
         ui.add(egui::Spinner::default());
     } else {
-        if ui.button("Get Info").clicked() {
-            class
-                .channels
-                .info
-                .input_tx
-                .0
-                .send(ClassInfoRequest {
-                    input: ClassInfoInput {
-                        class_id: ClassId::from(class.data.input.info.class_id),
-                    },
-                })
-                .unwrap();
-            class.data.input.info.loading = true;
+        if submit_button(ui, "Get Info") {
+            if let Some(input) = form_input(&class.data.input.info) {
+                class.channels.info.send(input);
+                class.data.input.info.loading = true;
+            }
         }
     }
     if let Some(output) = &class.data.output.info {
         ui.separator();
         if let Some(info) = &output.info {
             ui.label("Class ID");
-            ui.text_edit_singleline(&mut u64::from(info.class_id).to_string());
+            link_chip(ui, links, LinkValue::Class(info.class_id));
             ui.label("Metadata");
             ui.text_edit_multiline(&mut info.metadata.to_string());
             ui.label("Owner");
-            ui.text_edit_singleline(&mut info.owner.as_str());
+            link_chip(ui, links, LinkValue::Account(info.owner.clone()));
         } else {
             ui.label("No class info found");
         }
     }
 }
 
-pub fn handle_info_response(mut class: ResMut<ClassUi>, tokio_runtime: Res<TokioRuntime>) {
-    if let Ok(info_result) = class.channels.info.output_rx.0.try_recv() {
-        if let Some(info) = info_result {
+pub fn handle_info_response(mut class: ResMut<ClassUi>, mut api: ApiEvents<ClassInfo>) {
+    if let Some(result) = class.channels.info.poll(&mut api) {
+        if let Ok(info) = result {
             class.data.output.info = Some(info);
         }
         class.data.input.info.loading = false;
     }
-
-    request_handler::<ClassInfoRequest, ClassInfoInput, ClassInfoOutput>(
-        tokio_runtime.runtime.clone(),
-        class.channels.info.input_rx.clone(),
-        class.channels.info.output_tx.clone(),
-    );
 }
 
 pub struct ClassInfoPlugin;
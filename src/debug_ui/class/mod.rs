@@ -1,7 +1,19 @@
+use std::sync::Arc;
+
 use bevy::prelude::*;
 use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
 use sugarfunge_api_types::asset::*;
 
+use crate::{
+    api::{ApiCall, ClassInfo, CreateClass},
+    debug_ui::{
+        export::RequestExport,
+        links::{DebugUiLinks, LinkTarget, LinkValue},
+        registry::IdRegistry,
+    },
+};
+
 pub mod create;
 pub mod info;
 
@@ -12,14 +24,15 @@ pub struct ClassUi {
     pub channels: ClassChannels,
 }
 
-#[derive(Resource, Debug, Default, Eq, PartialEq)]
+#[derive(Resource, Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
 pub enum ClassActions {
     #[default]
     CreateClass,
     ClassInfo,
 }
 
-#[derive(Resource, Debug, Default, Clone)]
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ClassInputData {
     create: create::CreateClassInputData,
     info: info::ClassInfoInputData,
@@ -27,8 +40,8 @@ pub struct ClassInputData {
 
 #[derive(Resource, Default, Debug)]
 pub struct ClassOutputData {
-    create: Option<CreateClassOutput>,
-    info: Option<ClassInfoOutput>,
+    create: Option<Arc<CreateClassOutput>>,
+    info: Option<Arc<ClassInfoOutput>>,
 }
 
 #[derive(Resource, Default)]
@@ -39,11 +52,56 @@ pub struct ClassData {
 
 #[derive(Resource, Default)]
 pub struct ClassChannels {
-    create: create::CreateClassChannel,
-    info: info::ClassInfoChannel,
+    create: ApiCall<CreateClass>,
+    info: ApiCall<ClassInfo>,
+}
+
+impl ClassUi {
+    /// Restores the selected form's defaults.
+    pub fn reset_form(&mut self) {
+        let input = &mut self.data.input;
+        match self.actions {
+            ClassActions::CreateClass => input.create = Default::default(),
+            ClassActions::ClassInfo => input.info = Default::default(),
+        }
+    }
+
+    /// The selected form, when it sends a single request.
+    pub fn request_form(&mut self) -> Option<&mut dyn RequestExport> {
+        let input = &mut self.data.input;
+        match self.actions {
+            ClassActions::CreateClass => Some(&mut input.create),
+            ClassActions::ClassInfo => Some(&mut input.info),
+        }
+    }
+
+    /// Switches to the form `target` names and fills in `value`.
+    pub fn open_link(&mut self, target: LinkTarget, value: &LinkValue) {
+        let input = &mut self.data.input;
+        match target {
+            LinkTarget::CreateClass => {
+                self.actions = ClassActions::CreateClass;
+                if let Some(account) = value.account() {
+                    input.create.owner = account.to_string();
+                }
+            }
+            LinkTarget::ClassInfo => {
+                self.actions = ClassActions::ClassInfo;
+                if let Some(class_id) = value.class_id() {
+                    input.info.class_id = u64::from(class_id);
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
-pub fn class_ui(ui: &mut egui::Ui, class: &mut ResMut<ClassUi>) {
+pub fn class_ui(
+    ui: &mut egui::Ui,
+    class: &mut ResMut<ClassUi>,
+    registry: &IdRegistry,
+    links: &mut DebugUiLinks,
+) {
     ui.horizontal(|ui| {
         ui.selectable_value(&mut class.actions, ClassActions::CreateClass, "Create");
         ui.selectable_value(&mut class.actions, ClassActions::ClassInfo, "Info");
@@ -51,10 +109,10 @@ pub fn class_ui(ui: &mut egui::Ui, class: &mut ResMut<ClassUi>) {
     ui.separator();
     match &class.actions {
         ClassActions::CreateClass => {
-            create::create_class_ui(ui, class);
+            create::create_class_ui(ui, class, registry, links);
         }
         ClassActions::ClassInfo => {
-            info::class_info_ui(ui, class);
+            info::class_info_ui(ui, class, registry, links);
         }
     }
 }
@@ -0,0 +1,498 @@
+use std::collections::BTreeMap;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::api::{ApiClient, ApiLog};
+
+use super::{
+    account::{self, AccountActions, AccountUi},
+    asset::{self, AssetActions, AssetUi},
+    bag::{self, BagActions, BagUi},
+    bundle::{self, BundleActions, BundleUi},
+    class::{self, ClassActions, ClassUi},
+    export::request_menu_ui,
+    links::DebugUiLinks,
+    market::{self, MarketActions, MarketUi},
+    network_log::{self, NetworkLogUi},
+    registry::IdRegistry,
+    validator::{self, ValidatorActions, ValidatorUi},
+    DebugUiActions,
+};
+
+/// A section of the debug UI showing one of its forms. Panels of the same
+/// section share inputs and outputs but each keeps its own selected form.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebugUiPanel {
+    Account(AccountActions),
+    Class(ClassActions),
+    Asset(AssetActions),
+    Bag(BagActions),
+    Bundle(BundleActions),
+    Market(MarketActions),
+    Validator(ValidatorActions),
+    /// Responses of every endpoint, not a form.
+    NetworkLog,
+}
+
+impl DebugUiPanel {
+    /// One panel per section, in tab order, showing its default form.
+    pub fn sections() -> [DebugUiPanel; 8] {
+        [
+            DebugUiPanel::Account(default()),
+            DebugUiPanel::Class(default()),
+            DebugUiPanel::Asset(default()),
+            DebugUiPanel::Bag(default()),
+            DebugUiPanel::Bundle(default()),
+            DebugUiPanel::Market(default()),
+            DebugUiPanel::Validator(default()),
+            DebugUiPanel::NetworkLog,
+        ]
+    }
+
+    /// Every form of every section, for the command palette.
+    pub fn all() -> Vec<DebugUiPanel> {
+        use AccountActions::*;
+        use AssetActions::*;
+        use BagActions::*;
+        use BundleActions::*;
+        use ClassActions::*;
+        use MarketActions::*;
+        use ValidatorActions::*;
+        let account = [
+            CreateAccount,
+            FundAccount,
+            GetAccountBalance,
+            GetSeededAccount,
+            GetAccountExists,
+        ];
+        let asset = [
+            CreateAsset,
+            AssetInfo,
+            UpdateAssetMetadata,
+            AssetMint,
+            AssetBurn,
+            AssetBalance,
+            AssetBalances,
+            AssetTransferFrom,
+        ];
+        let market = [
+            CreateMarket,
+            CreateMarketRate,
+            DepositMarketAssets,
+            ExchangeMarketAssets,
+            Simulator,
+            Wizard,
+        ];
+        account
+            .into_iter()
+            .map(DebugUiPanel::Account)
+            .chain([CreateClass, ClassInfo].map(DebugUiPanel::Class))
+            .chain(asset.map(DebugUiPanel::Asset))
+            .chain(
+                [CreateBag, RegisterBag, SweepBag, DepositBag, InspectBag].map(DebugUiPanel::Bag),
+            )
+            .chain([RegisterBundle, MintBundle, BurnBundle].map(DebugUiPanel::Bundle))
+            .chain(market.map(DebugUiPanel::Market))
+            .chain([Dashboard, AddValidator, RemoveValidator].map(DebugUiPanel::Validator))
+            .chain([DebugUiPanel::NetworkLog])
+            .collect()
+    }
+
+    pub fn section(&self) -> DebugUiActions {
+        match self {
+            DebugUiPanel::Account(_) => DebugUiActions::Account,
+            DebugUiPanel::Class(_) => DebugUiActions::Class,
+            DebugUiPanel::Asset(_) => DebugUiActions::Asset,
+            DebugUiPanel::Bag(_) => DebugUiActions::Bag,
+            DebugUiPanel::Bundle(_) => DebugUiActions::Bundle,
+            DebugUiPanel::Market(_) => DebugUiActions::Market,
+            DebugUiPanel::Validator(_) => DebugUiActions::Validator,
+            DebugUiPanel::NetworkLog => DebugUiActions::Network,
+        }
+    }
+
+    pub fn section_name(&self) -> &'static str {
+        match self {
+            DebugUiPanel::Account(_) => "Account",
+            DebugUiPanel::Class(_) => "Class",
+            DebugUiPanel::Asset(_) => "Asset",
+            DebugUiPanel::Bag(_) => "Bag",
+            DebugUiPanel::Bundle(_) => "Bundle",
+            DebugUiPanel::Market(_) => "Market",
+            DebugUiPanel::Validator(_) => "Validator",
+            DebugUiPanel::NetworkLog => "Network",
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        match self {
+            DebugUiPanel::Account(form) => match form {
+                AccountActions::CreateAccount => "Create",
+                AccountActions::FundAccount => "Fund",
+                AccountActions::GetAccountBalance => "Balance",
+                AccountActions::GetSeededAccount => "Seeded",
+                AccountActions::GetAccountExists => "Exists",
+            },
+            DebugUiPanel::Class(form) => match form {
+                ClassActions::CreateClass => "Create",
+                ClassActions::ClassInfo => "Info",
+            },
+            DebugUiPanel::Asset(form) => match form {
+                AssetActions::CreateAsset => "Create",
+                AssetActions::AssetInfo => "Info",
+                AssetActions::UpdateAssetMetadata => "Update Metadata",
+                AssetActions::AssetMint => "Mint",
+                AssetActions::AssetBurn => "Burn",
+                AssetActions::AssetBalance => "Balance",
+                AssetActions::AssetBalances => "Balances",
+                AssetActions::AssetTransferFrom => "Transfer From",
+            },
+            DebugUiPanel::Bag(form) => match form {
+                BagActions::CreateBag => "Create",
+                BagActions::RegisterBag => "Register",
+                BagActions::SweepBag => "Sweep",
+                BagActions::DepositBag => "Deposit",
+                BagActions::InspectBag => "Inspect",
+            },
+            DebugUiPanel::Bundle(form) => match form {
+                BundleActions::RegisterBundle => "Register",
+                BundleActions::MintBundle => "Mint",
+                BundleActions::BurnBundle => "Burn",
+            },
+            DebugUiPanel::Market(form) => match form {
+                MarketActions::CreateMarket => "Create",
+                MarketActions::CreateMarketRate => "Create Rate",
+                MarketActions::DepositMarketAssets => "Deposit",
+                MarketActions::ExchangeMarketAssets => "Exchange",
+                MarketActions::Simulator => "Simulate",
+                MarketActions::Wizard => "Wizard",
+            },
+            DebugUiPanel::Validator(form) => match form {
+                ValidatorActions::Dashboard => "Dashboard",
+                ValidatorActions::AddValidator => "Add",
+                ValidatorActions::RemoveValidator => "Remove",
+            },
+            DebugUiPanel::NetworkLog => "Log",
+        }
+    }
+
+    pub fn title(&self) -> String {
+        format!("{}: {}", self.section_name(), self.form_name())
+    }
+}
+
+/// A panel floating in its own window. The id keys the egui window, so its
+/// position and size stay with the panel when other windows close.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DockWindow {
+    pub id: u64,
+    pub panel: DebugUiPanel,
+}
+
+/// Which panels are docked as tabs in the main window, which one is split
+/// to the right of the active tab, and which float in their own windows.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct DockLayout {
+    pub tabs: Vec<DebugUiPanel>,
+    pub active: usize,
+    pub split: Option<usize>,
+    pub windows: Vec<DockWindow>,
+    next_window_id: u64,
+}
+
+impl Default for DockLayout {
+    fn default() -> Self {
+        Self {
+            tabs: DebugUiPanel::sections().to_vec(),
+            active: 0,
+            split: None,
+            windows: vec![],
+            next_window_id: 0,
+        }
+    }
+}
+
+impl DockLayout {
+    /// Shows `panel`'s form in a panel of the same section, preferring
+    /// visible ones, or docks a new tab for it.
+    pub fn focus(&mut self, panel: DebugUiPanel) {
+        let section = panel.section();
+        let visible = [Some(self.active), self.split]
+            .into_iter()
+            .flatten()
+            .find(|i| self.tabs.get(*i).map(DebugUiPanel::section) == Some(section));
+        if let Some(i) = visible {
+            self.tabs[i] = panel;
+        } else if let Some(window) = self
+            .windows
+            .iter_mut()
+            .find(|window| window.panel.section() == section)
+        {
+            window.panel = panel;
+        } else if let Some(i) = self.tabs.iter().position(|tab| tab.section() == section) {
+            self.tabs[i] = panel;
+            self.active = i;
+        } else {
+            self.tabs.push(panel);
+            self.active = self.tabs.len() - 1;
+        }
+        if self.split == Some(self.active) {
+            self.split = None;
+        }
+    }
+
+    /// Activates the tab `offset` places away, wrapping around.
+    pub fn cycle(&mut self, offset: isize) {
+        if self.tabs.is_empty() {
+            return;
+        }
+        let count = self.tabs.len() as isize;
+        self.active = (self.active as isize + offset).rem_euclid(count) as usize;
+        if self.split == Some(self.active) {
+            self.split = None;
+        }
+    }
+
+    pub fn close_tab(&mut self, i: usize) {
+        if i >= self.tabs.len() {
+            return;
+        }
+        self.tabs.remove(i);
+        self.split = match self.split {
+            Some(split) if split == i => None,
+            Some(split) if split > i => Some(split - 1),
+            split => split,
+        };
+        if self.active > i || self.active >= self.tabs.len() {
+            self.active = self.active.saturating_sub(1);
+        }
+        if self.split == Some(self.active) {
+            self.split = None;
+        }
+    }
+
+    pub fn undock(&mut self, i: usize) {
+        if let Some(panel) = self.tabs.get(i).copied() {
+            let id = self.next_window_id;
+            self.next_window_id += 1;
+            self.windows.push(DockWindow { id, panel });
+            self.close_tab(i);
+        }
+    }
+
+    pub fn dock(&mut self, window: usize) {
+        if window < self.windows.len() {
+            self.tabs.push(self.windows.remove(window).panel);
+            self.active = self.tabs.len() - 1;
+        }
+    }
+}
+
+/// The current layout and the layouts saved by name.
+#[derive(Resource, Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct DebugUiLayouts {
+    pub current: DockLayout,
+    pub named: BTreeMap<String, DockLayout>,
+    #[serde(skip)]
+    pub new_name: String,
+}
+
+/// Every debug UI section, so panels can draw any of them.
+#[derive(SystemParam)]
+pub struct DebugUiSections<'w> {
+    pub account: ResMut<'w, AccountUi>,
+    pub class: ResMut<'w, ClassUi>,
+    pub asset: ResMut<'w, AssetUi>,
+    pub bag: ResMut<'w, BagUi>,
+    pub bundle: ResMut<'w, BundleUi>,
+    pub market: ResMut<'w, MarketUi>,
+    pub validator: ResMut<'w, ValidatorUi>,
+    pub network: ResMut<'w, NetworkLogUi>,
+    pub log: ResMut<'w, ApiLog>,
+    /// For the URL of copied requests.
+    pub api: Res<'w, ApiClient>,
+}
+
+/// Runs `$body` with the section's selected form swapped for the panel's,
+/// keeping any form change made while drawing on the panel.
+macro_rules! with_form {
+    ($section:expr, $form:expr, $body:expr) => {{
+        let previous = std::mem::replace(&mut $section.actions, *$form);
+        $body;
+        *$form = std::mem::replace(&mut $section.actions, previous);
+    }};
+}
+
+impl DebugUiSections<'_> {
+    /// The panel of `section` showing that section's selected form.
+    pub fn panel(&self, section: &DebugUiActions) -> DebugUiPanel {
+        match section {
+            DebugUiActions::Account => DebugUiPanel::Account(self.account.actions),
+            DebugUiActions::Class => DebugUiPanel::Class(self.class.actions),
+            DebugUiActions::Asset => DebugUiPanel::Asset(self.asset.actions),
+            DebugUiActions::Bag => DebugUiPanel::Bag(self.bag.actions),
+            DebugUiActions::Bundle => DebugUiPanel::Bundle(self.bundle.actions),
+            DebugUiActions::Market => DebugUiPanel::Market(self.market.actions),
+            DebugUiActions::Validator => DebugUiPanel::Validator(self.validator.actions),
+            DebugUiActions::Network => DebugUiPanel::NetworkLog,
+        }
+    }
+
+    pub fn panel_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        panel: &mut DebugUiPanel,
+        registry: &IdRegistry,
+        links: &mut DebugUiLinks,
+    ) {
+        let url = self.api.0.url();
+        match panel {
+            DebugUiPanel::Account(form) => with_form!(self.account, form, {
+                request_menu_ui(ui, url, self.account.request_form());
+                account::account_ui(ui, &mut self.account, links)
+            }),
+            DebugUiPanel::Class(form) => with_form!(self.class, form, {
+                request_menu_ui(ui, url, self.class.request_form());
+                class::class_ui(ui, &mut self.class, registry, links)
+            }),
+            DebugUiPanel::Asset(form) => with_form!(self.asset, form, {
+                request_menu_ui(ui, url, self.asset.request_form());
+                asset::asset_ui(ui, &mut self.asset, registry, links)
+            }),
+            DebugUiPanel::Bag(form) => with_form!(self.bag, form, {
+                request_menu_ui(ui, url, self.bag.request_form());
+                bag::bag_ui(ui, &mut self.bag, registry, links)
+            }),
+            DebugUiPanel::Bundle(form) => with_form!(self.bundle, form, {
+                request_menu_ui(ui, url, self.bundle.request_form());
+                bundle::bundle_ui(ui, &mut self.bundle, registry, links)
+            }),
+            DebugUiPanel::Market(form) => with_form!(self.market, form, {
+                request_menu_ui(ui, url, self.market.request_form());
+                market::market_ui(ui, &mut self.market, registry, links)
+            }),
+            DebugUiPanel::Validator(form) => with_form!(self.validator, form, {
+                request_menu_ui(ui, url, self.validator.request_form());
+                validator::validator_ui(ui, &mut self.validator)
+            }),
+            DebugUiPanel::NetworkLog => {
+                network_log::network_log_ui(ui, &mut self.network, &mut self.log)
+            }
+        }
+    }
+
+    pub fn reset_form(&mut self, panel: &mut DebugUiPanel) {
+        match panel {
+            DebugUiPanel::Account(form) => {
+                with_form!(self.account, form, self.account.reset_form())
+            }
+            DebugUiPanel::Class(form) => with_form!(self.class, form, self.class.reset_form()),
+            DebugUiPanel::Asset(form) => with_form!(self.asset, form, self.asset.reset_form()),
+            DebugUiPanel::Bag(form) => with_form!(self.bag, form, self.bag.reset_form()),
+            DebugUiPanel::Bundle(form) => with_form!(self.bundle, form, self.bundle.reset_form()),
+            DebugUiPanel::Market(form) => with_form!(self.market, form, self.market.reset_form()),
+            DebugUiPanel::Validator(form) => {
+                with_form!(self.validator, form, self.validator.reset_form())
+            }
+            DebugUiPanel::NetworkLog => *self.network = NetworkLogUi::default(),
+        }
+    }
+}
+
+/// Tab strip of the docked panels. Right-clicking a tab splits, undocks or
+/// closes it; "+" docks a new panel.
+pub fn tabs_ui(ui: &mut egui::Ui, layout: &mut DockLayout) {
+    let mut split = None;
+    let mut undock = None;
+    let mut close = None;
+    ui.horizontal_wrapped(|ui| {
+        for (i, panel) in layout.tabs.iter().enumerate() {
+            let selected = layout.active == i || layout.split == Some(i);
+            let response = ui
+                .selectable_label(selected, panel.title())
+                .on_hover_text("Right-click to split, undock or close");
+            if response.clicked() {
+                layout.active = i;
+                if layout.split == Some(i) {
+                    layout.split = None;
+                }
+            }
+            response.context_menu(|ui| {
+                if layout.active != i && ui.button("Split Right").clicked() {
+                    split = Some(i);
+                    ui.close_menu();
+                }
+                if layout.split == Some(i) && ui.button("Unsplit").clicked() {
+                    layout.split = None;
+                    ui.close_menu();
+                }
+                if ui.button("Open In Window").clicked() {
+                    undock = Some(i);
+                    ui.close_menu();
+                }
+                if ui.button("Close").clicked() {
+                    close = Some(i);
+                    ui.close_menu();
+                }
+            });
+        }
+        ui.menu_button("+", |ui| {
+            for panel in DebugUiPanel::sections() {
+                if ui.button(panel.section_name()).clicked() {
+                    layout.tabs.push(panel);
+                    layout.active = layout.tabs.len() - 1;
+                    ui.close_menu();
+                }
+            }
+        });
+    });
+    if split.is_some() {
+        layout.split = split;
+    }
+    if let Some(i) = undock {
+        layout.undock(i);
+    } else if let Some(i) = close {
+        layout.close_tab(i);
+    }
+}
+
+pub fn layouts_menu_ui(ui: &mut egui::Ui, layouts: &mut DebugUiLayouts) {
+    ui.menu_button("Layouts", |ui| {
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut layouts.new_name).hint_text("Layout name"));
+            let name = layouts.new_name.trim().to_string();
+            if ui
+                .add_enabled(!name.is_empty(), egui::Button::new("Save"))
+                .clicked()
+            {
+                layouts.named.insert(name, layouts.current.clone());
+                layouts.new_name.clear();
+            }
+        });
+        ui.separator();
+        let mut remove = None;
+        for (name, layout) in layouts.named.iter() {
+            ui.horizontal(|ui| {
+                if ui.button(name).clicked() {
+                    layouts.current = layout.clone();
+                    ui.close_menu();
+                }
+                if ui.small_button("Delete").clicked() {
+                    remove = Some(name.clone());
+                }
+            });
+        }
+        if let Some(name) = remove {
+            layouts.named.remove(&name);
+        }
+        ui.separator();
+        if ui.button("Default Layout").clicked() {
+            layouts.current = DockLayout::default();
+            ui.close_menu();
+        }
+    });
+}
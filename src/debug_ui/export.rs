@@ -0,0 +1,266 @@
+//! Copying a form's request as curl, JSON or a Rust client call, and filling
+//! a form in from pasted request JSON.
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+use serde::de::DeserializeOwned;
+use sugarfunge_api_types::primitives::Balance;
+use sugarfunge_client::units::to_whole_tokens;
+
+use crate::api::Endpoint;
+
+/// Stands in for seeds in exported requests. Pasting it back keeps the
+/// form's own seed.
+pub const REDACTED_SEED: &str = "<redacted>";
+
+pub type FormInput<F> = <<F as RequestForm>::Endpoint as Endpoint>::Input;
+
+/// A debug UI form that sends one endpoint's input.
+pub trait RequestForm: Clone {
+    type Endpoint: Endpoint;
+
+    /// The input the form's submit button sends.
+    fn input(&self) -> Result<FormInput<Self>, String>;
+
+    /// Fills the form in from `input`, failing on values it can't hold.
+    fn set_input(&mut self, input: FormInput<Self>) -> Result<(), String>;
+
+    fn seed(&mut self) -> Option<&mut String> {
+        None
+    }
+}
+
+/// The form's input for its submit button, logging why there is none.
+pub fn form_input<F: RequestForm>(form: &F) -> Option<FormInput<F>> {
+    form.input()
+        .map_err(|err| warn!("{}: {}", F::Endpoint::PATH, err))
+        .ok()
+}
+
+/// Object-safe side of `RequestForm`, so a section can hand out whichever
+/// form is selected.
+pub trait RequestExport {
+    fn path(&self) -> &'static str;
+    fn request_json(&self, redact_seeds: bool) -> Result<String, String>;
+    /// Call of the endpoint's `SugarFungeClient` method against `url`.
+    fn rust_call(&self, url: &str, redact_seeds: bool) -> Result<String, String>;
+    fn paste_json(&mut self, json: &str) -> Result<(), String>;
+}
+
+impl<F: RequestForm> RequestExport for F
+where
+    FormInput<F>: DeserializeOwned,
+{
+    fn path(&self) -> &'static str {
+        F::Endpoint::PATH
+    }
+
+    fn request_json(&self, redact_seeds: bool) -> Result<String, String> {
+        let input = if redact_seeds {
+            let mut form = self.clone();
+            if let Some(seed) = form.seed() {
+                *seed = REDACTED_SEED.to_string();
+            }
+            form.input()?
+        } else {
+            self.input()?
+        };
+        serde_json::to_string_pretty(&input).map_err(|err| err.to_string())
+    }
+
+    fn rust_call(&self, url: &str, redact_seeds: bool) -> Result<String, String> {
+        let json = self.request_json(redact_seeds)?;
+        let client = format!("let client = SugarFungeClient::new({:?});", url);
+        // Endpoints without input have methods without an input argument.
+        if json == "null" {
+            return Ok(format!(
+                "{}\nlet output = client.{}().await?;",
+                client,
+                F::Endpoint::METHOD
+            ));
+        }
+        // Enough `#`s that no `"#…` inside the JSON ends the raw string.
+        let mut hashes = "#".to_string();
+        while json.contains(&format!("\"{}", hashes)) {
+            hashes.push('#');
+        }
+        Ok(format!(
+            "{}\nlet input = serde_json::from_str(r{}\"{}\"{})?;\n\
+             let output = client.{}(&input).await?;",
+            client,
+            hashes,
+            json,
+            hashes,
+            F::Endpoint::METHOD,
+        ))
+    }
+
+    fn paste_json(&mut self, json: &str) -> Result<(), String> {
+        let input = serde_json::from_str::<FormInput<F>>(json).map_err(|err| err.to_string())?;
+        let previous_seed = self.seed().cloned();
+        let mut form = self.clone();
+        form.set_input(input)?;
+        if let (Some(previous), Some(seed)) = (previous_seed, form.seed()) {
+            if seed == REDACTED_SEED {
+                *seed = previous;
+            }
+        }
+        *self = form;
+        Ok(())
+    }
+}
+
+fn curl(form: &dyn RequestExport, url: &str, redact_seeds: bool) -> Result<String, String> {
+    let json = form.request_json(redact_seeds)?;
+    Ok(format!(
+        "curl -X POST '{}/{}' \\\n  -H 'Content-Type: application/json' \\\n  -d '{}'",
+        url,
+        form.path(),
+        json.replace('\'', "'\\''"),
+    ))
+}
+
+/// The form's amount field, for inputs that send it unscaled.
+pub fn form_amount(amount: Balance) -> Result<u64, String> {
+    u64::try_from(u128::from(amount))
+        .map_err(|_| format!("Amount {} is too large for the form", u128::from(amount)))
+}
+
+/// Whole tokens, for inputs that send the form's amount scaled by
+/// `whole_tokens`.
+pub fn form_whole_amount(amount: Balance) -> Result<u64, String> {
+    let units = u128::from(amount);
+    let tokens = to_whole_tokens(amount)
+        .ok_or_else(|| format!("Amount {} is not a whole number of tokens", units))?;
+    u64::try_from(tokens).map_err(|_| format!("Amount {} is too large for the form", units))
+}
+
+pub fn metadata_input(metadata: &str) -> Result<serde_json::Value, String> {
+    serde_json::from_str(metadata).map_err(|err| format!("Invalid metadata: {}", err))
+}
+
+fn redact_id() -> egui::Id {
+    egui::Id::new("request_export_redact_seeds")
+}
+
+/// "Request" menu of the selected form: copies its request as curl, JSON or
+/// a Rust client call against `url`, or fills it in from pasted request JSON.
+pub fn request_menu_ui(ui: &mut egui::Ui, url: &str, form: Option<&mut dyn RequestExport>) {
+    let Some(form) = form else {
+        return;
+    };
+    let paste_id = egui::Id::new(("request_export_paste", form.path()));
+    let error_id = egui::Id::new(("request_export_error", form.path()));
+    ui.menu_button("Request", |ui| {
+        let mut redact = ui.data_mut(|data| *data.get_persisted_mut_or(redact_id(), true));
+        if ui.checkbox(&mut redact, "Redact seeds").changed() {
+            ui.data_mut(|data| data.insert_persisted(redact_id(), redact));
+        }
+        let mut error = ui.data_mut(|data| data.get_temp::<String>(error_id));
+        let mut copied = None;
+        if ui.button("Copy as curl").clicked() {
+            copied = Some(curl(form, url, redact));
+        }
+        if ui.button("Copy Request JSON").clicked() {
+            copied = Some(form.request_json(redact));
+        }
+        if ui.button("Copy as Rust Client Call").clicked() {
+            copied = Some(form.rust_call(url, redact));
+        }
+        match copied {
+            Some(Ok(text)) => {
+                ui.output_mut(|output| output.copied_text = text);
+                error = None;
+                ui.close_menu();
+            }
+            Some(Err(err)) => error = Some(err),
+            None => {}
+        }
+        ui.separator();
+        ui.label("Paste request JSON");
+        let mut paste = ui.data_mut(|data| data.get_temp::<String>(paste_id).unwrap_or_default());
+        ui.add(
+            egui::TextEdit::multiline(&mut paste)
+                .code_editor()
+                .desired_rows(4),
+        );
+        if ui.button("Fill Form").clicked() {
+            match form.paste_json(&paste) {
+                Ok(()) => {
+                    paste.clear();
+                    error = None;
+                    ui.close_menu();
+                }
+                Err(err) => error = Some(err),
+            }
+        }
+        if let Some(err) = &error {
+            ui.colored_label(egui::Color32::RED, err);
+        }
+        ui.data_mut(|data| {
+            data.insert_temp(paste_id, paste);
+            match error {
+                Some(err) => data.insert_temp(error_id, err),
+                None => data.remove::<String>(error_id),
+            }
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::debug_ui::{
+        account::create::CreateAccountInputData, asset::mint::AssetMintInputData,
+        market::create_market_rate::CreateMarketRateInputData,
+    };
+
+    #[test]
+    fn rust_call_uses_client_method_and_url() {
+        let mint = AssetMintInputData::default()
+            .rust_call("http://node", true)
+            .unwrap();
+        assert!(mint.starts_with("let client = SugarFungeClient::new(\"http://node\");\n"));
+        assert!(mint.ends_with("let output = client.asset_mint(&input).await?;"));
+        assert!(mint.contains(REDACTED_SEED));
+        assert_eq!(
+            CreateAccountInputData::default()
+                .rust_call("http://node", true)
+                .unwrap(),
+            "let client = SugarFungeClient::new(\"http://node\");\n\
+             let output = client.create_account().await?;"
+        );
+    }
+
+    #[test]
+    fn curl_posts_to_url() {
+        let curl = curl(&AssetMintInputData::default(), "http://node", false).unwrap();
+        assert!(curl.starts_with("curl -X POST 'http://node/asset/mint'"));
+    }
+
+    #[test]
+    fn paste_rejects_rate_amounts_the_form_cant_hold() {
+        let json = r#"{
+            "seed": "//Alice",
+            "market_id": 7,
+            "market_rate_id": 1,
+            "rates": {
+                "rates": [{
+                    "class_id": 1,
+                    "asset_id": 1,
+                    "action": { "Burn": 9223372036854775808 },
+                    "from": "Buyer",
+                    "to": "Market"
+                }],
+                "metadata": null
+            }
+        }"#;
+        let mut form = CreateMarketRateInputData::default();
+        assert!(form.paste_json(json).unwrap_err().contains("too large"));
+        assert_eq!(u64::from(form.market_id), 0);
+        let fits = json.replace("9223372036854775808", "5");
+        form.paste_json(&fits).unwrap();
+        assert_eq!(u64::from(form.market_id), 7);
+        assert_eq!(form.rates[0].action_data.burn, 5);
+    }
+}
@@ -0,0 +1,320 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+use sugarfunge_api_types::primitives::{Account, AssetId, BundleId, ClassId, MarketId};
+
+use super::DebugUiActions;
+
+/// An account or ID shown in an output panel.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinkValue {
+    Account(Account),
+    Bag(Account),
+    Class(ClassId),
+    Asset(ClassId, AssetId),
+    Bundle(BundleId),
+    Market(MarketId),
+}
+
+/// A form field a chip can pre-fill. Account targets name the field they
+/// fill; ID targets fill the class, asset, bundle or market ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkTarget {
+    AccountBalance,
+    FundAccount,
+    CreateClass,
+    ClassInfo,
+    CreateAsset,
+    AssetInfo,
+    UpdateAssetMetadata,
+    MintAsset,
+    BurnAsset,
+    AssetBalance,
+    AssetBalances,
+    TransferFrom,
+    TransferTo,
+    CreateBag,
+    SweepBag,
+    SweepBagTo,
+    DepositBag,
+    InspectBag,
+    RegisterBundle,
+    MintBundle,
+    BurnBundle,
+    CreateMarketRate,
+    DepositMarketAssets,
+    ExchangeMarketAssets,
+}
+
+impl LinkTarget {
+    pub fn section(&self) -> DebugUiActions {
+        match self {
+            LinkTarget::AccountBalance | LinkTarget::FundAccount => DebugUiActions::Account,
+            LinkTarget::CreateClass | LinkTarget::ClassInfo => DebugUiActions::Class,
+            LinkTarget::CreateAsset
+            | LinkTarget::AssetInfo
+            | LinkTarget::UpdateAssetMetadata
+            | LinkTarget::MintAsset
+            | LinkTarget::BurnAsset
+            | LinkTarget::AssetBalance
+            | LinkTarget::AssetBalances
+            | LinkTarget::TransferFrom
+            | LinkTarget::TransferTo => DebugUiActions::Asset,
+            LinkTarget::CreateBag
+            | LinkTarget::SweepBag
+            | LinkTarget::SweepBagTo
+            | LinkTarget::DepositBag
+            | LinkTarget::InspectBag => DebugUiActions::Bag,
+            LinkTarget::RegisterBundle | LinkTarget::MintBundle | LinkTarget::BurnBundle => {
+                DebugUiActions::Bundle
+            }
+            LinkTarget::CreateMarketRate
+            | LinkTarget::DepositMarketAssets
+            | LinkTarget::ExchangeMarketAssets => DebugUiActions::Market,
+        }
+    }
+}
+
+impl LinkValue {
+    pub fn account(&self) -> Option<&Account> {
+        match self {
+            LinkValue::Account(account) | LinkValue::Bag(account) => Some(account),
+            _ => None,
+        }
+    }
+
+    pub fn class_id(&self) -> Option<ClassId> {
+        match self {
+            LinkValue::Class(class_id) | LinkValue::Asset(class_id, _) => Some(*class_id),
+            _ => None,
+        }
+    }
+
+    pub fn asset_id(&self) -> Option<AssetId> {
+        match self {
+            LinkValue::Asset(_, asset_id) => Some(*asset_id),
+            _ => None,
+        }
+    }
+
+    pub fn bundle_id(&self) -> Option<&BundleId> {
+        match self {
+            LinkValue::Bundle(bundle_id) => Some(bundle_id),
+            _ => None,
+        }
+    }
+
+    pub fn market_id(&self) -> Option<MarketId> {
+        match self {
+            LinkValue::Market(market_id) => Some(*market_id),
+            _ => None,
+        }
+    }
+
+    /// Text copied to the clipboard.
+    pub fn text(&self) -> String {
+        match self {
+            LinkValue::Account(account) | LinkValue::Bag(account) => account.to_string(),
+            LinkValue::Class(class_id) => u64::from(*class_id).to_string(),
+            LinkValue::Asset(_, asset_id) => u64::from(*asset_id).to_string(),
+            LinkValue::Bundle(bundle_id) => bundle_id.to_string(),
+            LinkValue::Market(market_id) => u64::from(*market_id).to_string(),
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            LinkValue::Account(account) | LinkValue::Bag(account) => short_account(account),
+            LinkValue::Class(class_id) => format!("Class {}", u64::from(*class_id)),
+            LinkValue::Asset(class_id, asset_id) => {
+                format!("Asset {}/{}", u64::from(*class_id), u64::from(*asset_id))
+            }
+            LinkValue::Bundle(bundle_id) => format!("Bundle {}", short_account(bundle_id)),
+            LinkValue::Market(market_id) => format!("Market {}", u64::from(*market_id)),
+        }
+    }
+
+    fn view_balance(&self) -> Option<LinkTarget> {
+        match self {
+            LinkValue::Account(_) => Some(LinkTarget::AccountBalance),
+            LinkValue::Bag(_) => Some(LinkTarget::InspectBag),
+            _ => None,
+        }
+    }
+
+    fn info(&self) -> Option<LinkTarget> {
+        match self {
+            LinkValue::Class(_) => Some(LinkTarget::ClassInfo),
+            LinkValue::Asset(..) => Some(LinkTarget::AssetInfo),
+            _ => None,
+        }
+    }
+
+    /// Forms this value can be used in, with their menu labels.
+    fn targets(&self) -> &'static [(LinkTarget, &'static str)] {
+        match self {
+            LinkValue::Account(_) => &[
+                (LinkTarget::AccountBalance, "Account Balance"),
+                (LinkTarget::FundAccount, "Fund Account: To"),
+                (LinkTarget::CreateClass, "Create Class: Owner"),
+                (LinkTarget::AssetBalances, "Asset Balances"),
+                (LinkTarget::MintAsset, "Mint Asset: To"),
+                (LinkTarget::BurnAsset, "Burn Asset: From"),
+                (LinkTarget::TransferFrom, "Transfer: From"),
+                (LinkTarget::TransferTo, "Transfer: To"),
+                (LinkTarget::CreateBag, "Create Bag: Owner"),
+                (LinkTarget::SweepBagTo, "Sweep Bag: To"),
+                (LinkTarget::MintBundle, "Mint Bundle: To"),
+                (LinkTarget::BurnBundle, "Burn Bundle: From"),
+            ],
+            LinkValue::Bag(_) => &[
+                (LinkTarget::InspectBag, "Inspect Bag"),
+                (LinkTarget::AssetBalances, "Asset Balances"),
+                (LinkTarget::SweepBag, "Sweep Bag"),
+                (LinkTarget::DepositBag, "Deposit Into Bag"),
+                (LinkTarget::MintAsset, "Mint Asset: To"),
+                (LinkTarget::TransferTo, "Transfer: To"),
+            ],
+            LinkValue::Class(_) => &[
+                (LinkTarget::ClassInfo, "Class Info"),
+                (LinkTarget::CreateAsset, "Create Asset"),
+                (LinkTarget::MintAsset, "Mint Asset"),
+                (LinkTarget::BurnAsset, "Burn Asset"),
+                (LinkTarget::AssetBalance, "Asset Balance"),
+                (LinkTarget::TransferFrom, "Transfer"),
+                (LinkTarget::CreateBag, "Create Bag"),
+            ],
+            LinkValue::Asset(..) => &[
+                (LinkTarget::AssetInfo, "Asset Info"),
+                (LinkTarget::UpdateAssetMetadata, "Update Metadata"),
+                (LinkTarget::MintAsset, "Mint Asset"),
+                (LinkTarget::BurnAsset, "Burn Asset"),
+                (LinkTarget::AssetBalance, "Asset Balance"),
+                (LinkTarget::TransferFrom, "Transfer"),
+                (LinkTarget::RegisterBundle, "Register Bundle"),
+            ],
+            LinkValue::Bundle(_) => &[
+                (LinkTarget::MintBundle, "Mint Bundle"),
+                (LinkTarget::BurnBundle, "Burn Bundle"),
+            ],
+            LinkValue::Market(_) => &[
+                (LinkTarget::CreateMarketRate, "Create Market Rate"),
+                (LinkTarget::DepositMarketAssets, "Deposit Market Assets"),
+                (LinkTarget::ExchangeMarketAssets, "Exchange Market Assets"),
+            ],
+        }
+    }
+}
+
+fn short_account(account: &str) -> String {
+    if account.chars().count() <= 12 {
+        return account.to_string();
+    }
+    let start: String = account.chars().take(6).collect();
+    let end: String = account.chars().skip(account.chars().count() - 4).collect();
+    format!("{}…{}", start, end)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AddressBookEntry {
+    pub name: String,
+    pub account: Account,
+}
+
+/// Chip actions picked this frame, applied after the debug UI is drawn, and
+/// the accounts saved to the address book.
+#[derive(Resource, Debug, Default)]
+pub struct DebugUiLinks {
+    pub pending: Vec<(LinkTarget, LinkValue)>,
+    pub address_book: Vec<AddressBookEntry>,
+}
+
+impl DebugUiLinks {
+    pub fn add_to_address_book(&mut self, account: &Account) {
+        if self
+            .address_book
+            .iter()
+            .any(|entry| entry.account == *account)
+        {
+            return;
+        }
+        self.address_book.push(AddressBookEntry {
+            name: format!("Account {}", self.address_book.len() + 1),
+            account: account.clone(),
+        });
+    }
+}
+
+/// Clicking copies the value; right-clicking offers the forms it can fill.
+pub fn link_chip(ui: &mut egui::Ui, links: &mut DebugUiLinks, value: LinkValue) {
+    let response = ui
+        .add(egui::Button::new(value.label()).small())
+        .on_hover_text(format!(
+            "{}\nClick to copy, right-click for actions",
+            value.text()
+        ));
+    if response.clicked() {
+        ui.output_mut(|output| output.copied_text = value.text());
+    }
+    response.context_menu(|ui| {
+        if ui.button("Copy").clicked() {
+            ui.output_mut(|output| output.copied_text = value.text());
+            ui.close_menu();
+        }
+        if let Some(target) = value.view_balance() {
+            if ui.button("View Balance").clicked() {
+                links.pending.push((target, value.clone()));
+                ui.close_menu();
+            }
+        }
+        if let Some(target) = value.info() {
+            if ui.button("Open Info").clicked() {
+                links.pending.push((target, value.clone()));
+                ui.close_menu();
+            }
+        }
+        ui.menu_button("Use As Input In", |ui| {
+            for (target, label) in value.targets() {
+                if ui.button(*label).clicked() {
+                    links.pending.push((*target, value.clone()));
+                    ui.close_menu();
+                }
+            }
+        });
+        if let Some(account) = value.account() {
+            if ui.button("Add To Address Book").clicked() {
+                links.add_to_address_book(account);
+                ui.close_menu();
+            }
+        }
+    });
+}
+
+pub fn address_book_ui(ui: &mut egui::Ui, links: &mut DebugUiLinks) {
+    let mut book = std::mem::take(&mut links.address_book);
+    let mut remove_index = None;
+    egui::CollapsingHeader::new(format!("Address Book ({})", book.len())).show(ui, |ui| {
+        if book.is_empty() {
+            ui.label("Right-click an account in an output to add it here.");
+        }
+        for (i, entry) in book.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut entry.name).desired_width(100.0));
+                link_chip(ui, links, LinkValue::Account(entry.account.clone()));
+                if ui.button("Remove").clicked() {
+                    remove_index = Some(i);
+                }
+            });
+        }
+    });
+    if let Some(index) = remove_index {
+        book.remove(index);
+    }
+    // The book's own chips can't add duplicates while it is taken out.
+    for entry in std::mem::take(&mut links.address_book) {
+        if !book.iter().any(|other| other.account == entry.account) {
+            book.push(entry);
+        }
+    }
+    links.address_book = book;
+}
@@ -0,0 +1,392 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+use sugarfunge_api_types::primitives::Account;
+
+use crate::debug_ui::registry::{asset_id_ui, class_id_ui, IdRegistry};
+
+use super::{
+    rate_account::{market_rate_account_ui, MarketRateAccount, MarketRateAccountInputData},
+    rate_action::{market_rate_action_ui, MarketRateAction},
+    MarketRateInputData,
+};
+
+const NODE_RADIUS: f32 = 24.0;
+const EDGE_SPACING: f32 = 28.0;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MarketRateNode {
+    Buyer,
+    Market,
+    Account(String),
+}
+
+impl MarketRateNode {
+    fn of(account: &MarketRateAccountInputData) -> Self {
+        match account.rate_account {
+            MarketRateAccount::Buyer => MarketRateNode::Buyer,
+            MarketRateAccount::Market => MarketRateNode::Market,
+            MarketRateAccount::Account => MarketRateNode::Account(account.account.to_string()),
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            MarketRateNode::Buyer => "Buyer".to_string(),
+            MarketRateNode::Market => "Market".to_string(),
+            MarketRateNode::Account(account) if account.chars().count() > 8 => {
+                format!("{}…", account.chars().take(8).collect::<String>())
+            }
+            MarketRateNode::Account(account) => account.clone(),
+        }
+    }
+
+    fn rate_account(&self) -> MarketRateAccountInputData {
+        let mut input = MarketRateAccountInputData::default();
+        match self {
+            MarketRateNode::Buyer => input.rate_account = MarketRateAccount::Buyer,
+            MarketRateNode::Market => input.rate_account = MarketRateAccount::Market,
+            MarketRateNode::Account(account) => {
+                input.rate_account = MarketRateAccount::Account;
+                input.account = Account::from(account.clone());
+                input.account_enabled = true;
+            }
+        }
+        input
+    }
+}
+
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarketRatesGraphInputData {
+    pub enabled: bool,
+    /// Node positions, normalized to the canvas size.
+    #[serde(skip)]
+    pub nodes: Vec<(MarketRateNode, egui::Vec2)>,
+    #[serde(skip)]
+    pub selected: Option<usize>,
+    #[serde(skip)]
+    pub connect_from: Option<MarketRateNode>,
+    /// Account nodes added with "Add Account Node", kept without any rate.
+    #[serde(skip)]
+    pub added: Vec<MarketRateNode>,
+    pub new_account: String,
+}
+
+impl Default for MarketRatesGraphInputData {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            nodes: vec![
+                (MarketRateNode::Buyer, egui::vec2(0.15, 0.5)),
+                (MarketRateNode::Market, egui::vec2(0.85, 0.5)),
+            ],
+            selected: None,
+            connect_from: None,
+            added: vec![],
+            new_account: String::default(),
+        }
+    }
+}
+
+impl MarketRatesGraphInputData {
+    fn add_node(&mut self, node: MarketRateNode) {
+        if self.nodes.iter().all(|(existing, _)| *existing != node) {
+            let accounts = self.nodes.len().saturating_sub(2) as f32;
+            let position = egui::vec2(0.3 + (accounts * 0.2) % 0.6, 0.12 + (accounts * 0.3) % 0.8);
+            self.nodes.push((node, position));
+        }
+    }
+
+    /// Matches the nodes to `rates`: adds the accounts they reference and drops
+    /// account nodes nothing references anymore, such as the prefixes typed
+    /// while editing a rate's account.
+    fn sync_nodes(&mut self, rates: &[MarketRateInputData]) {
+        let referenced: Vec<MarketRateNode> = rates
+            .iter()
+            .flat_map(|rate| [MarketRateNode::of(&rate.from), MarketRateNode::of(&rate.to)])
+            .collect();
+        let added = &self.added;
+        self.nodes.retain(|(node, _)| {
+            !matches!(node, MarketRateNode::Account(_))
+                || referenced.contains(node)
+                || added.contains(node)
+        });
+        if let Some(from) = &self.connect_from {
+            if self.nodes.iter().all(|(node, _)| node != from) {
+                self.connect_from = None;
+            }
+        }
+        for node in referenced {
+            self.add_node(node);
+        }
+    }
+
+    fn position(&self, node: &MarketRateNode) -> egui::Vec2 {
+        self.nodes
+            .iter()
+            .find(|(existing, _)| existing == node)
+            .map(|(_, position)| *position)
+            .unwrap_or(egui::vec2(0.5, 0.5))
+    }
+}
+
+fn bezier(start: egui::Pos2, control: egui::Pos2, end: egui::Pos2, t: f32) -> egui::Pos2 {
+    start + (control - start) * (2.0 * (1.0 - t) * t) + (end - start) * (t * t)
+}
+
+fn rate_color(action: &MarketRateAction) -> egui::Color32 {
+    match action {
+        MarketRateAction::Transfer => egui::Color32::LIGHT_BLUE,
+        MarketRateAction::MarketTransfer => egui::Color32::LIGHT_GREEN,
+        MarketRateAction::Mint => egui::Color32::GOLD,
+        MarketRateAction::Burn => egui::Color32::LIGHT_RED,
+        MarketRateAction::Has => egui::Color32::GRAY,
+    }
+}
+
+fn rate_label(rate: &MarketRateInputData) -> String {
+    let action = match rate.action_ui {
+        MarketRateAction::Transfer => format!("Transfer {}", rate.action_data.transfer),
+        MarketRateAction::MarketTransfer => format!(
+            "Market Transfer {:?} {}/{}",
+            rate.action_data.market_transfer.amm,
+            u64::from(rate.action_data.market_transfer.class_id),
+            u64::from(rate.action_data.market_transfer.asset_id)
+        ),
+        MarketRateAction::Mint => format!("Mint {}", rate.action_data.mint),
+        MarketRateAction::Burn => format!("Burn {}", rate.action_data.burn),
+        MarketRateAction::Has => format!(
+            "Has {:?} {}",
+            rate.action_data.has.amount_op, rate.action_data.has.amount
+        ),
+    };
+    format!(
+        "{}\n{}/{}",
+        action,
+        u64::from(rate.class_id),
+        u64::from(rate.asset_id)
+    )
+}
+
+/// Draws buyer, market and named accounts as nodes and every rate as a labeled
+/// edge between them. Edits are applied straight to `rates`.
+pub fn market_rates_graph_ui(
+    ui: &mut egui::Ui,
+    graph: &mut MarketRatesGraphInputData,
+    rates: &mut Vec<MarketRateInputData>,
+    registry: &IdRegistry,
+) {
+    graph.sync_nodes(rates);
+    if graph
+        .selected
+        .map_or(false, |selected| selected >= rates.len())
+    {
+        graph.selected = None;
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Account");
+        ui.text_edit_singleline(&mut graph.new_account);
+        if ui.button("Add Account Node").clicked() && !graph.new_account.is_empty() {
+            let node = MarketRateNode::Account(std::mem::take(&mut graph.new_account));
+            if !graph.added.contains(&node) {
+                graph.added.push(node.clone());
+            }
+            graph.add_node(node);
+        }
+    });
+    match &graph.connect_from {
+        Some(node) => ui.label(format!(
+            "Click the destination node for a new rate from {}",
+            node.label()
+        )),
+        None => ui.label("Drag nodes to move them, click two nodes to add a rate"),
+    };
+
+    let (response, painter) = ui.allocate_painter(
+        egui::vec2(ui.available_width(), 320.0),
+        egui::Sense::hover(),
+    );
+    let rect = response.rect;
+    painter.rect_filled(rect, 4.0, ui.visuals().extreme_bg_color);
+    let to_screen = |position: egui::Vec2| rect.min + position * rect.size();
+
+    // Edges
+    let mut pairs: Vec<(MarketRateNode, MarketRateNode)> = vec![];
+    for (i, rate) in rates.iter().enumerate() {
+        let from = MarketRateNode::of(&rate.from);
+        let to = MarketRateNode::of(&rate.to);
+        let offset = pairs
+            .iter()
+            .filter(|(a, b)| (*a == from && *b == to) || (*a == to && *b == from))
+            .count() as f32;
+        pairs.push((from.clone(), to.clone()));
+
+        let start = to_screen(graph.position(&from));
+        let end = to_screen(graph.position(&to));
+        let color = rate_color(&rate.action_ui);
+        let width = if graph.selected == Some(i) { 3.0 } else { 1.5 };
+        let stroke = egui::Stroke::new(width, color);
+        // Nodes dragged onto each other have no direction between them, so
+        // their edges are drawn as loops too.
+        let label_position = if from == to || (end - start).length_sq() < f32::EPSILON {
+            let center = start - egui::vec2(0.0, NODE_RADIUS + 14.0 + offset * EDGE_SPACING);
+            painter.circle_stroke(center, 14.0 + offset * 4.0, stroke);
+            center - egui::vec2(0.0, 24.0 + offset * 4.0)
+        } else {
+            let direction = (end - start).normalized();
+            let normal = egui::vec2(-direction.y, direction.x);
+            let bend = normal * (offset * EDGE_SPACING);
+            let control = start + (end - start) * 0.5 + bend * 2.0;
+            let mut previous = start;
+            for step in 1..=16 {
+                let t = step as f32 / 16.0;
+                let point = bezier(start, control, end, t);
+                painter.line_segment([previous, point], stroke);
+                previous = point;
+            }
+            let tip = end - (end - control).normalized() * NODE_RADIUS;
+            let tail = (end - control).normalized() * 12.0;
+            painter.arrow(tip - tail, tail, stroke);
+            bezier(start, control, end, 0.5)
+        };
+        let label_rect = painter.text(
+            label_position,
+            egui::Align2::CENTER_CENTER,
+            rate_label(rate),
+            egui::FontId::proportional(11.0),
+            color,
+        );
+        let label_response = ui.interact(
+            label_rect,
+            ui.make_persistent_id(("market_rate_edge", i)),
+            egui::Sense::click(),
+        );
+        if label_response.clicked() {
+            graph.selected = Some(i);
+        }
+    }
+
+    // Nodes
+    let mut connect_to: Option<MarketRateNode> = None;
+    for (node, position) in graph.nodes.iter_mut() {
+        let center = to_screen(*position);
+        let node_rect = egui::Rect::from_center_size(center, egui::Vec2::splat(NODE_RADIUS * 2.0));
+        let node_response = ui.interact(
+            node_rect,
+            ui.make_persistent_id(("market_rate_node", &*node)),
+            egui::Sense::click_and_drag(),
+        );
+        if node_response.dragged() {
+            *position = (*position + node_response.drag_delta() / rect.size())
+                .clamp(egui::Vec2::ZERO, egui::Vec2::splat(1.0));
+        }
+        if node_response.clicked() {
+            connect_to = Some(node.clone());
+        }
+        let connecting = graph.connect_from.as_ref() == Some(node);
+        let fill = if connecting {
+            egui::Color32::DARK_GREEN
+        } else {
+            ui.visuals().widgets.inactive.bg_fill
+        };
+        painter.circle(
+            center,
+            NODE_RADIUS,
+            fill,
+            egui::Stroke::new(1.0, egui::Color32::WHITE),
+        );
+        painter.text(
+            center,
+            egui::Align2::CENTER_CENTER,
+            node.label(),
+            egui::FontId::proportional(11.0),
+            egui::Color32::WHITE,
+        );
+        if let MarketRateNode::Account(account) = node {
+            node_response.on_hover_text(account.as_str());
+        }
+    }
+    if let Some(node) = connect_to {
+        match graph.connect_from.take() {
+            Some(from) if from == node => {}
+            Some(from) => {
+                rates.push(MarketRateInputData {
+                    from: from.rate_account(),
+                    to: node.rate_account(),
+                    ..Default::default()
+                });
+                graph.selected = Some(rates.len() - 1);
+            }
+            None => graph.connect_from = Some(node),
+        }
+    }
+
+    // Selected rate
+    if let Some(i) = graph.selected {
+        ui.separator();
+        ui.label(format!("Rate {}", i));
+        ui.label("Class ID");
+        class_id_ui(ui, registry, &mut rates[i].class_id);
+        ui.label("Asset ID");
+        asset_id_ui(ui, registry, *rates[i].class_id, &mut rates[i].asset_id);
+        market_rate_action_ui(ui, &mut rates[i], registry);
+        market_rate_account_ui(ui, &mut rates[i]);
+        for error in rates[i].validate() {
+            ui.colored_label(egui::Color32::RED, error);
+        }
+        if ui.button("Remove").clicked() {
+            rates.remove(i);
+            graph.selected = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account_rate(account: &str) -> MarketRateInputData {
+        MarketRateInputData {
+            to: MarketRateNode::Account(account.to_string()).rate_account(),
+            ..Default::default()
+        }
+    }
+
+    fn account_nodes(graph: &MarketRatesGraphInputData) -> Vec<MarketRateNode> {
+        graph
+            .nodes
+            .iter()
+            .filter(|(node, _)| matches!(node, MarketRateNode::Account(_)))
+            .map(|(node, _)| node.clone())
+            .collect()
+    }
+
+    #[test]
+    fn account_nodes_follow_the_rates() {
+        let mut graph = MarketRatesGraphInputData::default();
+        for typed in ["5", "5G", "5Gr"] {
+            graph.sync_nodes(&[account_rate(typed)]);
+        }
+        assert_eq!(
+            account_nodes(&graph),
+            vec![MarketRateNode::Account("5Gr".to_string())]
+        );
+        graph.sync_nodes(&[]);
+        assert!(account_nodes(&graph).is_empty());
+        assert_eq!(graph.nodes.len(), 2);
+    }
+
+    #[test]
+    fn added_account_nodes_are_kept() {
+        let mut graph = MarketRatesGraphInputData::default();
+        let node = MarketRateNode::Account("5Alice".to_string());
+        graph.added.push(node.clone());
+        graph.add_node(node.clone());
+        graph.connect_from = Some(MarketRateNode::Account("5Gone".to_string()));
+        graph.sync_nodes(&[]);
+        assert_eq!(account_nodes(&graph), vec![node]);
+        assert_eq!(graph.connect_from, None);
+    }
+}
@@ -1,68 +1,37 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use crossbeam::channel;
+use serde::{Deserialize, Serialize};
 use sugarfunge_api_types::{
-    market::{
-        AssetRate, CreateMarketRateInput, CreateMarketRateOutput, RateAccount, RateAction, Rates,
-        AMM,
-    },
+    market::{AssetRate, CreateMarketRateInput, RateAction, Rates},
     primitives::{Amount, AssetId, ClassId, MarketId, Seed},
 };
 
-use crate::{prelude::*, util::request_handler};
+use crate::{
+    api::{ApiEvents, CreateMarketRate},
+    debug_ui::{
+        export::{form_input, RequestForm},
+        links::{link_chip, DebugUiLinks, LinkValue},
+        registry::{asset_id_ui, class_id_ui, market_id_ui, market_rate_id_ui, IdRegistry},
+    },
+    shortcuts::submit_button,
+};
 
-use self::{rate_account::*, rate_action::*};
+use self::{
+    graph::market_rates_graph_ui,
+    rate_account::*,
+    rate_action::*,
+    template::{market_rates_template_ui, MarketRatesTemplate},
+};
 
 use super::MarketUi;
 
+pub mod graph;
 pub mod rate_account;
 pub mod rate_action;
+pub mod template;
 
-#[derive(Debug)]
-pub struct CreateMarketRateRequest {
-    pub input: CreateMarketRateInput,
-}
-
-impl Request<CreateMarketRateInput> for CreateMarketRateRequest {
-    fn endpoint(&self) -> &str {
-        "market/create_market_rate"
-    }
-
-    fn input(&self) -> Option<CreateMarketRateInput> {
-        Some(CreateMarketRateInput {
-            seed: self.input.seed.clone(),
-            market_id: self.input.market_id.clone(),
-            market_rate_id: self.input.market_rate_id.clone(),
-            rates: Rates {
-                rates: self.input.rates.rates.clone(),
-                metadata: self.input.rates.metadata.clone(),
-            },
-        })
-    }
-}
-
-#[derive(Resource)]
-pub struct CreateMarketRateChannel {
-    pub input_tx: InputSender<CreateMarketRateRequest>,
-    pub input_rx: InputReceiver<CreateMarketRateRequest>,
-    pub output_tx: OutputSender<CreateMarketRateOutput>,
-    pub output_rx: OutputReceiver<CreateMarketRateOutput>,
-}
-
-impl Default for CreateMarketRateChannel {
-    fn default() -> Self {
-        let (input_tx, input_rx) = channel::unbounded::<CreateMarketRateRequest>();
-        let (output_tx, output_rx) = channel::unbounded::<Option<CreateMarketRateOutput>>();
-        Self {
-            input_tx: InputSender(input_tx),
-            input_rx: InputReceiver(input_rx),
-            output_tx: OutputSender(output_tx),
-            output_rx: OutputReceiver(output_rx),
-        }
-    }
-}
-
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MarketRateInputData {
     pub class_id: ClassId,
     pub asset_id: AssetId,
@@ -85,13 +54,85 @@ impl Default for MarketRateInputData {
     }
 }
 
-#[derive(Resource, Debug, Clone)]
+impl MarketRateInputData {
+    pub fn asset_rate(&self) -> AssetRate {
+        AssetRate {
+            class_id: self.class_id,
+            asset_id: self.asset_id,
+            action: match self.action_ui {
+                MarketRateAction::Transfer => {
+                    RateAction::Transfer(Amount::from(self.action_data.transfer as i128))
+                }
+                MarketRateAction::MarketTransfer => RateAction::MarketTransfer(
+                    self.action_data.market_transfer.amm.clone(),
+                    self.action_data.market_transfer.class_id,
+                    self.action_data.market_transfer.asset_id,
+                ),
+                MarketRateAction::Mint => {
+                    RateAction::Mint(Amount::from(self.action_data.mint as i128))
+                }
+                MarketRateAction::Burn => {
+                    RateAction::Burn(Amount::from(self.action_data.burn as i128))
+                }
+                MarketRateAction::Has => RateAction::Has(
+                    self.action_data.has.amount_op.clone(),
+                    Amount::from(self.action_data.has.amount as i128),
+                ),
+            },
+            from: self.from.rate_account(),
+            to: self.to.rate_account(),
+        }
+    }
+
+    /// Checks the combinations the market pallet would reject, so they can be
+    /// fixed before the rate is sent.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = vec![];
+        for (label, account) in [("From", &self.from), ("To", &self.to)] {
+            if account.rate_account == MarketRateAccount::Account && account.account.is_empty() {
+                errors.push(format!("{} account is empty", label));
+            }
+        }
+        match self.action_ui {
+            MarketRateAction::Transfer => {
+                if self.from.rate_account == self.to.rate_account
+                    && self.from.account.as_str() == self.to.account.as_str()
+                {
+                    errors.push("Transfer needs different source and destination".to_string());
+                }
+            }
+            MarketRateAction::MarketTransfer => {
+                if self.from.rate_account != MarketRateAccount::Market
+                    && self.to.rate_account != MarketRateAccount::Market
+                {
+                    errors
+                        .push("Market Transfer must move assets to or from the market".to_string());
+                }
+            }
+            MarketRateAction::Mint | MarketRateAction::Burn => {}
+            MarketRateAction::Has => {
+                if self.from.rate_account != MarketRateAccount::Buyer {
+                    errors.push("Has can only check the buyer as source".to_string());
+                }
+                if self.action_data.has.amount < 0 {
+                    errors.push("Has amount can't be negative".to_string());
+                }
+            }
+        }
+        errors
+    }
+}
+
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CreateMarketRateInputData {
+    #[serde(skip)]
     pub seed: Seed,
     pub market_id: MarketId,
     pub market_rate_id: MarketId,
     pub rates: Vec<MarketRateInputData>,
     pub rates_metadata: String,
+    #[serde(skip)]
     pub loading: bool,
 }
 
@@ -108,53 +149,110 @@ impl Default for CreateMarketRateInputData {
     }
 }
 
-pub fn create_market_rate_ui(ui: &mut egui::Ui, market: &mut ResMut<MarketUi>) {
-    ui.label("Create Market Rate");
-    ui.separator();
-    ui.label("Seed");
-    ui.text_edit_singleline(&mut *market.data.input.create_market_rate.seed);
-    ui.label("Market ID");
-    ui.add(egui::DragValue::new(&mut *market.data.input.create_market_rate.market_id).speed(1.0));
-    ui.label("Market Rate ID");
-    ui.add(
-        egui::DragValue::new(&mut *market.data.input.create_market_rate.market_rate_id).speed(1.0),
-    );
+impl RequestForm for CreateMarketRateInputData {
+    type Endpoint = CreateMarketRate;
+
+    fn input(&self) -> Result<CreateMarketRateInput, String> {
+        if let Some(error) = self.rates.iter().flat_map(|rate| rate.validate()).next() {
+            return Err(error);
+        }
+        let template = MarketRatesTemplate::from_input(self)?;
+        Ok(CreateMarketRateInput {
+            seed: self.seed.clone(),
+            market_id: self.market_id,
+            market_rate_id: self.market_rate_id,
+            rates: Rates {
+                rates: template.rates,
+                metadata: template.metadata,
+            },
+        })
+    }
+
+    /// Fails like the other forms when a rate amount doesn't fit the form.
+    fn set_input(&mut self, input: CreateMarketRateInput) -> Result<(), String> {
+        MarketRatesTemplate {
+            rates: input.rates.rates,
+            metadata: input.rates.metadata,
+        }
+        .apply(self)?;
+        self.seed = input.seed;
+        self.market_id = input.market_id;
+        self.market_rate_id = input.market_rate_id;
+        Ok(())
+    }
+
+    fn seed(&mut self) -> Option<&mut String> {
+        Some(&mut *self.seed)
+    }
+}
+
+pub fn market_rates_ui(
+    ui: &mut egui::Ui,
+    rates: &mut Vec<MarketRateInputData>,
+    registry: &IdRegistry,
+) {
     ui.label("Rates");
     if ui.button("Add Rate").clicked() {
-        market
-            .data
-            .input
-            .create_market_rate
-            .rates
-            .push(MarketRateInputData::default());
+        rates.push(MarketRateInputData::default());
     }
-    let rates = market.data.input.create_market_rate.rates.clone();
+    let rates_clone = rates.clone();
     let mut rate_remove_index: Option<usize> = None;
-    for (i, _) in rates.iter().enumerate() {
+    for (i, _) in rates_clone.iter().enumerate() {
         ui.label(format!("Rate {}", i));
         ui.label("Class ID");
-        ui.add(
-            egui::DragValue::new::<u64>(
-                &mut market.data.input.create_market_rate.rates[i].class_id,
-            )
-            .speed(0.1),
-        );
+        class_id_ui(ui, registry, &mut rates[i].class_id);
         ui.label("Asset ID");
-        ui.add(
-            egui::DragValue::new::<u64>(
-                &mut market.data.input.create_market_rate.rates[i].asset_id,
-            )
-            .speed(0.1),
-        );
-        market_rate_action_ui(ui, &mut market.data.input.create_market_rate.rates[i]);
-        market_rate_account_ui(ui, &mut market.data.input.create_market_rate.rates[i]);
+        asset_id_ui(ui, registry, *rates[i].class_id, &mut rates[i].asset_id);
+        market_rate_action_ui(ui, &mut rates[i], registry);
+        market_rate_account_ui(ui, &mut rates[i]);
+        for error in rates[i].validate() {
+            ui.colored_label(egui::Color32::RED, error);
+        }
         if ui.button("Remove").clicked() {
             rate_remove_index = Some(i);
         }
         ui.separator();
     }
     if let Some(index) = rate_remove_index {
-        market.data.input.create_market_rate.rates.remove(index);
+        rates.remove(index);
+    }
+}
+
+pub fn create_market_rate_ui(
+    ui: &mut egui::Ui,
+    market: &mut ResMut<MarketUi>,
+    registry: &IdRegistry,
+    links: &mut DebugUiLinks,
+) {
+    ui.label("Create Market Rate");
+    ui.separator();
+    ui.label("Seed");
+    ui.text_edit_singleline(&mut *market.data.input.create_market_rate.seed);
+    ui.label("Market ID");
+    market_id_ui(
+        ui,
+        registry,
+        &mut market.data.input.create_market_rate.market_id,
+    );
+    ui.label("Market Rate ID");
+    market_rate_id_ui(
+        ui,
+        registry,
+        u64::from(market.data.input.create_market_rate.market_id),
+        &mut market.data.input.create_market_rate.market_rate_id,
+    );
+    let input = &mut market.data.input;
+    market_rates_template_ui(ui, &mut input.rates_template);
+    ui.checkbox(&mut input.rates_graph.enabled, "Graph View");
+    if input.rates_graph.enabled {
+        market_rates_graph_ui(
+            ui,
+            &mut input.rates_graph,
+            &mut input.create_market_rate.rates,
+            registry,
+        );
+    } else {
+        market_rates_ui(ui, &mut input.create_market_rate.rates, registry);
     }
     ui.label("Rates Metadata");
     ui.text_edit_multiline(&mut market.data.input.create_market_rate.rates_metadata);
@@ -163,109 +261,51 @@ pub fn create_market_rate_ui(ui: &mut egui::Ui, market: &mut ResMut<MarketUi>) {
         ui.separator();
         ui.add(egui::Spinner::default());
     } else {
-        if ui.button("Create Market Rate").clicked() {
-            market
-                .channels
-                .create_market_rate
-                .input_tx
-                .0
-                .send(CreateMarketRateRequest {
-                    input: CreateMarketRateInput {
-                        seed: market.data.input.create_market_rate.seed.clone(),
-                        market_id: market.data.input.create_market_rate.market_id,
-                        market_rate_id: market.data.input.create_market_rate.market_rate_id,
-                        rates: Rates {
-                            rates: market
-                                .data
-                                .input
-                                .create_market_rate
-                                .rates
-                                .iter()
-                                .map(|rate| AssetRate {
-                                    class_id: rate.class_id,
-                                    asset_id: rate.asset_id,
-                                    action: match rate.action_ui {
-                                        MarketRateAction::Transfer => RateAction::Transfer(
-                                            Amount::from(rate.action_data.transfer as i128),
-                                        ),
-                                        MarketRateAction::MarketTransfer => {
-                                            RateAction::MarketTransfer(
-                                                AMM::Constant,
-                                                rate.action_data.market_transfer.class_id,
-                                                rate.action_data.market_transfer.asset_id,
-                                            )
-                                        }
-                                        MarketRateAction::Mint => RateAction::Mint(Amount::from(
-                                            rate.action_data.mint as i128,
-                                        )),
-                                        MarketRateAction::Burn => RateAction::Burn(Amount::from(
-                                            rate.action_data.burn as i128,
-                                        )),
-                                        MarketRateAction::Has => RateAction::Has(
-                                            rate.action_data.has.amount_op.clone(),
-                                            Amount::from(rate.action_data.has.amount as i128),
-                                        ),
-                                    },
-                                    from: match rate.from.rate_account {
-                                        MarketRateAccount::Buyer => RateAccount::Buyer,
-                                        MarketRateAccount::Market => RateAccount::Market,
-                                        MarketRateAccount::Account => {
-                                            RateAccount::Account(rate.from.account.clone())
-                                        }
-                                    },
-                                    to: match rate.to.rate_account {
-                                        MarketRateAccount::Buyer => RateAccount::Buyer,
-                                        MarketRateAccount::Market => RateAccount::Market,
-                                        MarketRateAccount::Account => {
-                                            RateAccount::Account(rate.to.account.clone())
-                                        }
-                                    },
-                                })
-                                .collect(),
-                            metadata: serde_json::from_str(
-                                &market.data.input.create_market_rate.rates_metadata,
-                            )
-                            .unwrap(),
-                        },
-                    },
-                })
-                .unwrap();
-            market.data.input.create_market_rate.loading = true;
+        let valid = market
+            .data
+            .input
+            .create_market_rate
+            .rates
+            .iter()
+            .all(|rate| rate.validate().is_empty());
+        if ui
+            .add_enabled_ui(valid, |ui| submit_button(ui, "Create Market Rate"))
+            .inner
+        {
+            if let Some(input) = form_input(&market.data.input.create_market_rate) {
+                market.channels.create_market_rate.send(input);
+                market.data.input.create_market_rate.loading = true;
+            }
         }
     }
     if let Some(output) = &market.data.output.create_market_rate {
         ui.separator();
         ui.label("Market ID");
-        ui.text_edit_singleline(&mut u64::from(output.market_id).to_string());
+        link_chip(ui, links, LinkValue::Market(output.market_id));
         ui.label("Market Rate ID");
         ui.text_edit_singleline(&mut u64::from(output.market_rate_id).to_string());
         ui.label("Who");
-        ui.text_edit_singleline(&mut output.who.as_str());
+        link_chip(ui, links, LinkValue::Account(output.who.clone()));
     }
 }
 
 pub fn handle_create_market_rate_response(
     mut market: ResMut<MarketUi>,
-    tokio_runtime: Res<TokioRuntime>,
+    mut api: ApiEvents<CreateMarketRate>,
 ) {
-    if let Ok(created_result) = market.channels.create_market_rate.output_rx.0.try_recv() {
-        if let Some(created) = created_result {
+    if let Some(result) = market.channels.create_market_rate.poll(&mut api) {
+        if let Ok(created) = result {
             market.data.output.create_market_rate = Some(created);
         }
         market.data.input.create_market_rate.loading = false;
     }
-
-    request_handler::<CreateMarketRateRequest, CreateMarketRateInput, CreateMarketRateOutput>(
-        tokio_runtime.runtime.clone(),
-        market.channels.create_market_rate.input_rx.clone(),
-        market.channels.create_market_rate.output_tx.clone(),
-    );
 }
 
 pub struct CreateMarketRatePlugin;
 
 impl Plugin for CreateMarketRatePlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(handle_create_market_rate_response);
+        app.add_system(handle_create_market_rate_response)
+            .add_system(template::handle_market_rate_templates);
     }
 }
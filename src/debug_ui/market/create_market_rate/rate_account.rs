@@ -1,10 +1,11 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use sugarfunge_api_types::primitives::Account;
+use serde::{Deserialize, Serialize};
+use sugarfunge_api_types::{market::RateAccount, primitives::Account};
 
 use super::MarketRateInputData;
 
-#[derive(Resource, Debug, Default, Clone, PartialEq)]
+#[derive(Resource, Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub enum MarketRateAccount {
     #[default]
     Market,
@@ -12,7 +13,8 @@ pub enum MarketRateAccount {
     Buyer,
 }
 
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MarketRateAccountInputData {
     pub account: Account,
     pub account_enabled: bool,
@@ -29,6 +31,16 @@ impl Default for MarketRateAccountInputData {
     }
 }
 
+impl MarketRateAccountInputData {
+    pub fn rate_account(&self) -> RateAccount {
+        match self.rate_account {
+            MarketRateAccount::Buyer => RateAccount::Buyer,
+            MarketRateAccount::Market => RateAccount::Market,
+            MarketRateAccount::Account => RateAccount::Account(self.account.clone()),
+        }
+    }
+}
+
 pub fn market_rate_account_ui(ui: &mut egui::Ui, rate: &mut MarketRateInputData) {
     ui.horizontal(|ui| {
         ui.label("From:");
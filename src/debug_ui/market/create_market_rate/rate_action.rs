@@ -1,16 +1,19 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
 use sugarfunge_api_types::{
-    market::AmountOp,
+    market::{AmountOp, AMM},
     primitives::{AssetId, ClassId},
 };
 
+use crate::debug_ui::registry::{asset_id_ui, class_id_ui, IdRegistry};
+
 use super::MarketRateInputData;
 
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
 pub struct MarketHasAction {
     pub amount_op: AmountOp,
-    pub amount: u64,
+    pub amount: i64,
 }
 
 impl Default for MarketHasAction {
@@ -22,8 +25,9 @@ impl Default for MarketHasAction {
     }
 }
 
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
 pub struct MarketTransferAction {
+    pub amm: AMM,
     pub class_id: ClassId,
     pub asset_id: AssetId,
 }
@@ -31,22 +35,24 @@ pub struct MarketTransferAction {
 impl Default for MarketTransferAction {
     fn default() -> Self {
         Self {
+            amm: AMM::Constant,
             class_id: ClassId::from(0),
             asset_id: AssetId::from(0),
         }
     }
 }
 
-#[derive(Resource, Debug, Default, Clone)]
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MarketRateActionInputData {
-    pub transfer: u64,
+    pub transfer: i64,
     pub market_transfer: MarketTransferAction,
-    pub mint: u64,
-    pub burn: u64,
+    pub mint: i64,
+    pub burn: i64,
     pub has: MarketHasAction,
 }
 
-#[derive(Resource, Debug, Clone, PartialEq)]
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum MarketRateAction {
     Transfer,
     MarketTransfer,
@@ -55,7 +61,14 @@ pub enum MarketRateAction {
     Has,
 }
 
-pub fn market_rate_action_ui(ui: &mut egui::Ui, rate: &mut MarketRateInputData) {
+/// Every `AMM` curve a market transfer can be priced with, and its label.
+pub const MARKET_RATE_AMMS: [(AMM, &str); 1] = [(AMM::Constant, "Constant")];
+
+pub fn market_rate_action_ui(
+    ui: &mut egui::Ui,
+    rate: &mut MarketRateInputData,
+    registry: &IdRegistry,
+) {
     ui.label("Action");
     ui.horizontal(|ui| {
         ui.selectable_value(&mut rate.action_ui, MarketRateAction::Transfer, "Transfer");
@@ -75,13 +88,24 @@ pub fn market_rate_action_ui(ui: &mut egui::Ui, rate: &mut MarketRateInputData)
             ui.add(egui::DragValue::new(&mut rate.action_data.transfer).speed(1.0));
         }
         MarketRateAction::MarketTransfer => {
+            ui.label("AMM");
+            ui.horizontal(|ui| {
+                for (amm, label) in MARKET_RATE_AMMS.iter() {
+                    ui.selectable_value(
+                        &mut rate.action_data.market_transfer.amm,
+                        amm.clone(),
+                        *label,
+                    );
+                }
+            });
             ui.label("Market Transfer Class ID");
-            ui.add(
-                egui::DragValue::new(&mut *rate.action_data.market_transfer.class_id).speed(1.0),
-            );
+            class_id_ui(ui, registry, &mut rate.action_data.market_transfer.class_id);
             ui.label("Market Transfer Asset ID");
-            ui.add(
-                egui::DragValue::new(&mut *rate.action_data.market_transfer.asset_id).speed(1.0),
+            asset_id_ui(
+                ui,
+                registry,
+                u64::from(rate.action_data.market_transfer.class_id),
+                &mut rate.action_data.market_transfer.asset_id,
             );
         }
         MarketRateAction::Mint => {
@@ -0,0 +1,309 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+use sugarfunge_api_types::market::{AssetRate, RateAccount, RateAction};
+
+use crate::{
+    asset_files::{list_asset_files, poll_asset_file, read_asset_file, AssetFileReceiver},
+    debug_ui::market::MarketUi,
+};
+
+use super::{
+    rate_account::{MarketRateAccount, MarketRateAccountInputData},
+    rate_action::MarketRateAction,
+    CreateMarketRateInputData, MarketRateInputData,
+};
+
+/// Relative to the asset folder.
+pub const MARKET_RATE_TEMPLATES_DIR: &str = "market_rates";
+
+/// A market rate set as stored in the asset folder, either as `.ron` or
+/// `.json`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MarketRatesTemplate {
+    pub rates: Vec<AssetRate>,
+    pub metadata: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarketRatesTemplateRequest {
+    Refresh,
+    Load,
+    /// The web build serves the asset folder read-only, so it can't save.
+    #[cfg(not(target_arch = "wasm32"))]
+    Save,
+}
+
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarketRatesTemplateInputData {
+    /// Relative to the asset folder.
+    pub path: String,
+    #[serde(skip)]
+    pub templates: Vec<String>,
+    #[serde(skip)]
+    pub status: Option<String>,
+    /// Handled by `handle_market_rate_templates`, which has the asset server.
+    #[serde(skip)]
+    pub request: Option<MarketRatesTemplateRequest>,
+    #[serde(skip)]
+    loading: Option<(String, AssetFileReceiver)>,
+}
+
+impl Default for MarketRatesTemplateInputData {
+    fn default() -> Self {
+        Self {
+            path: format!("{}/simple_swap.rates.json", MARKET_RATE_TEMPLATES_DIR),
+            templates: vec![],
+            status: None,
+            request: None,
+            loading: None,
+        }
+    }
+}
+
+impl From<&RateAccount> for MarketRateAccountInputData {
+    fn from(account: &RateAccount) -> Self {
+        let mut input = MarketRateAccountInputData::default();
+        match account {
+            RateAccount::Market => input.rate_account = MarketRateAccount::Market,
+            RateAccount::Buyer => input.rate_account = MarketRateAccount::Buyer,
+            RateAccount::Account(account) => {
+                input.rate_account = MarketRateAccount::Account;
+                input.account = account.clone();
+                input.account_enabled = true;
+            }
+        }
+        input
+    }
+}
+
+/// A rate amount as the form's `i64`, failing on amounts it can't hold.
+fn form_rate_amount(amount: impl Into<i128>) -> Result<i64, String> {
+    let amount = amount.into();
+    i64::try_from(amount).map_err(|_| format!("Rate amount {} is too large for the form", amount))
+}
+
+impl TryFrom<&AssetRate> for MarketRateInputData {
+    type Error = String;
+
+    fn try_from(rate: &AssetRate) -> Result<Self, String> {
+        let mut input = MarketRateInputData {
+            class_id: rate.class_id,
+            asset_id: rate.asset_id,
+            from: MarketRateAccountInputData::from(&rate.from),
+            to: MarketRateAccountInputData::from(&rate.to),
+            ..Default::default()
+        };
+        match &rate.action {
+            RateAction::Transfer(amount) => {
+                input.action_ui = MarketRateAction::Transfer;
+                input.action_data.transfer = form_rate_amount(*amount)?;
+            }
+            RateAction::MarketTransfer(amm, class_id, asset_id) => {
+                input.action_ui = MarketRateAction::MarketTransfer;
+                input.action_data.market_transfer.amm = amm.clone();
+                input.action_data.market_transfer.class_id = *class_id;
+                input.action_data.market_transfer.asset_id = *asset_id;
+            }
+            RateAction::Mint(amount) => {
+                input.action_ui = MarketRateAction::Mint;
+                input.action_data.mint = form_rate_amount(*amount)?;
+            }
+            RateAction::Burn(amount) => {
+                input.action_ui = MarketRateAction::Burn;
+                input.action_data.burn = form_rate_amount(*amount)?;
+            }
+            RateAction::Has(amount_op, amount) => {
+                input.action_ui = MarketRateAction::Has;
+                input.action_data.has.amount_op = amount_op.clone();
+                input.action_data.has.amount = form_rate_amount(*amount)?;
+            }
+        }
+        Ok(input)
+    }
+}
+
+impl MarketRatesTemplate {
+    pub fn from_input(input: &CreateMarketRateInputData) -> Result<Self, String> {
+        let metadata = if input.rates_metadata.trim().is_empty() {
+            serde_json::Value::Null
+        } else {
+            serde_json::from_str(&input.rates_metadata)
+                .map_err(|err| format!("Invalid rates metadata: {}", err))?
+        };
+        Ok(Self {
+            rates: input.rates.iter().map(|rate| rate.asset_rate()).collect(),
+            metadata,
+        })
+    }
+
+    /// Fills the form's rates in, leaving it untouched when a rate doesn't
+    /// fit the form.
+    pub fn apply(&self, input: &mut CreateMarketRateInputData) -> Result<(), String> {
+        let rates = self
+            .rates
+            .iter()
+            .enumerate()
+            .map(|(index, rate)| {
+                MarketRateInputData::try_from(rate)
+                    .map_err(|err| format!("Rate {}: {}", index, err))
+            })
+            .collect::<Result<_, _>>()?;
+        input.rates = rates;
+        input.rates_metadata = serde_json::to_string_pretty(&self.metadata).unwrap_or_default();
+        Ok(())
+    }
+
+    /// Parses the `contents` of the template at `path`, as JSON or RON by
+    /// extension.
+    pub fn parse(path: &str, contents: &str) -> Result<Self, String> {
+        if is_json(path) {
+            serde_json::from_str(contents).map_err(|err| err.to_string())
+        } else {
+            ron::from_str(contents).map_err(|err| err.to_string())
+        }
+    }
+
+    /// Saves to `path`, relative to the asset folder.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let contents = if is_json(path) {
+            serde_json::to_string_pretty(self).map_err(|err| err.to_string())?
+        } else {
+            ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+                .map_err(|err| err.to_string())?
+        };
+        crate::asset_files::write_asset_file(path, &contents)
+    }
+}
+
+fn is_json(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .map_or(false, |extension| extension == "json")
+}
+
+fn list_templates(asset_server: &AssetServer) -> Vec<String> {
+    let mut templates = list_asset_files(asset_server, MARKET_RATE_TEMPLATES_DIR, ".ron");
+    templates.extend(list_asset_files(
+        asset_server,
+        MARKET_RATE_TEMPLATES_DIR,
+        ".json",
+    ));
+    templates.sort();
+    templates
+}
+
+pub fn market_rates_template_ui(ui: &mut egui::Ui, template: &mut MarketRatesTemplateInputData) {
+    ui.collapsing("Templates", |ui| {
+        if ui.button("Refresh Templates").clicked() {
+            template.request = Some(MarketRatesTemplateRequest::Refresh);
+        }
+        for path in template.templates.clone() {
+            if ui.selectable_label(template.path == path, &path).clicked() {
+                template.path = path;
+            }
+        }
+        ui.label("Path (.ron or .json, in the asset folder)");
+        ui.text_edit_singleline(&mut template.path);
+        ui.horizontal(|ui| {
+            if template.loading.is_some() {
+                ui.add(egui::Spinner::default());
+            } else if ui.button("Load").clicked() {
+                template.request = Some(MarketRatesTemplateRequest::Load);
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            if ui.button("Save").clicked() {
+                template.request = Some(MarketRatesTemplateRequest::Save);
+            }
+        });
+        if let Some(status) = &template.status {
+            ui.label(status);
+        }
+    });
+}
+
+/// Lists, loads and saves templates for the Templates section, which can't
+/// reach the asset server from the debug UI.
+pub fn handle_market_rate_templates(asset_server: Res<AssetServer>, mut market: ResMut<MarketUi>) {
+    let input = &mut market.data.input;
+    let template = &mut input.rates_template;
+    match template.request.take() {
+        Some(MarketRatesTemplateRequest::Refresh) => {
+            template.templates = list_templates(&asset_server);
+        }
+        Some(MarketRatesTemplateRequest::Load) => {
+            let path = template.path.clone();
+            template.loading = Some((path.clone(), read_asset_file(&asset_server, &path)));
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        Some(MarketRatesTemplateRequest::Save) => {
+            template.status = Some(
+                match MarketRatesTemplate::from_input(&input.create_market_rate)
+                    .and_then(|saved| saved.save(&template.path))
+                {
+                    Ok(()) => format!("Saved {}", template.path),
+                    Err(err) => format!("Save failed: {}", err),
+                },
+            );
+        }
+        None => {}
+    }
+    let Some(result) = template
+        .loading
+        .as_ref()
+        .and_then(|(path, loading)| poll_asset_file(path, loading))
+    else {
+        return;
+    };
+    let Some((path, _)) = template.loading.take() else {
+        return;
+    };
+    template.status = Some(
+        match result
+            .and_then(|contents| MarketRatesTemplate::parse(&path, &contents))
+            .and_then(|loaded| loaded.apply(&mut input.create_market_rate))
+        {
+            Ok(()) => format!("Loaded {} rate(s)", input.create_market_rate.rates.len()),
+            Err(err) => format!("Load failed: {}", err),
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(amount: &str) -> MarketRatesTemplate {
+        let json = format!(
+            r#"{{"rates": [{{"class_id": 1, "asset_id": 2, "action": {{"Mint": {}}}, "from": "Market", "to": "Buyer"}}], "metadata": null}}"#,
+            amount
+        );
+        MarketRatesTemplate::parse("rates.json", &json).unwrap()
+    }
+
+    #[test]
+    fn apply_fills_rates_in() {
+        let mut input = CreateMarketRateInputData::default();
+        template("-5").apply(&mut input).unwrap();
+        assert_eq!(input.rates.len(), 1);
+        assert_eq!(input.rates[0].action_data.mint, -5);
+    }
+
+    #[test]
+    fn apply_rejects_amounts_the_form_cant_hold() {
+        let mut input = CreateMarketRateInputData::default();
+        let before = format!("{:?}", input.rates);
+        let err = template(&(i64::MAX as i128 + 1).to_string())
+            .apply(&mut input)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            "Rate 0: Rate amount 9223372036854775808 is too large for the form"
+        );
+        assert_eq!(format!("{:?}", input.rates), before);
+    }
+}
@@ -1,11 +1,25 @@
+use std::sync::Arc;
+
 use bevy::prelude::*;
 use bevy_egui::egui;
-use sugarfunge_api_types::market::*;
+use serde::{Deserialize, Serialize};
+use sugarfunge_api_types::{market::*, primitives::MarketId};
+
+use crate::{
+    api::{ApiCall, CreateMarket, CreateMarketRate, DepositAssets, ExchangeAssets},
+    debug_ui::{
+        export::RequestExport,
+        links::{DebugUiLinks, LinkTarget, LinkValue},
+        registry::IdRegistry,
+    },
+};
 
 pub mod create_market;
 pub mod create_market_rate;
 pub mod deposit_market_assets;
 pub mod exchange_market_assets;
+pub mod simulator;
+pub mod wizard;
 
 #[derive(Resource, Default)]
 pub struct MarketUi {
@@ -14,46 +28,122 @@ pub struct MarketUi {
     pub channels: MarketChannels,
 }
 
-#[derive(Resource, Debug, Default, Eq, PartialEq)]
+#[derive(Resource, Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
 pub enum MarketActions {
     #[default]
     CreateMarket,
     CreateMarketRate,
     DepositMarketAssets,
     ExchangeMarketAssets,
+    Simulator,
+    Wizard,
 }
 
-#[derive(Resource, Debug, Default, Clone)]
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MarketInputData {
     create_market: create_market::CreateMarketInputData,
     create_market_rate: create_market_rate::CreateMarketRateInputData,
+    rates_template: create_market_rate::template::MarketRatesTemplateInputData,
+    rates_graph: create_market_rate::graph::MarketRatesGraphInputData,
     deposit_market_assets: deposit_market_assets::DepositMarketAssetsInputData,
     exchange_market_assets: exchange_market_assets::ExchangeMarketAssetsInputData,
+    simulator: simulator::MarketSimulatorInputData,
+    wizard: wizard::MarketWizardInputData,
 }
 
 #[derive(Resource, Default, Debug)]
 pub struct MarketOutputData {
-    create_market: Option<CreateMarketOutput>,
-    create_market_rate: Option<CreateMarketRateOutput>,
-    deposit_market_assets: Option<DepositAssetsOutput>,
-    exchange_market_assets: Option<ExchangeAssetsOutput>,
+    create_market: Option<Arc<CreateMarketOutput>>,
+    create_market_rate: Option<Arc<CreateMarketRateOutput>>,
+    deposit_market_assets: Option<Arc<DepositAssetsOutput>>,
+    exchange_market_assets: Option<Arc<ExchangeAssetsOutput>>,
 }
 
 #[derive(Resource, Default)]
 pub struct MarketData {
-    input: MarketInputData,
+    pub input: MarketInputData,
     output: MarketOutputData,
 }
 
 #[derive(Resource, Default)]
 pub struct MarketChannels {
-    create_market: create_market::CreateMarketChannel,
-    create_market_rate: create_market_rate::CreateMarketRateChannel,
-    deposit_market_assets: deposit_market_assets::DepositMarketAssetsChannel,
-    exchange_market_assets: exchange_market_assets::ExchangeMarketAssetsChannel,
+    create_market: ApiCall<CreateMarket>,
+    create_market_rate: ApiCall<CreateMarketRate>,
+    deposit_market_assets: ApiCall<DepositAssets>,
+    exchange_market_assets: ApiCall<ExchangeAssets>,
+    simulator: simulator::MarketSimulatorChannel,
+}
+
+/// Switches to the exchange tab with `market_id` filled in, also carrying it to
+/// the deposit tab.
+pub fn show_market(market: &mut MarketUi, market_id: MarketId) {
+    market.actions = MarketActions::ExchangeMarketAssets;
+    market.data.input.exchange_market_assets.market_id = market_id;
+    market.data.input.deposit_market_assets.market_id = market_id;
+}
+
+impl MarketUi {
+    /// Restores the selected form's defaults.
+    pub fn reset_form(&mut self) {
+        let input = &mut self.data.input;
+        match self.actions {
+            MarketActions::CreateMarket => input.create_market = Default::default(),
+            MarketActions::CreateMarketRate => {
+                input.create_market_rate = Default::default();
+                input.rates_graph = Default::default();
+            }
+            MarketActions::DepositMarketAssets => input.deposit_market_assets = Default::default(),
+            MarketActions::ExchangeMarketAssets => {
+                input.exchange_market_assets = Default::default()
+            }
+            MarketActions::Simulator => input.simulator = Default::default(),
+            MarketActions::Wizard => input.wizard = Default::default(),
+        }
+    }
+
+    /// The selected form, when it sends a single request.
+    pub fn request_form(&mut self) -> Option<&mut dyn RequestExport> {
+        let input = &mut self.data.input;
+        match self.actions {
+            MarketActions::CreateMarket => Some(&mut input.create_market),
+            MarketActions::CreateMarketRate => Some(&mut input.create_market_rate),
+            MarketActions::DepositMarketAssets => Some(&mut input.deposit_market_assets),
+            MarketActions::ExchangeMarketAssets => Some(&mut input.exchange_market_assets),
+            MarketActions::Simulator => None,
+            MarketActions::Wizard => None,
+        }
+    }
+
+    /// Switches to the form `target` names and fills in `value`.
+    pub fn open_link(&mut self, target: LinkTarget, value: &LinkValue) {
+        let (Some(market_id), input) = (value.market_id(), &mut self.data.input) else {
+            return;
+        };
+        match target {
+            LinkTarget::CreateMarketRate => {
+                self.actions = MarketActions::CreateMarketRate;
+                input.create_market_rate.market_id = market_id;
+            }
+            LinkTarget::DepositMarketAssets => {
+                self.actions = MarketActions::DepositMarketAssets;
+                input.deposit_market_assets.market_id = market_id;
+            }
+            LinkTarget::ExchangeMarketAssets => {
+                self.actions = MarketActions::ExchangeMarketAssets;
+                input.exchange_market_assets.market_id = market_id;
+            }
+            _ => {}
+        }
+    }
 }
 
-pub fn market_ui(ui: &mut egui::Ui, market: &mut ResMut<MarketUi>) {
+pub fn market_ui(
+    ui: &mut egui::Ui,
+    market: &mut ResMut<MarketUi>,
+    registry: &IdRegistry,
+    links: &mut DebugUiLinks,
+) {
     ui.horizontal(|ui| {
         ui.selectable_value(&mut market.actions, MarketActions::CreateMarket, "Create");
         ui.selectable_value(
@@ -71,20 +161,28 @@ pub fn market_ui(ui: &mut egui::Ui, market: &mut ResMut<MarketUi>) {
             MarketActions::ExchangeMarketAssets,
             "Exchange",
         );
+        ui.selectable_value(&mut market.actions, MarketActions::Simulator, "Simulate");
+        ui.selectable_value(&mut market.actions, MarketActions::Wizard, "Wizard");
     });
     ui.separator();
     match &market.actions {
         MarketActions::CreateMarket => {
-            create_market::create_market_ui(ui, market);
+            create_market::create_market_ui(ui, market, registry, links);
         }
         MarketActions::CreateMarketRate => {
-            create_market_rate::create_market_rate_ui(ui, market);
+            create_market_rate::create_market_rate_ui(ui, market, registry, links);
         }
         MarketActions::DepositMarketAssets => {
-            deposit_market_assets::deposit_market_assets_ui(ui, market);
+            deposit_market_assets::deposit_market_assets_ui(ui, market, registry, links);
         }
         MarketActions::ExchangeMarketAssets => {
-            exchange_market_assets::exchange_market_assets_ui(ui, market);
+            exchange_market_assets::exchange_market_assets_ui(ui, market, registry, links);
+        }
+        MarketActions::Simulator => {
+            simulator::market_simulator_ui(ui, market, registry, links);
+        }
+        MarketActions::Wizard => {
+            wizard::market_wizard_ui(ui, market, registry, links);
         }
     }
 }
@@ -97,6 +195,7 @@ impl Plugin for MarketPlugin {
             .add_plugin(create_market::CreateMarketPlugin)
             .add_plugin(create_market_rate::CreateMarketRatePlugin)
             .add_plugin(deposit_market_assets::DepositMarketAssetsPlugin)
-            .add_plugin(exchange_market_assets::ExchangeMarketAssetsPlugin);
+            .add_plugin(exchange_market_assets::ExchangeMarketAssetsPlugin)
+            .add_plugin(simulator::MarketSimulatorPlugin);
     }
 }
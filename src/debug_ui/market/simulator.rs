@@ -0,0 +1,490 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+use sugarfunge_api_types::{
+    asset::{AssetBalancesInput, AssetBalancesOutput},
+    market::{AmountOp, AssetRate, RateAccount, RateAction, RateBalance, AMM},
+    primitives::{Account, Amount, AssetId, ClassId},
+};
+
+use crate::{
+    api::{ApiEvents, AssetBalances, AssetBalancesRequest, RequestId},
+    behavior::actions::amount_op_holds,
+    debug_ui::{
+        links::DebugUiLinks,
+        registry::{asset_id_ui, class_id_ui, IdRegistry},
+    },
+    shortcuts::submit_button,
+};
+
+use super::{
+    create_market_rate::rate_account::MarketRateAccount, deposit_market_assets::rate_balances_ui,
+    MarketUi,
+};
+
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+pub struct SimulatedBalance {
+    pub account: MarketRateAccount,
+    pub named_account: Account,
+    pub class_id: ClassId,
+    pub asset_id: AssetId,
+    pub amount: u64,
+}
+
+impl Default for SimulatedBalance {
+    fn default() -> Self {
+        Self {
+            account: MarketRateAccount::Buyer,
+            named_account: Account::from("".to_string()),
+            class_id: ClassId::from(0),
+            asset_id: AssetId::from(0),
+            amount: 0,
+        }
+    }
+}
+
+impl SimulatedBalance {
+    fn matches(&self, account: &RateAccount, class_id: ClassId, asset_id: AssetId) -> bool {
+        let same_account = match account {
+            RateAccount::Buyer => self.account == MarketRateAccount::Buyer,
+            RateAccount::Market => self.account == MarketRateAccount::Market,
+            RateAccount::Account(account) => {
+                self.account == MarketRateAccount::Account
+                    && self.named_account.as_str() == account.as_str()
+            }
+        };
+        same_account
+            && u64::from(self.class_id) == u64::from(class_id)
+            && u64::from(self.asset_id) == u64::from(asset_id)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MarketSimulationResult {
+    pub amount: u64,
+    pub balances: Vec<RateBalance>,
+    pub failed_has: Vec<usize>,
+    pub success: bool,
+}
+
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarketSimulatorInputData {
+    pub buyer: Account,
+    pub market: Account,
+    pub balances: Vec<SimulatedBalance>,
+    pub amount: u64,
+    #[serde(skip)]
+    pub result: Option<MarketSimulationResult>,
+    /// Fetched balances the simulator couldn't hold.
+    #[serde(skip)]
+    pub fetch_errors: Vec<String>,
+    #[serde(skip)]
+    pub loading: bool,
+}
+
+impl Default for MarketSimulatorInputData {
+    fn default() -> Self {
+        Self {
+            buyer: Account::from("".to_string()),
+            market: Account::from("".to_string()),
+            balances: vec![],
+            amount: 1,
+            result: None,
+            fetch_errors: vec![],
+            loading: false,
+        }
+    }
+}
+
+/// Buyer and market balance requests share one endpoint, so responses are told
+/// apart by request id.
+#[derive(Resource, Default)]
+pub struct MarketSimulatorChannel {
+    outbox: Vec<AssetBalancesRequest>,
+    pending: Vec<(RequestId, MarketRateAccount)>,
+}
+
+fn available_balance(
+    balances: &[SimulatedBalance],
+    account: &RateAccount,
+    class_id: ClassId,
+    asset_id: AssetId,
+) -> i128 {
+    balances
+        .iter()
+        .filter(|balance| balance.matches(account, class_id, asset_id))
+        .map(|balance| balance.amount as i128)
+        .sum()
+}
+
+/// `amount_op_holds` for the simulator's signed amounts. A negative `Has`
+/// amount is never met, since balances can't go below zero.
+fn has_holds(amount_op: &AmountOp, available: i128, amount: i128) -> bool {
+    match (u128::try_from(available), u128::try_from(amount)) {
+        (Ok(available), Ok(amount)) => amount_op_holds(amount_op, available, amount),
+        _ => false,
+    }
+}
+
+/// Evaluates the rates the same way `market/exchange_assets` reports them: each
+/// `RateBalance` holds what the source account would have left for that rate
+/// (negative when it can't cover it), minted amounts for `Mint` and the current
+/// holding for `Has`. All rates are evaluated against the starting balances.
+pub fn simulate_market_rates(
+    rates: &[AssetRate],
+    balances: &[SimulatedBalance],
+    amount: u64,
+) -> MarketSimulationResult {
+    let amount = amount as i128;
+    let mut failed_has = vec![];
+    let rate_balances: Vec<RateBalance> = rates
+        .iter()
+        .enumerate()
+        .map(|(i, rate)| {
+            let available = available_balance(balances, &rate.from, rate.class_id, rate.asset_id);
+            let balance = match &rate.action {
+                RateAction::Transfer(rate_amount) | RateAction::Burn(rate_amount) => {
+                    available - i128::from(*rate_amount) * amount
+                }
+                RateAction::Mint(rate_amount) => i128::from(*rate_amount) * amount,
+                RateAction::Has(amount_op, rate_amount) => {
+                    if !has_holds(amount_op, available, i128::from(*rate_amount)) {
+                        failed_has.push(i);
+                    }
+                    available
+                }
+                RateAction::MarketTransfer(AMM::Constant, class_id, asset_id) => {
+                    // Constant product quote: the market keeps `reserve * paired` fixed.
+                    let paired =
+                        available_balance(balances, &RateAccount::Market, *class_id, *asset_id);
+                    let quote = if paired + amount > 0 {
+                        available * amount / (paired + amount)
+                    } else {
+                        0
+                    };
+                    available - quote
+                }
+            };
+            RateBalance {
+                rate: rate.clone(),
+                balance: Amount::from(balance),
+            }
+        })
+        .collect();
+    let success = failed_has.is_empty()
+        && rate_balances
+            .iter()
+            .all(|rate_balance| i128::from(rate_balance.balance) >= 0);
+    MarketSimulationResult {
+        amount: amount as u64,
+        balances: rate_balances,
+        failed_has,
+        success,
+    }
+}
+
+fn simulated_balances_ui(
+    ui: &mut egui::Ui,
+    balances: &mut Vec<SimulatedBalance>,
+    registry: &IdRegistry,
+) {
+    ui.label("Balances");
+    if ui.button("Add Balance").clicked() {
+        balances.push(SimulatedBalance::default());
+    }
+    let balances_clone = balances.clone();
+    let mut balance_remove_index: Option<usize> = None;
+    for (i, _) in balances_clone.iter().enumerate() {
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut balances[i].account, MarketRateAccount::Buyer, "Buyer");
+            ui.radio_value(
+                &mut balances[i].account,
+                MarketRateAccount::Market,
+                "Market",
+            );
+            ui.radio_value(
+                &mut balances[i].account,
+                MarketRateAccount::Account,
+                "Account",
+            );
+        });
+        if balances[i].account == MarketRateAccount::Account {
+            ui.text_edit_singleline(&mut *balances[i].named_account);
+        }
+        ui.horizontal(|ui| {
+            ui.label("Class ID");
+            class_id_ui(ui, registry, &mut balances[i].class_id);
+            ui.label("Asset ID");
+            asset_id_ui(
+                ui,
+                registry,
+                *balances[i].class_id,
+                &mut balances[i].asset_id,
+            );
+            ui.label("Amount");
+            ui.add(egui::DragValue::new(&mut balances[i].amount).speed(1.0));
+            if ui.button("Remove").clicked() {
+                balance_remove_index = Some(i);
+            }
+        });
+        ui.separator();
+    }
+    if let Some(index) = balance_remove_index {
+        balances.remove(index);
+    }
+}
+
+pub fn market_simulator_ui(
+    ui: &mut egui::Ui,
+    market: &mut ResMut<MarketUi>,
+    registry: &IdRegistry,
+    links: &mut DebugUiLinks,
+) {
+    ui.label("Simulate Market Rate");
+    ui.label("Evaluates the rates from Create Rate locally, without calling the chain.");
+    ui.separator();
+    ui.label("Buyer Account");
+    ui.text_edit_singleline(&mut *market.data.input.simulator.buyer);
+    ui.label("Market Account");
+    ui.text_edit_singleline(&mut *market.data.input.simulator.market);
+    if market.data.input.simulator.loading {
+        ui.add(egui::Spinner::default());
+    } else if ui.button("Fetch Balances").clicked() {
+        market.data.input.simulator.balances.clear();
+        market.data.input.simulator.fetch_errors.clear();
+        for (rate_account, account) in [
+            (
+                MarketRateAccount::Buyer,
+                market.data.input.simulator.buyer.clone(),
+            ),
+            (
+                MarketRateAccount::Market,
+                market.data.input.simulator.market.clone(),
+            ),
+        ] {
+            let request = AssetBalancesRequest::new(AssetBalancesInput {
+                class_id: None,
+                account,
+            });
+            market
+                .channels
+                .simulator
+                .pending
+                .push((request.id, rate_account));
+            market.channels.simulator.outbox.push(request);
+        }
+        market.data.input.simulator.loading = true;
+    }
+    for err in market.data.input.simulator.fetch_errors.iter() {
+        ui.colored_label(egui::Color32::RED, err);
+    }
+    ui.separator();
+    simulated_balances_ui(ui, &mut market.data.input.simulator.balances, registry);
+    ui.label("Amount");
+    ui.add(egui::DragValue::new(&mut market.data.input.simulator.amount).speed(1.0));
+    if submit_button(ui, "Simulate") {
+        let rates: Vec<AssetRate> = market
+            .data
+            .input
+            .create_market_rate
+            .rates
+            .iter()
+            .map(|rate| rate.asset_rate())
+            .collect();
+        market.data.input.simulator.result = Some(simulate_market_rates(
+            &rates,
+            &market.data.input.simulator.balances,
+            market.data.input.simulator.amount,
+        ));
+    }
+    if let Some(result) = &market.data.input.simulator.result {
+        ui.separator();
+        ui.label(format!("Quote for amount {}", result.amount));
+        for i in result.failed_has.iter() {
+            ui.colored_label(
+                egui::Color32::RED,
+                format!("Rate {}: Has condition would fail", i),
+            );
+        }
+        rate_balances_ui(ui, &result.balances, links);
+        ui.label("Success");
+        ui.text_edit_singleline(&mut result.success.to_string());
+    }
+}
+
+/// Adds the fetched balances, leaving out and reporting those too large for
+/// the simulator's `u64` amounts.
+fn push_fetched_balances(
+    simulator: &mut MarketSimulatorInputData,
+    account: MarketRateAccount,
+    output: &AssetBalancesOutput,
+) {
+    for item in output.balances.iter() {
+        let amount = u128::from(item.amount);
+        let Ok(amount) = u64::try_from(amount) else {
+            simulator.fetch_errors.push(format!(
+                "{:?} balance of class {} asset {} is too large to simulate: {}",
+                account,
+                u64::from(item.class_id),
+                u64::from(item.asset_id),
+                amount
+            ));
+            continue;
+        };
+        simulator.balances.push(SimulatedBalance {
+            account: account.clone(),
+            named_account: Account::from("".to_string()),
+            class_id: item.class_id,
+            asset_id: item.asset_id,
+            amount,
+        });
+    }
+}
+
+pub fn handle_simulator_balances_response(
+    mut market: ResMut<MarketUi>,
+    mut api: ApiEvents<AssetBalances>,
+) {
+    let market = &mut *market;
+    let simulator = &mut market.channels.simulator;
+    api.requests.send_batch(simulator.outbox.drain(..));
+    for completed in api.completed.iter() {
+        if let Some(index) = simulator
+            .pending
+            .iter()
+            .position(|(id, _)| *id == completed.id)
+        {
+            let (_, account) = simulator.pending.remove(index);
+            push_fetched_balances(&mut market.data.input.simulator, account, &completed.output);
+            market.data.input.simulator.loading = !simulator.pending.is_empty();
+        }
+    }
+    for failed in api.failed.iter() {
+        if let Some(index) = simulator
+            .pending
+            .iter()
+            .position(|(id, _)| *id == failed.id)
+        {
+            let (_, account) = simulator.pending.remove(index);
+            market.data.input.simulator.fetch_errors.push(format!(
+                "{:?} balances failed to load: {}",
+                account, failed.error
+            ));
+            market.data.input.simulator.loading = !simulator.pending.is_empty();
+        }
+    }
+}
+
+pub struct MarketSimulatorPlugin;
+
+impl Plugin for MarketSimulatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(handle_simulator_balances_response);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn balance(account: MarketRateAccount, amount: u64) -> SimulatedBalance {
+        SimulatedBalance {
+            account,
+            class_id: ClassId::from(1),
+            asset_id: AssetId::from(1),
+            amount,
+            ..default()
+        }
+    }
+
+    fn rate(action: RateAction, from: RateAccount) -> AssetRate {
+        AssetRate {
+            class_id: ClassId::from(1),
+            asset_id: AssetId::from(1),
+            action,
+            from,
+            to: RateAccount::Buyer,
+        }
+    }
+
+    #[test]
+    fn transfer_leaves_source_balance() {
+        let result = simulate_market_rates(
+            &[rate(
+                RateAction::Transfer(Amount::from(3)),
+                RateAccount::Market,
+            )],
+            &[balance(MarketRateAccount::Market, 10)],
+            2,
+        );
+        assert_eq!(i128::from(result.balances[0].balance), 4);
+        assert!(result.success);
+    }
+
+    #[test]
+    fn uncovered_transfer_is_negative_and_fails() {
+        let result = simulate_market_rates(
+            &[rate(RateAction::Burn(Amount::from(4)), RateAccount::Buyer)],
+            &[balance(MarketRateAccount::Buyer, 5)],
+            2,
+        );
+        assert_eq!(i128::from(result.balances[0].balance), -3);
+        assert!(result.failed_has.is_empty());
+        assert!(!result.success);
+    }
+
+    #[test]
+    fn missing_balance_counts_as_zero() {
+        let result = simulate_market_rates(
+            &[rate(
+                RateAction::Transfer(Amount::from(1)),
+                RateAccount::Buyer,
+            )],
+            &[balance(MarketRateAccount::Market, 100)],
+            1,
+        );
+        assert_eq!(i128::from(result.balances[0].balance), -1);
+        assert!(!result.success);
+    }
+
+    #[test]
+    fn failed_has_is_reported_by_rate_index() {
+        let rates = [
+            rate(
+                RateAction::Has(AmountOp::GreaterEqualThan, Amount::from(5)),
+                RateAccount::Buyer,
+            ),
+            rate(
+                RateAction::Has(AmountOp::LessThan, Amount::from(5)),
+                RateAccount::Buyer,
+            ),
+        ];
+        let result = simulate_market_rates(&rates, &[balance(MarketRateAccount::Buyer, 5)], 1);
+        assert_eq!(result.failed_has, vec![1]);
+        assert_eq!(i128::from(result.balances[1].balance), 5);
+        assert!(!result.success);
+    }
+
+    #[test]
+    fn has_conditions_that_hold_succeed() {
+        let rates = [rate(
+            RateAction::Has(AmountOp::Equal, Amount::from(5)),
+            RateAccount::Buyer,
+        )];
+        let result = simulate_market_rates(&rates, &[balance(MarketRateAccount::Buyer, 5)], 1);
+        assert!(result.failed_has.is_empty());
+        assert!(result.success);
+    }
+
+    #[test]
+    fn mint_scales_with_amount() {
+        let result = simulate_market_rates(
+            &[rate(RateAction::Mint(Amount::from(7)), RateAccount::Market)],
+            &[],
+            3,
+        );
+        assert_eq!(i128::from(result.balances[0].balance), 21);
+        assert!(result.success);
+    }
+}
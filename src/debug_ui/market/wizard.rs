@@ -0,0 +1,417 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+use sugarfunge_api_types::{
+    market::{
+        CreateMarketInput, CreateMarketRateInput, DepositAssetsInput, ExchangeAssetsInput, Rates,
+    },
+    primitives::{Balance, MarketId, Seed},
+};
+
+use crate::{
+    debug_ui::{
+        links::DebugUiLinks,
+        registry::{market_id_ui, market_rate_id_ui, IdRegistry},
+    },
+    shortcuts::submit_button,
+};
+
+use super::{
+    create_market_rate::{market_rates_ui, template::market_rates_template_ui},
+    deposit_market_assets::rate_balances_ui,
+    MarketUi,
+};
+
+#[derive(Resource, Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum MarketWizardStep {
+    #[default]
+    CreateMarket,
+    CreateMarketRate,
+    DepositMarketAssets,
+    ExchangeMarketAssets,
+    Summary,
+}
+
+impl MarketWizardStep {
+    fn title(&self) -> &str {
+        match self {
+            MarketWizardStep::CreateMarket => "1. Create Market",
+            MarketWizardStep::CreateMarketRate => "2. Create Market Rate",
+            MarketWizardStep::DepositMarketAssets => "3. Deposit Market Assets",
+            MarketWizardStep::ExchangeMarketAssets => "4. Test Exchange",
+            MarketWizardStep::Summary => "5. Summary",
+        }
+    }
+}
+
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarketWizardInputData {
+    pub step: MarketWizardStep,
+    #[serde(skip)]
+    pub seed: Seed,
+    pub market_id: MarketId,
+    pub market_rate_id: MarketId,
+    pub deposit_amount: u64,
+    #[serde(skip)]
+    pub buyer_seed: Seed,
+    pub exchange_amount: u64,
+    #[serde(skip)]
+    pub pending: bool,
+    #[serde(skip)]
+    pub error: Option<String>,
+    #[serde(skip)]
+    pub summary: Vec<String>,
+}
+
+impl Default for MarketWizardInputData {
+    fn default() -> Self {
+        Self {
+            step: MarketWizardStep::default(),
+            seed: Seed::from("".to_string()),
+            market_id: MarketId::from(0),
+            market_rate_id: MarketId::from(0),
+            deposit_amount: 0,
+            buyer_seed: Seed::from("".to_string()),
+            exchange_amount: 0,
+            pending: false,
+            error: None,
+            summary: vec![],
+        }
+    }
+}
+
+pub fn market_wizard_ui(
+    ui: &mut egui::Ui,
+    market: &mut ResMut<MarketUi>,
+    registry: &IdRegistry,
+    links: &mut DebugUiLinks,
+) {
+    ui.label("Market Wizard");
+    ui.separator();
+    ui.label(market.data.input.wizard.step.title());
+    ui.separator();
+
+    if market.data.input.wizard.pending {
+        check_pending_step(market);
+    }
+
+    match market.data.input.wizard.step {
+        MarketWizardStep::CreateMarket => {
+            ui.label("Seed");
+            ui.text_edit_singleline(&mut *market.data.input.wizard.seed);
+            ui.label("Market ID");
+            market_id_ui(ui, registry, &mut market.data.input.wizard.market_id);
+        }
+        MarketWizardStep::CreateMarketRate => {
+            ui.label(format!(
+                "Market ID: {}",
+                u64::from(market.data.input.wizard.market_id)
+            ));
+            ui.label("Market Rate ID");
+            market_rate_id_ui(
+                ui,
+                registry,
+                u64::from(market.data.input.wizard.market_id),
+                &mut market.data.input.wizard.market_rate_id,
+            );
+            market_rates_template_ui(ui, &mut market.data.input.rates_template);
+            market_rates_ui(
+                ui,
+                &mut market.data.input.create_market_rate.rates,
+                registry,
+            );
+            ui.label("Rates Metadata");
+            ui.text_edit_multiline(&mut market.data.input.create_market_rate.rates_metadata);
+        }
+        MarketWizardStep::DepositMarketAssets => {
+            ui.label(format!(
+                "Market ID: {}  Market Rate ID: {}",
+                u64::from(market.data.input.wizard.market_id),
+                u64::from(market.data.input.wizard.market_rate_id)
+            ));
+            ui.label("Amount");
+            ui.add(egui::DragValue::new(&mut market.data.input.wizard.deposit_amount).speed(1.0));
+        }
+        MarketWizardStep::ExchangeMarketAssets => {
+            ui.label(format!(
+                "Market ID: {}  Market Rate ID: {}",
+                u64::from(market.data.input.wizard.market_id),
+                u64::from(market.data.input.wizard.market_rate_id)
+            ));
+            ui.label("Buyer Seed");
+            ui.text_edit_singleline(&mut *market.data.input.wizard.buyer_seed);
+            ui.label("Amount");
+            ui.add(egui::DragValue::new(&mut market.data.input.wizard.exchange_amount).speed(1.0));
+        }
+        MarketWizardStep::Summary => {
+            for line in market.data.input.wizard.summary.iter() {
+                ui.label(line);
+            }
+            if let Some(output) = &market.data.output.exchange_market_assets {
+                ui.separator();
+                rate_balances_ui(ui, &output.balances, links);
+            }
+        }
+    }
+
+    if let Some(error) = &market.data.input.wizard.error {
+        ui.separator();
+        ui.colored_label(egui::Color32::RED, error);
+    }
+
+    ui.separator();
+    if market.data.input.wizard.pending {
+        ui.add(egui::Spinner::default());
+        return;
+    }
+    ui.horizontal(|ui| match market.data.input.wizard.step {
+        MarketWizardStep::CreateMarket => {
+            if submit_button(ui, "Create Market") {
+                submit_create_market(market);
+            }
+        }
+        MarketWizardStep::CreateMarketRate => {
+            if submit_button(ui, "Create Market Rate") {
+                submit_create_market_rate(market);
+            }
+        }
+        MarketWizardStep::DepositMarketAssets => {
+            if submit_button(ui, "Deposit") {
+                submit_deposit_market_assets(market);
+            }
+        }
+        MarketWizardStep::ExchangeMarketAssets => {
+            if submit_button(ui, "Exchange") {
+                submit_exchange_market_assets(market);
+            }
+            if ui.button("Skip").clicked() {
+                market
+                    .data
+                    .input
+                    .wizard
+                    .summary
+                    .push("Test exchange skipped".to_string());
+                market.data.input.wizard.step = MarketWizardStep::Summary;
+            }
+        }
+        MarketWizardStep::Summary => {
+            if ui.button("Start Over").clicked() {
+                market.data.input.wizard = MarketWizardInputData::default();
+            }
+        }
+    });
+}
+
+fn submit_create_market(market: &mut ResMut<MarketUi>) {
+    let wizard = market.data.input.wizard.clone();
+    market.data.input.create_market.seed = wizard.seed.clone();
+    market.data.input.create_market.market_id = wizard.market_id;
+    market.data.output.create_market = None;
+    market.channels.create_market.send(CreateMarketInput {
+        seed: wizard.seed,
+        market_id: wizard.market_id,
+    });
+    market.data.input.create_market.loading = true;
+    market.data.input.wizard.pending = true;
+    market.data.input.wizard.error = None;
+}
+
+fn submit_create_market_rate(market: &mut ResMut<MarketUi>) {
+    let wizard = market.data.input.wizard.clone();
+    if market.data.input.create_market_rate.rates.is_empty() {
+        market.data.input.wizard.error = Some("At least one rate is required".to_string());
+        return;
+    }
+    if let Some(error) = market
+        .data
+        .input
+        .create_market_rate
+        .rates
+        .iter()
+        .enumerate()
+        .find_map(|(i, rate)| {
+            rate.validate()
+                .first()
+                .map(|error| format!("Rate {}: {}", i, error))
+        })
+    {
+        market.data.input.wizard.error = Some(error);
+        return;
+    }
+    let metadata = match serde_json::from_str(&market.data.input.create_market_rate.rates_metadata)
+    {
+        Ok(metadata) => metadata,
+        Err(err) => {
+            market.data.input.wizard.error = Some(format!("Invalid rates metadata: {}", err));
+            return;
+        }
+    };
+    market.data.input.create_market_rate.seed = wizard.seed.clone();
+    market.data.input.create_market_rate.market_id = wizard.market_id;
+    market.data.input.create_market_rate.market_rate_id = wizard.market_rate_id;
+    market.data.output.create_market_rate = None;
+    let input = CreateMarketRateInput {
+        seed: wizard.seed,
+        market_id: wizard.market_id,
+        market_rate_id: wizard.market_rate_id,
+        rates: Rates {
+            rates: market
+                .data
+                .input
+                .create_market_rate
+                .rates
+                .iter()
+                .map(|rate| rate.asset_rate())
+                .collect(),
+            metadata,
+        },
+    };
+    market.channels.create_market_rate.send(input);
+    market.data.input.create_market_rate.loading = true;
+    market.data.input.wizard.pending = true;
+    market.data.input.wizard.error = None;
+}
+
+fn submit_deposit_market_assets(market: &mut ResMut<MarketUi>) {
+    let wizard = market.data.input.wizard.clone();
+    market.data.input.deposit_market_assets.seed = wizard.seed.clone();
+    market.data.input.deposit_market_assets.market_id = wizard.market_id;
+    market.data.input.deposit_market_assets.market_rate_id = wizard.market_rate_id;
+    market.data.input.deposit_market_assets.amount = wizard.deposit_amount;
+    market.data.output.deposit_market_assets = None;
+    market
+        .channels
+        .deposit_market_assets
+        .send(DepositAssetsInput {
+            seed: wizard.seed,
+            market_id: wizard.market_id,
+            market_rate_id: wizard.market_rate_id,
+            amount: Balance::from(wizard.deposit_amount as u128),
+        });
+    market.data.input.deposit_market_assets.loading = true;
+    market.data.input.wizard.pending = true;
+    market.data.input.wizard.error = None;
+}
+
+fn submit_exchange_market_assets(market: &mut ResMut<MarketUi>) {
+    let wizard = market.data.input.wizard.clone();
+    market.data.input.exchange_market_assets.seed = wizard.buyer_seed.clone();
+    market.data.input.exchange_market_assets.market_id = wizard.market_id;
+    market.data.input.exchange_market_assets.market_rate_id = wizard.market_rate_id;
+    market.data.input.exchange_market_assets.amount = wizard.exchange_amount;
+    market.data.output.exchange_market_assets = None;
+    market
+        .channels
+        .exchange_market_assets
+        .send(ExchangeAssetsInput {
+            seed: wizard.buyer_seed,
+            market_id: wizard.market_id,
+            market_rate_id: wizard.market_rate_id,
+            amount: Balance::from(wizard.exchange_amount as u128),
+        });
+    market.data.input.exchange_market_assets.loading = true;
+    market.data.input.wizard.pending = true;
+    market.data.input.wizard.error = None;
+}
+
+/// Once the request sent by the current step has been answered, validate its
+/// output against the wizard state and either advance or report the failure.
+fn check_pending_step(market: &mut ResMut<MarketUi>) {
+    let market_id = market.data.input.wizard.market_id;
+    let market_rate_id = market.data.input.wizard.market_rate_id;
+    let result: Result<String, String> = match market.data.input.wizard.step {
+        MarketWizardStep::CreateMarket => {
+            if market.data.input.create_market.loading {
+                return;
+            }
+            match &market.data.output.create_market {
+                Some(output) if u64::from(output.market_id) == u64::from(market_id) => Ok(format!(
+                    "Market {} created by {}",
+                    u64::from(output.market_id),
+                    output.who.as_str()
+                )),
+                Some(output) => Err(format!(
+                    "Expected market {} but got {}",
+                    u64::from(market_id),
+                    u64::from(output.market_id)
+                )),
+                None => Err("Create market request failed".to_string()),
+            }
+        }
+        MarketWizardStep::CreateMarketRate => {
+            if market.data.input.create_market_rate.loading {
+                return;
+            }
+            match &market.data.output.create_market_rate {
+                Some(output)
+                    if u64::from(output.market_id) == u64::from(market_id)
+                        && u64::from(output.market_rate_id) == u64::from(market_rate_id) =>
+                {
+                    Ok(format!(
+                        "Market rate {} created for market {} with {} rate(s)",
+                        u64::from(output.market_rate_id),
+                        u64::from(output.market_id),
+                        market.data.input.create_market_rate.rates.len()
+                    ))
+                }
+                Some(output) => Err(format!(
+                    "Expected market rate {}/{} but got {}/{}",
+                    u64::from(market_id),
+                    u64::from(market_rate_id),
+                    u64::from(output.market_id),
+                    u64::from(output.market_rate_id)
+                )),
+                None => Err("Create market rate request failed".to_string()),
+            }
+        }
+        MarketWizardStep::DepositMarketAssets => {
+            if market.data.input.deposit_market_assets.loading {
+                return;
+            }
+            match &market.data.output.deposit_market_assets {
+                Some(output) if output.success => Ok(format!(
+                    "Deposited {} into market rate {} by {}",
+                    u128::from(output.amount),
+                    u64::from(output.market_rate_id),
+                    output.who.as_str()
+                )),
+                Some(_) => Err("Deposit was not successful".to_string()),
+                None => Err("Deposit request failed".to_string()),
+            }
+        }
+        MarketWizardStep::ExchangeMarketAssets => {
+            if market.data.input.exchange_market_assets.loading {
+                return;
+            }
+            match &market.data.output.exchange_market_assets {
+                Some(output) if output.success => Ok(format!(
+                    "Exchanged {} on market rate {} by {}",
+                    u128::from(output.amount),
+                    u64::from(output.market_rate_id),
+                    output.buyer.as_str()
+                )),
+                Some(_) => Err("Exchange was not successful".to_string()),
+                None => Err("Exchange request failed".to_string()),
+            }
+        }
+        MarketWizardStep::Summary => return,
+    };
+
+    let wizard = &mut market.data.input.wizard;
+    wizard.pending = false;
+    match result {
+        Ok(line) => {
+            wizard.summary.push(line);
+            wizard.step = match wizard.step {
+                MarketWizardStep::CreateMarket => MarketWizardStep::CreateMarketRate,
+                MarketWizardStep::CreateMarketRate => MarketWizardStep::DepositMarketAssets,
+                MarketWizardStep::DepositMarketAssets => MarketWizardStep::ExchangeMarketAssets,
+                MarketWizardStep::ExchangeMarketAssets | MarketWizardStep::Summary => {
+                    MarketWizardStep::Summary
+                }
+            };
+        }
+        Err(error) => wizard.error = Some(error),
+    }
+}
@@ -6,10 +6,19 @@ pub mod asset;
 pub mod bag;
 pub mod bundle;
 pub mod class;
+pub mod dock;
+pub mod export;
+pub mod links;
 pub mod market;
+pub mod network_log;
+pub mod palette;
+pub mod persist;
+pub mod registry;
 pub mod validator;
 
-#[derive(Resource, Debug, Default, Eq, PartialEq)]
+/// The section to bring forward. Setting it shows that section's selected
+/// form in a visible panel, docking a new tab if none shows the section.
+#[derive(Resource, Debug, Default, Clone, Copy, Eq, PartialEq)]
 pub enum DebugUiActions {
     #[default]
     Account,
@@ -19,42 +28,110 @@ pub enum DebugUiActions {
     Bundle,
     Market,
     Validator,
+    Network,
 }
 
 pub fn debug_ui(
     mut ctx: EguiContexts,
     mut debug_actions: ResMut<DebugUiActions>,
-    mut asset: ResMut<asset::AssetUi>,
-    mut account: ResMut<account::AccountUi>,
-    mut class: ResMut<class::ClassUi>,
-    mut bag: ResMut<bag::BagUi>,
-    mut bundle: ResMut<bundle::BundleUi>,
-    mut market: ResMut<market::MarketUi>,
-    mut validator: ResMut<validator::ValidatorUi>,
+    mut sections: dock::DebugUiSections,
+    mut layouts: ResMut<dock::DebugUiLayouts>,
+    registry: Res<registry::IdRegistry>,
+    mut links: ResMut<links::DebugUiLinks>,
 ) {
+    if debug_actions.is_changed() && !debug_actions.is_added() {
+        let panel = sections.panel(&debug_actions);
+        layouts.current.focus(panel);
+    }
+
+    let ctx = ctx.ctx_mut();
+    let layouts = &mut *layouts;
     egui::Window::new("SugarFunge Debug UI")
         .scroll2([false, true])
-        .show(&mut ctx.ctx_mut(), |ui| {
+        .show(ctx, |ui| {
             ui.horizontal(|ui| {
-                ui.selectable_value(&mut *debug_actions, DebugUiActions::Account, "Account");
-                ui.selectable_value(&mut *debug_actions, DebugUiActions::Class, "Class");
-                ui.selectable_value(&mut *debug_actions, DebugUiActions::Asset, "Asset");
-                ui.selectable_value(&mut *debug_actions, DebugUiActions::Bag, "Bag");
-                ui.selectable_value(&mut *debug_actions, DebugUiActions::Bundle, "Bundle");
-                ui.selectable_value(&mut *debug_actions, DebugUiActions::Market, "Market");
-                ui.selectable_value(&mut *debug_actions, DebugUiActions::Validator, "Validator");
+                dock::layouts_menu_ui(ui, layouts);
+                let layout = &mut layouts.current;
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if let Some(panel) = layout.tabs.get_mut(layout.active) {
+                        if ui
+                            .button("Reset Form")
+                            .on_hover_text("Restore the active tab's form defaults")
+                            .clicked()
+                        {
+                            sections.reset_form(panel);
+                        }
+                    }
+                });
             });
+            dock::tabs_ui(ui, &mut layouts.current);
+            links::address_book_ui(ui, &mut links);
             ui.separator();
-            match &*debug_actions {
-                DebugUiActions::Account => account::account_ui(ui, &mut account),
-                DebugUiActions::Class => class::class_ui(ui, &mut class),
-                DebugUiActions::Asset => asset::asset_ui(ui, &mut asset),
-                DebugUiActions::Bag => bag::bag_ui(ui, &mut bag),
-                DebugUiActions::Bundle => bundle::bundle_ui(ui, &mut bundle),
-                DebugUiActions::Market => market::market_ui(ui, &mut market),
-                DebugUiActions::Validator => validator::validator_ui(ui, &mut validator),
+            let layout = &mut layouts.current;
+            let (active, split) = (layout.active, layout.split);
+            match split.filter(|split| *split < layout.tabs.len() && *split != active) {
+                Some(split) => {
+                    ui.columns(2, |columns| {
+                        for (column, i) in columns.iter_mut().zip([active, split]) {
+                            column.push_id(i, |ui| {
+                                sections.panel_ui(ui, &mut layout.tabs[i], &registry, &mut links);
+                            });
+                        }
+                    });
+                }
+                None => match layout.tabs.get_mut(active) {
+                    Some(panel) => sections.panel_ui(ui, panel, &registry, &mut links),
+                    None => {
+                        ui.label("No panels open. Add one with \"+\".");
+                    }
+                },
             }
         });
+
+    let layout = &mut layouts.current;
+    let mut dock = None;
+    let mut close = None;
+    for (i, window) in layout.windows.iter_mut().enumerate() {
+        let panel = &mut window.panel;
+        let mut open = true;
+        egui::Window::new(panel.title())
+            .id(egui::Id::new(("debug_ui_panel", window.id)))
+            .open(&mut open)
+            .scroll2([false, true])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("Dock").clicked() {
+                        dock = Some(i);
+                    }
+                    if ui.button("Reset Form").clicked() {
+                        sections.reset_form(panel);
+                    }
+                });
+                ui.separator();
+                sections.panel_ui(ui, panel, &registry, &mut links);
+            });
+        if !open {
+            close = Some(i);
+        }
+    }
+    if let Some(i) = dock {
+        layout.dock(i);
+    } else if let Some(i) = close {
+        layout.windows.remove(i);
+    }
+
+    for (target, value) in std::mem::take(&mut links.pending) {
+        match target.section() {
+            DebugUiActions::Account => sections.account.open_link(target, &value),
+            DebugUiActions::Class => sections.class.open_link(target, &value),
+            DebugUiActions::Asset => sections.asset.open_link(target, &value),
+            DebugUiActions::Bag => sections.bag.open_link(target, &value),
+            DebugUiActions::Bundle => sections.bundle.open_link(target, &value),
+            DebugUiActions::Market => sections.market.open_link(target, &value),
+            DebugUiActions::Validator | DebugUiActions::Network => {}
+        }
+        *debug_actions = target.section();
+    }
 }
 
 pub struct DebugUiPlugin;
@@ -62,6 +139,12 @@ pub struct DebugUiPlugin;
 impl Plugin for DebugUiPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<DebugUiActions>()
+            .init_resource::<links::DebugUiLinks>()
+            .init_resource::<dock::DebugUiLayouts>()
+            .init_resource::<palette::CommandPalette>()
+            .init_resource::<network_log::NetworkLogUi>()
+            .add_plugin(registry::IdRegistryPlugin)
+            .add_plugin(persist::DebugUiPersistPlugin)
             .add_plugin(account::AccountPlugin)
             .add_plugin(class::ClassPlugin)
             .add_plugin(asset::AssetPlugin)
@@ -69,6 +152,7 @@ impl Plugin for DebugUiPlugin {
             .add_plugin(bundle::BundlePlugin)
             .add_plugin(market::MarketPlugin)
             .add_plugin(validator::ValidatorPlugin)
-            .add_system(debug_ui);
+            .add_system(debug_ui)
+            .add_system(palette::command_palette_ui.after(debug_ui));
     }
 }
@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::api::{ApiLog, ApiLogEntry};
+
+use super::export::REDACTED_SEED;
+
+/// Filters of the network log panel.
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkLogUi {
+    /// Shows only endpoints whose path contains it.
+    pub filter: String,
+    pub failures_only: bool,
+}
+
+impl NetworkLogUi {
+    fn shows(&self, entry: &ApiLogEntry) -> bool {
+        (!self.failures_only || entry.result.is_err()) && entry.path.contains(self.filter.trim())
+    }
+}
+
+/// Replaces every `seed` field, so the log can be shown and copied safely.
+fn redact_seeds(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(fields) => {
+            for (key, field) in fields.iter_mut() {
+                if key == "seed" {
+                    *field = serde_json::Value::String(REDACTED_SEED.to_string());
+                } else {
+                    redact_seeds(field);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_seeds),
+        _ => {}
+    }
+}
+
+fn pretty(value: &serde_json::Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+fn entry_ui(ui: &mut egui::Ui, entry: &ApiLogEntry) {
+    let (mark, color) = match &entry.result {
+        Ok(_) => ("ok", egui::Color32::GREEN),
+        Err(_) => ("failed", egui::Color32::RED),
+    };
+    let title = egui::RichText::new(format!("{} {} {}", entry.id, entry.path, mark)).color(color);
+    egui::CollapsingHeader::new(title)
+        .id_source(entry.id)
+        .show(ui, |ui| {
+            let mut input = entry.input.clone();
+            redact_seeds(&mut input);
+            let input = pretty(&input);
+            ui.horizontal(|ui| {
+                ui.label("Input");
+                if ui.small_button("Copy").clicked() {
+                    ui.output_mut(|output| output.copied_text = input.clone());
+                }
+            });
+            ui.monospace(input);
+            match &entry.result {
+                Ok(output) => {
+                    ui.label("Output");
+                    ui.monospace(pretty(output));
+                }
+                Err(err) => {
+                    ui.colored_label(egui::Color32::RED, err);
+                }
+            }
+        });
+}
+
+/// Every API response the app received, whichever system sent the request,
+/// newest first.
+pub fn network_log_ui(ui: &mut egui::Ui, network: &mut NetworkLogUi, log: &mut ApiLog) {
+    ui.label("Network Log");
+    ui.separator();
+    ui.horizontal(|ui| {
+        ui.add(egui::TextEdit::singleline(&mut network.filter).hint_text("Filter by path"));
+        ui.checkbox(&mut network.failures_only, "Failures only");
+        if ui.button("Clear").clicked() {
+            log.entries.clear();
+        }
+    });
+    ui.separator();
+    let mut shown = 0;
+    for entry in log
+        .entries
+        .iter()
+        .rev()
+        .filter(|entry| network.shows(entry))
+    {
+        entry_ui(ui, entry);
+        shown += 1;
+    }
+    if shown == 0 {
+        ui.label("No responses yet");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_nested_seeds() {
+        let mut input = serde_json::json!({
+            "seed": "//Alice",
+            "rates": [{"seed": "//Bob", "amount": 1}],
+        });
+        redact_seeds(&mut input);
+        assert_eq!(
+            input,
+            serde_json::json!({
+                "seed": REDACTED_SEED,
+                "rates": [{"seed": REDACTED_SEED, "amount": 1}],
+            })
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use super::dock::{DebugUiLayouts, DebugUiPanel};
+
+const HISTORY_LENGTH: usize = 8;
+const MAX_RESULTS: usize = 12;
+
+/// Ctrl+K search over every debug UI form. Running one shows it, docking a
+/// tab for it if needed.
+#[derive(Resource, Debug, Default)]
+pub struct CommandPalette {
+    pub open: bool,
+    pub query: String,
+    pub selected: usize,
+    /// Most recently run first.
+    pub history: VecDeque<DebugUiPanel>,
+    focus_query: bool,
+}
+
+impl CommandPalette {
+    pub fn toggle(&mut self) {
+        if self.open {
+            self.close();
+        } else {
+            self.open = true;
+            self.query.clear();
+            self.selected = 0;
+            self.focus_query = true;
+        }
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    fn remember(&mut self, panel: DebugUiPanel) {
+        self.history.retain(|recent| *recent != panel);
+        self.history.push_front(panel);
+        self.history.truncate(HISTORY_LENGTH);
+    }
+
+    /// Recent commands while the query is empty, otherwise every command
+    /// matching it, best first.
+    fn results(&self) -> Vec<(DebugUiPanel, bool)> {
+        if self.query.trim().is_empty() {
+            let recent = self.history.iter().map(|panel| (*panel, true));
+            let rest = DebugUiPanel::all()
+                .into_iter()
+                .filter(|panel| !self.history.contains(panel))
+                .map(|panel| (panel, false));
+            return recent.chain(rest).take(MAX_RESULTS).collect();
+        }
+        let mut scored: Vec<_> = DebugUiPanel::all()
+            .into_iter()
+            .filter_map(|panel| {
+                let recent = self.history.contains(&panel);
+                fuzzy_score(&self.query, &panel.title())
+                    .map(|score| (score + if recent { 5 } else { 0 }, panel, recent))
+            })
+            .collect();
+        scored.sort_by_key(|(score, _, _)| std::cmp::Reverse(*score));
+        scored
+            .into_iter()
+            .take(MAX_RESULTS)
+            .map(|(_, panel, recent)| (panel, recent))
+            .collect()
+    }
+}
+
+/// Scores `text` when every character of `query` appears in it in order,
+/// favoring consecutive matches and matches at word starts.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+    for query_char in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = (position..text.len()).find(|i| text[*i] == query_char)?;
+        score += 1;
+        if previous == Some(found.wrapping_sub(1)) {
+            score += 3;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 2;
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+    Some(score - text.len() as i32 / 8)
+}
+
+pub fn command_palette_ui(
+    mut ctx: EguiContexts,
+    mut palette: ResMut<CommandPalette>,
+    mut layouts: ResMut<DebugUiLayouts>,
+) {
+    if !palette.open {
+        return;
+    }
+    let ctx = ctx.ctx_mut();
+    let results = palette.results();
+    let (up, down, enter, escape) = ctx.input(|input| {
+        (
+            input.key_pressed(egui::Key::ArrowUp),
+            input.key_pressed(egui::Key::ArrowDown),
+            input.key_pressed(egui::Key::Enter),
+            input.key_pressed(egui::Key::Escape),
+        )
+    });
+    if down {
+        palette.selected = (palette.selected + 1).min(results.len().saturating_sub(1));
+    }
+    if up {
+        palette.selected = palette.selected.saturating_sub(1);
+    }
+    let mut run = if enter {
+        results.get(palette.selected).map(|(panel, _)| *panel)
+    } else {
+        None
+    };
+    egui::Window::new("Command Palette")
+        .title_bar(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 40.0])
+        .show(ctx, |ui| {
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut palette.query)
+                    .hint_text("Search actions…")
+                    .desired_width(320.0),
+            );
+            if palette.focus_query {
+                response.request_focus();
+                palette.focus_query = false;
+            }
+            if response.changed() {
+                palette.selected = 0;
+            }
+            ui.separator();
+            if results.is_empty() {
+                ui.label("No matching actions");
+            }
+            for (i, (panel, recent)) in results.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui
+                        .selectable_label(palette.selected == i, panel.title())
+                        .clicked()
+                    {
+                        run = Some(*panel);
+                    }
+                    if *recent {
+                        ui.weak("recent");
+                    }
+                });
+            }
+        });
+    if let Some(panel) = run {
+        layouts.current.focus(panel);
+        palette.remember(panel);
+        palette.close();
+    } else if escape {
+        palette.close();
+    }
+}
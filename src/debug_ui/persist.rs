@@ -0,0 +1,175 @@
+use bevy::{app::AppExit, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    account::{AccountInputData, AccountUi},
+    asset::{AssetInputData, AssetUi},
+    bag::{BagInputData, BagUi},
+    bundle::{BundleInputData, BundleUi},
+    class::{ClassInputData, ClassUi},
+    dock::DebugUiLayouts,
+    links::{AddressBookEntry, DebugUiLinks},
+    market::{MarketInputData, MarketUi},
+    validator::{ValidatorInputData, ValidatorUi},
+};
+
+/// File the form state is saved to on native builds.
+#[cfg(not(target_arch = "wasm32"))]
+pub const DEBUG_UI_STATE_PATH: &str = "debug_ui_state.ron";
+
+/// `localStorage` key the form state is saved under on the web.
+#[cfg(target_arch = "wasm32")]
+pub const DEBUG_UI_STATE_KEY: &str = "sf-wasm-app.debug_ui_state";
+
+const SAVE_INTERVAL_SECONDS: f32 = 2.0;
+
+/// The inputs of every debug UI form, the panel layouts and the address book.
+/// Seeds, loading flags and results are skipped by the input types
+/// themselves, so secrets never reach storage.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct DebugUiState {
+    pub account: AccountInputData,
+    pub class: ClassInputData,
+    pub asset: AssetInputData,
+    pub bag: BagInputData,
+    pub bundle: BundleInputData,
+    pub market: MarketInputData,
+    pub validator: ValidatorInputData,
+    pub layouts: DebugUiLayouts,
+    pub address_book: Vec<AddressBookEntry>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_state() -> Option<String> {
+    std::fs::read_to_string(DEBUG_UI_STATE_PATH).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_state(contents: &str) -> Result<(), String> {
+    std::fs::write(DEBUG_UI_STATE_PATH, contents).map_err(|err| err.to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_state() -> Option<String> {
+    local_storage()?.get_item(DEBUG_UI_STATE_KEY).ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write_state(contents: &str) -> Result<(), String> {
+    local_storage()
+        .ok_or_else(|| "localStorage is unavailable".to_string())?
+        .set_item(DEBUG_UI_STATE_KEY, contents)
+        .map_err(|err| format!("{:?}", err))
+}
+
+/// Last saved contents, so unchanged forms aren't written again.
+#[derive(Resource)]
+pub struct DebugUiPersistence {
+    timer: Timer,
+    saved: String,
+}
+
+impl Default for DebugUiPersistence {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(SAVE_INTERVAL_SECONDS, TimerMode::Repeating),
+            saved: String::new(),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn load_debug_ui_state(
+    mut persistence: ResMut<DebugUiPersistence>,
+    mut account: ResMut<AccountUi>,
+    mut class: ResMut<ClassUi>,
+    mut asset: ResMut<AssetUi>,
+    mut bag: ResMut<BagUi>,
+    mut bundle: ResMut<BundleUi>,
+    mut market: ResMut<MarketUi>,
+    mut validator: ResMut<ValidatorUi>,
+    mut layouts: ResMut<DebugUiLayouts>,
+    mut links: ResMut<DebugUiLinks>,
+) {
+    let Some(contents) = read_state() else {
+        return;
+    };
+    match ron::from_str::<DebugUiState>(&contents) {
+        Ok(state) => {
+            account.data.input = state.account;
+            class.data.input = state.class;
+            asset.data.input = state.asset;
+            bag.data.input = state.bag;
+            bundle.data.input = state.bundle;
+            market.data.input = state.market;
+            validator.data.input = state.validator;
+            *layouts = state.layouts;
+            links.address_book = state.address_book;
+            persistence.saved = contents;
+        }
+        Err(err) => warn!("Ignoring saved debug UI state: {}", err),
+    }
+}
+
+/// Saves every few seconds and on exit, when anything changed.
+#[allow(clippy::too_many_arguments)]
+fn save_debug_ui_state(
+    time: Res<Time>,
+    mut exit: EventReader<AppExit>,
+    mut persistence: ResMut<DebugUiPersistence>,
+    account: Res<AccountUi>,
+    class: Res<ClassUi>,
+    asset: Res<AssetUi>,
+    bag: Res<BagUi>,
+    bundle: Res<BundleUi>,
+    market: Res<MarketUi>,
+    validator: Res<ValidatorUi>,
+    layouts: Res<DebugUiLayouts>,
+    links: Res<DebugUiLinks>,
+) {
+    let exiting = exit.iter().last().is_some();
+    if !persistence.timer.tick(time.delta()).just_finished() && !exiting {
+        return;
+    }
+    let state = DebugUiState {
+        account: account.data.input.clone(),
+        class: class.data.input.clone(),
+        asset: asset.data.input.clone(),
+        bag: bag.data.input.clone(),
+        bundle: bundle.data.input.clone(),
+        market: market.data.input.clone(),
+        validator: validator.data.input.clone(),
+        layouts: layouts.clone(),
+        address_book: links.address_book.clone(),
+    };
+    let contents = match ron::ser::to_string_pretty(&state, ron::ser::PrettyConfig::default()) {
+        Ok(contents) => contents,
+        Err(err) => {
+            warn!("Failed to serialize debug UI state: {}", err);
+            return;
+        }
+    };
+    if contents == persistence.saved {
+        return;
+    }
+    match write_state(&contents) {
+        Ok(()) => persistence.saved = contents,
+        Err(err) => warn!("Failed to save debug UI state: {}", err),
+    }
+}
+
+pub struct DebugUiPersistPlugin;
+
+impl Plugin for DebugUiPersistPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugUiPersistence>()
+            .add_startup_system(load_debug_ui_state)
+            .add_system(save_debug_ui_state);
+    }
+}
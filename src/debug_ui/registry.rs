@@ -0,0 +1,345 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+use sugarfunge_api_types::primitives::{AssetId, ClassId, MarketId};
+
+use crate::api::{
+    AssetBalanceCompleted, AssetBalancesCompleted, AssetInfoCompleted, BurnAssetCompleted,
+    BurnBundleCompleted, ClassInfoCompleted, CreateAssetCompleted, CreateBagCompleted,
+    CreateClassCompleted, CreateMarketCompleted, CreateMarketRateCompleted, DepositAssetsCompleted,
+    DepositBagCompleted, ExchangeAssetsCompleted, MintAssetCompleted, MintBundleCompleted,
+    RegisterBagCompleted, RegisterBundleCompleted, SweepBagCompleted, TransferFromCompleted,
+    UpdateAssetMetadataCompleted,
+};
+
+/// Every ID seen in an API output this session, with the `name` from its
+/// metadata when known. Feeds the dropdowns next to the ID fields.
+#[derive(Resource, Debug, Default)]
+pub struct IdRegistry {
+    pub classes: BTreeMap<u64, String>,
+    pub assets: BTreeMap<(u64, u64), String>,
+    /// Bag account to the class it was created in.
+    pub bags: BTreeMap<String, u64>,
+    /// Bundle ID to its `class/asset`.
+    pub bundles: BTreeMap<String, String>,
+    pub markets: BTreeMap<u64, String>,
+    pub rates: BTreeMap<(u64, u64), String>,
+}
+
+fn metadata_name(metadata: &serde_json::Value) -> Option<&str> {
+    metadata.get("name").and_then(|name| name.as_str())
+}
+
+/// Adds `key`, keeping a known name unless `name` replaces it.
+fn insert<K: Ord>(map: &mut BTreeMap<K, String>, key: K, name: Option<&str>) {
+    let entry = map.entry(key).or_default();
+    if let Some(name) = name {
+        *entry = name.to_string();
+    }
+}
+
+impl IdRegistry {
+    pub fn add_class(&mut self, class_id: ClassId, name: Option<&str>) {
+        insert(&mut self.classes, u64::from(class_id), name);
+    }
+
+    pub fn add_asset(&mut self, class_id: ClassId, asset_id: AssetId, name: Option<&str>) {
+        self.add_class(class_id, None);
+        insert(
+            &mut self.assets,
+            (u64::from(class_id), u64::from(asset_id)),
+            name,
+        );
+    }
+
+    pub fn add_market(&mut self, market_id: MarketId) {
+        insert(&mut self.markets, u64::from(market_id), None);
+    }
+
+    pub fn add_rate(&mut self, market_id: MarketId, market_rate_id: MarketId, name: Option<&str>) {
+        self.add_market(market_id);
+        insert(
+            &mut self.rates,
+            (u64::from(market_id), u64::from(market_rate_id)),
+            name,
+        );
+    }
+}
+
+fn register_class_ids(
+    mut registry: ResMut<IdRegistry>,
+    mut create: EventReader<CreateClassCompleted>,
+    mut info: EventReader<ClassInfoCompleted>,
+) {
+    for created in create.iter() {
+        registry.add_class(
+            created.output.class_id,
+            metadata_name(&created.input.metadata),
+        );
+    }
+    for info in info
+        .iter()
+        .filter_map(|completed| completed.output.info.as_ref())
+    {
+        registry.add_class(info.class_id, metadata_name(&info.metadata));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn register_asset_ids(
+    mut registry: ResMut<IdRegistry>,
+    mut create: EventReader<CreateAssetCompleted>,
+    mut info: EventReader<AssetInfoCompleted>,
+    mut update_metadata: EventReader<UpdateAssetMetadataCompleted>,
+    mut mint: EventReader<MintAssetCompleted>,
+    mut burn: EventReader<BurnAssetCompleted>,
+    mut balance: EventReader<AssetBalanceCompleted>,
+    mut balances: EventReader<AssetBalancesCompleted>,
+    mut transfer_from: EventReader<TransferFromCompleted>,
+) {
+    for created in create.iter() {
+        let name = metadata_name(&created.input.metadata);
+        registry.add_asset(created.output.class_id, created.output.asset_id, name);
+    }
+    for info in info
+        .iter()
+        .filter_map(|completed| completed.output.info.as_ref())
+    {
+        registry.add_asset(info.class_id, info.asset_id, metadata_name(&info.metadata));
+    }
+    for updated in update_metadata.iter() {
+        let output = &updated.output;
+        registry.add_asset(
+            output.class_id,
+            output.asset_id,
+            metadata_name(&output.metadata),
+        );
+    }
+    for mint in mint.iter() {
+        registry.add_asset(mint.output.class_id, mint.output.asset_id, None);
+    }
+    for burn in burn.iter() {
+        registry.add_asset(burn.output.class_id, burn.output.asset_id, None);
+    }
+    for balance in balance.iter() {
+        registry.add_asset(balance.input.class_id, balance.input.asset_id, None);
+    }
+    for balances in balances.iter() {
+        for balance in balances.output.balances.iter() {
+            registry.add_asset(balance.class_id, balance.asset_id, None);
+        }
+    }
+    for transfer in transfer_from.iter() {
+        registry.add_asset(transfer.output.class_id, transfer.output.asset_id, None);
+    }
+}
+
+fn register_bag_ids(
+    mut registry: ResMut<IdRegistry>,
+    mut register: EventReader<RegisterBagCompleted>,
+    mut create: EventReader<CreateBagCompleted>,
+    mut sweep: EventReader<SweepBagCompleted>,
+    mut deposit: EventReader<DepositBagCompleted>,
+) {
+    for registered in register.iter() {
+        registry.add_class(
+            registered.output.class_id,
+            metadata_name(&registered.input.metadata),
+        );
+    }
+    for created in create.iter() {
+        let output = &created.output;
+        registry.add_asset(output.class_id, output.asset_id, None);
+        registry
+            .bags
+            .insert(output.bag.to_string(), u64::from(output.class_id));
+    }
+    for bag in sweep
+        .iter()
+        .map(|swept| &swept.output.bag)
+        .chain(deposit.iter().map(|deposited| &deposited.output.bag))
+    {
+        registry.bags.entry(bag.to_string()).or_default();
+    }
+}
+
+fn register_bundle_ids(
+    mut registry: ResMut<IdRegistry>,
+    mut register: EventReader<RegisterBundleCompleted>,
+    mut mint: EventReader<MintBundleCompleted>,
+    mut burn: EventReader<BurnBundleCompleted>,
+) {
+    for registered in register.iter() {
+        let output = &registered.output;
+        registry.add_asset(
+            output.class_id,
+            output.asset_id,
+            metadata_name(&registered.input.metadata),
+        );
+        registry.bundles.insert(
+            output.bundle_id.to_string(),
+            format!(
+                "{}/{}",
+                u64::from(output.class_id),
+                u64::from(output.asset_id)
+            ),
+        );
+    }
+    for bundle_id in mint
+        .iter()
+        .map(|minted| &minted.output.bundle_id)
+        .chain(burn.iter().map(|burned| &burned.output.bundle_id))
+    {
+        registry.bundles.entry(bundle_id.to_string()).or_default();
+    }
+}
+
+fn register_market_ids(
+    mut registry: ResMut<IdRegistry>,
+    mut create: EventReader<CreateMarketCompleted>,
+    mut create_rate: EventReader<CreateMarketRateCompleted>,
+    mut deposit: EventReader<DepositAssetsCompleted>,
+    mut exchange: EventReader<ExchangeAssetsCompleted>,
+) {
+    for created in create.iter() {
+        registry.add_market(created.output.market_id);
+    }
+    for created in create_rate.iter() {
+        let output = &created.output;
+        registry.add_rate(
+            output.market_id,
+            output.market_rate_id,
+            metadata_name(&created.input.rates.metadata),
+        );
+    }
+    for deposit in deposit.iter() {
+        registry.add_rate(
+            deposit.output.market_id,
+            deposit.output.market_rate_id,
+            None,
+        );
+    }
+    for exchange in exchange.iter() {
+        registry.add_rate(
+            exchange.output.market_id,
+            exchange.output.market_rate_id,
+            None,
+        );
+    }
+}
+
+fn id_label(id: impl std::fmt::Display, name: &str) -> String {
+    if name.is_empty() {
+        id.to_string()
+    } else {
+        format!("{} {}", id, name)
+    }
+}
+
+/// Dropdown of known values next to an ID field; picking one replaces `value`.
+fn known_ids_menu<T: PartialEq + Clone>(
+    ui: &mut egui::Ui,
+    value: &mut T,
+    known: impl Iterator<Item = (T, String)>,
+) {
+    let known: Vec<_> = known.collect();
+    ui.add_enabled_ui(!known.is_empty(), |ui| {
+        ui.menu_button("▾", |ui| {
+            for (id, label) in known {
+                if ui.selectable_label(*value == id, label).clicked() {
+                    *value = id;
+                    ui.close_menu();
+                }
+            }
+        })
+        .response
+        .on_hover_text("Known IDs");
+    });
+}
+
+pub fn class_id_ui(ui: &mut egui::Ui, registry: &IdRegistry, class_id: &mut u64) {
+    ui.horizontal(|ui| {
+        ui.add(egui::DragValue::new::<u64>(class_id).speed(0.1));
+        let known = registry
+            .classes
+            .iter()
+            .map(|(id, name)| (*id, id_label(id, name)));
+        known_ids_menu(ui, class_id, known);
+    });
+}
+
+/// Only offers assets of `class_id`.
+pub fn asset_id_ui(ui: &mut egui::Ui, registry: &IdRegistry, class_id: u64, asset_id: &mut u64) {
+    ui.horizontal(|ui| {
+        ui.add(egui::DragValue::new::<u64>(asset_id).speed(0.1));
+        let known = registry
+            .assets
+            .range((class_id, 0)..=(class_id, u64::MAX))
+            .map(|((_, id), name)| (*id, id_label(id, name)));
+        known_ids_menu(ui, asset_id, known);
+    });
+}
+
+pub fn market_id_ui(ui: &mut egui::Ui, registry: &IdRegistry, market_id: &mut u64) {
+    ui.horizontal(|ui| {
+        ui.add(egui::DragValue::new::<u64>(market_id).speed(1.0));
+        let known = registry
+            .markets
+            .iter()
+            .map(|(id, name)| (*id, id_label(id, name)));
+        known_ids_menu(ui, market_id, known);
+    });
+}
+
+/// Only offers rates of `market_id`.
+pub fn market_rate_id_ui(
+    ui: &mut egui::Ui,
+    registry: &IdRegistry,
+    market_id: u64,
+    market_rate_id: &mut u64,
+) {
+    ui.horizontal(|ui| {
+        ui.add(egui::DragValue::new::<u64>(market_rate_id).speed(1.0));
+        let known = registry
+            .rates
+            .range((market_id, 0)..=(market_id, u64::MAX))
+            .map(|((_, id), name)| (*id, id_label(id, name)));
+        known_ids_menu(ui, market_rate_id, known);
+    });
+}
+
+pub fn bag_account_ui(ui: &mut egui::Ui, registry: &IdRegistry, bag: &mut String) {
+    ui.horizontal(|ui| {
+        ui.text_edit_singleline(bag);
+        let known = registry
+            .bags
+            .iter()
+            .map(|(bag, class_id)| (bag.clone(), format!("{} (class {})", bag, class_id)));
+        known_ids_menu(ui, bag, known);
+    });
+}
+
+pub fn bundle_id_ui(ui: &mut egui::Ui, registry: &IdRegistry, bundle_id: &mut String) {
+    ui.horizontal(|ui| {
+        ui.text_edit_singleline(bundle_id);
+        let known = registry
+            .bundles
+            .iter()
+            .map(|(id, asset)| (id.clone(), id_label(id, asset)));
+        known_ids_menu(ui, bundle_id, known);
+    });
+}
+
+pub struct IdRegistryPlugin;
+
+impl Plugin for IdRegistryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<IdRegistry>()
+            .add_system(register_class_ids)
+            .add_system(register_asset_ids)
+            .add_system(register_bag_ids)
+            .add_system(register_bundle_ids)
+            .add_system(register_market_ids);
+    }
+}
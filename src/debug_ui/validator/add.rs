@@ -1,58 +1,26 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use crossbeam::channel;
+use serde::{Deserialize, Serialize};
 use sugarfunge_api_types::{
     primitives::{Seed, ValidatorId},
-    validator::{AddValidatorInput, AddValidatorOutput},
+    validator::AddValidatorInput,
 };
 
-use crate::{prelude::*, util::request_handler};
+use crate::{
+    api::{AddValidator, ApiEvents},
+    debug_ui::export::{form_input, RequestForm},
+    shortcuts::submit_button,
+};
 
 use super::ValidatorUi;
 
-#[derive(Debug)]
-pub struct AddValidatorRequest {
-    pub input: AddValidatorInput,
-}
-
-impl Request<AddValidatorInput> for AddValidatorRequest {
-    fn endpoint(&self) -> &str {
-        "validator/add_validator"
-    }
-
-    fn input(&self) -> Option<AddValidatorInput> {
-        Some(AddValidatorInput {
-            seed: self.input.seed.clone(),
-            validator_id: self.input.validator_id.clone(),
-        })
-    }
-}
-
-#[derive(Resource)]
-pub struct AddValidatorChannel {
-    pub input_tx: InputSender<AddValidatorRequest>,
-    pub input_rx: InputReceiver<AddValidatorRequest>,
-    pub output_tx: OutputSender<AddValidatorOutput>,
-    pub output_rx: OutputReceiver<AddValidatorOutput>,
-}
-
-impl Default for AddValidatorChannel {
-    fn default() -> Self {
-        let (input_tx, input_rx) = channel::unbounded::<AddValidatorRequest>();
-        let (output_tx, output_rx) = channel::unbounded::<Option<AddValidatorOutput>>();
-        Self {
-            input_tx: InputSender(input_tx),
-            input_rx: InputReceiver(input_rx),
-            output_tx: OutputSender(output_tx),
-            output_rx: OutputReceiver(output_rx),
-        }
-    }
-}
-
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AddValidatorInputData {
+    #[serde(skip)]
     pub seed: Seed,
     pub validator_id: ValidatorId,
+    #[serde(skip)]
     pub loading: bool,
 }
 
@@ -66,6 +34,27 @@ impl Default for AddValidatorInputData {
     }
 }
 
+impl RequestForm for AddValidatorInputData {
+    type Endpoint = AddValidator;
+
+    fn input(&self) -> Result<AddValidatorInput, String> {
+        Ok(AddValidatorInput {
+            seed: self.seed.clone(),
+            validator_id: self.validator_id.clone(),
+        })
+    }
+
+    fn set_input(&mut self, input: AddValidatorInput) -> Result<(), String> {
+        self.seed = input.seed;
+        self.validator_id = input.validator_id;
+        Ok(())
+    }
+
+    fn seed(&mut self) -> Option<&mut String> {
+        Some(&mut *self.seed)
+    }
+}
+
 pub fn add_validator_ui(ui: &mut egui::Ui, validator: &mut ResMut<ValidatorUi>) {
     ui.label("Add Validator");
     ui.separator();
@@ -77,20 +66,11 @@ pub fn add_validator_ui(ui: &mut egui::Ui, validator: &mut ResMut<ValidatorUi>)
         ui.separator();
         ui.add(egui::Spinner::default());
     } else {
-        if ui.button("Add").clicked() {
-            validator
-                .channels
-                .add
-                .input_tx
-                .0
-                .send(AddValidatorRequest {
-                    input: AddValidatorInput {
-                        seed: validator.data.input.add.seed.clone(),
-                        validator_id: validator.data.input.add.validator_id.clone(),
-                    },
-                })
-                .unwrap();
-            validator.data.input.add.loading = true;
+        if submit_button(ui, "Add") {
+            if let Some(input) = form_input(&validator.data.input.add) {
+                validator.channels.add.send(input);
+                validator.data.input.add.loading = true;
+            }
         }
     }
     if let Some(output) = &validator.data.output.add {
@@ -100,19 +80,13 @@ pub fn add_validator_ui(ui: &mut egui::Ui, validator: &mut ResMut<ValidatorUi>)
     }
 }
 
-pub fn handle_add_response(mut validator: ResMut<ValidatorUi>, tokio_runtime: Res<TokioRuntime>) {
-    if let Ok(added_result) = validator.channels.add.output_rx.0.try_recv() {
-        if let Some(added) = added_result {
+pub fn handle_add_response(mut validator: ResMut<ValidatorUi>, mut api: ApiEvents<AddValidator>) {
+    if let Some(result) = validator.channels.add.poll(&mut api) {
+        if let Ok(added) = result {
             validator.data.output.add = Some(added);
         }
         validator.data.input.add.loading = false;
     }
-
-    request_handler::<AddValidatorRequest, AddValidatorInput, AddValidatorOutput>(
-        tokio_runtime.runtime.clone(),
-        validator.channels.add.input_rx.clone(),
-        validator.channels.add.output_tx.clone(),
-    );
 }
 
 pub struct AddValidatorPlugin;
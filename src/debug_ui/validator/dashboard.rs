@@ -0,0 +1,218 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+use sugarfunge_api_types::{
+    primitives::{Seed, ValidatorId},
+    validator::{AddValidatorInput, RemoveValidatorInput},
+};
+
+use crate::shortcuts::submit_button;
+
+use super::ValidatorUi;
+
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ValidatorDashboardInputData {
+    #[serde(skip)]
+    pub seed: Seed,
+    pub validator_id: ValidatorId,
+    /// Added or removed by this dashboard but not yet reflected in the listed
+    /// set; the change only applies once the next session starts.
+    #[serde(skip)]
+    pub pending_additions: Vec<ValidatorId>,
+    #[serde(skip)]
+    pub pending_removals: Vec<ValidatorId>,
+    #[serde(skip)]
+    pub awaiting_add: bool,
+    #[serde(skip)]
+    pub awaiting_remove: bool,
+    #[serde(skip)]
+    pub error: Option<String>,
+}
+
+impl Default for ValidatorDashboardInputData {
+    fn default() -> Self {
+        Self {
+            seed: Seed::from("".to_string()),
+            validator_id: ValidatorId::from("".to_string()),
+            pending_additions: vec![],
+            pending_removals: vec![],
+            awaiting_add: false,
+            awaiting_remove: false,
+            error: None,
+        }
+    }
+}
+
+fn contains(validators: &[ValidatorId], validator_id: &ValidatorId) -> bool {
+    validators
+        .iter()
+        .any(|validator| validator.as_str() == validator_id.as_str())
+}
+
+fn refresh(validator: &mut ResMut<ValidatorUi>) {
+    validator.channels.list.send(());
+    validator.data.input.list.loading = true;
+}
+
+fn add(validator: &mut ResMut<ValidatorUi>, validator_id: ValidatorId) {
+    validator.data.output.add = None;
+    let input = AddValidatorInput {
+        seed: validator.data.input.dashboard.seed.clone(),
+        validator_id,
+    };
+    validator.channels.add.send(input);
+    validator.data.input.add.loading = true;
+    validator.data.input.dashboard.awaiting_add = true;
+}
+
+fn remove(validator: &mut ResMut<ValidatorUi>, validator_id: ValidatorId) {
+    validator.data.output.remove = None;
+    let input = RemoveValidatorInput {
+        seed: validator.data.input.dashboard.seed.clone(),
+        validator_id,
+    };
+    validator.channels.remove.send(input);
+    validator.data.input.remove.loading = true;
+    validator.data.input.dashboard.awaiting_remove = true;
+}
+
+/// Moves finished add/remove requests into the pending lists and drops pending
+/// entries the listed validator set already reflects.
+fn update_pending(validator: &mut ResMut<ValidatorUi>) {
+    if validator.data.input.dashboard.awaiting_add && !validator.data.input.add.loading {
+        validator.data.input.dashboard.awaiting_add = false;
+        match validator.data.output.add.as_ref() {
+            Some(output) => {
+                let validator_id = output.validator_id.clone();
+                validator
+                    .data
+                    .input
+                    .dashboard
+                    .pending_additions
+                    .push(validator_id);
+            }
+            None => validator.data.input.dashboard.error = Some("Add validator failed".to_string()),
+        }
+    }
+    if validator.data.input.dashboard.awaiting_remove && !validator.data.input.remove.loading {
+        validator.data.input.dashboard.awaiting_remove = false;
+        match validator.data.output.remove.as_ref() {
+            Some(output) => {
+                let validator_id = output.validator_id.clone();
+                validator
+                    .data
+                    .input
+                    .dashboard
+                    .pending_removals
+                    .push(validator_id);
+            }
+            None => {
+                validator.data.input.dashboard.error = Some("Remove validator failed".to_string())
+            }
+        }
+    }
+    if let Some(list) = validator.data.output.list.as_ref() {
+        let validators = list.validators.clone();
+        let dashboard = &mut validator.data.input.dashboard;
+        dashboard
+            .pending_additions
+            .retain(|validator_id| !contains(&validators, validator_id));
+        dashboard
+            .pending_removals
+            .retain(|validator_id| contains(&validators, validator_id));
+    }
+}
+
+pub fn validator_dashboard_ui(ui: &mut egui::Ui, validator: &mut ResMut<ValidatorUi>) {
+    update_pending(validator);
+
+    ui.label("Validator Set");
+    ui.separator();
+    ui.label("Seed");
+    ui.text_edit_singleline(&mut *validator.data.input.dashboard.seed);
+    if validator.data.input.list.loading {
+        ui.add(egui::Spinner::default());
+    } else if ui.button("Refresh").clicked() {
+        refresh(validator);
+    }
+    ui.separator();
+
+    let list = validator
+        .data
+        .output
+        .list
+        .as_ref()
+        .map(|list| (list.validators.clone(), list.session_index, list.active_era));
+    let busy = validator.data.input.add.loading || validator.data.input.remove.loading;
+    if let Some((validators, session_index, active_era)) = &list {
+        ui.horizontal(|ui| {
+            ui.label(format!(
+                "Session: {}",
+                session_index.map_or("-".to_string(), |session| session.to_string())
+            ));
+            ui.label(format!(
+                "Era: {}",
+                active_era.map_or("-".to_string(), |era| era.to_string())
+            ));
+            ui.label(format!("Validators: {}", validators.len()));
+        });
+        ui.separator();
+        // The client refuses to remove the last listed validator on every
+        // route; the dashboard also knows which removals are still pending.
+        let remaining = validators.len()
+            - validators
+                .iter()
+                .filter(|validator_id| {
+                    contains(
+                        &validator.data.input.dashboard.pending_removals,
+                        validator_id,
+                    )
+                })
+                .count();
+        egui::Grid::new("validator_dashboard")
+            .striped(true)
+            .show(ui, |ui| {
+                for validator_id in validators.iter() {
+                    let pending_removal = contains(
+                        &validator.data.input.dashboard.pending_removals,
+                        validator_id,
+                    );
+                    ui.label(validator_id.as_str());
+                    if pending_removal {
+                        ui.label("Pending removal");
+                    } else if ui.add_enabled(!busy, egui::Button::new("Remove")).clicked() {
+                        if remaining <= 1 {
+                            validator.data.input.dashboard.error =
+                                Some("Refusing to remove the last remaining validator".to_string());
+                        } else {
+                            validator.data.input.dashboard.error = None;
+                            remove(validator, validator_id.clone());
+                        }
+                    }
+                    ui.end_row();
+                }
+                for validator_id in validator.data.input.dashboard.pending_additions.iter() {
+                    ui.label(validator_id.as_str());
+                    ui.label("Pending addition");
+                    ui.end_row();
+                }
+            });
+    } else {
+        ui.label("Refresh to load the current validator set");
+    }
+
+    ui.separator();
+    ui.label("Validator ID");
+    ui.text_edit_singleline(&mut *validator.data.input.dashboard.validator_id);
+    if busy {
+        ui.add(egui::Spinner::default());
+    } else if submit_button(ui, "Add") {
+        let validator_id = validator.data.input.dashboard.validator_id.clone();
+        validator.data.input.dashboard.error = None;
+        add(validator, validator_id);
+    }
+    if let Some(error) = &validator.data.input.dashboard.error {
+        ui.colored_label(egui::Color32::RED, error);
+    }
+}
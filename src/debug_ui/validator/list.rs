@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::api::{ApiEvents, Validators};
+
+use super::ValidatorUi;
+
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ValidatorsInputData {
+    #[serde(skip)]
+    pub loading: bool,
+}
+
+pub fn handle_validators_response(
+    mut validator: ResMut<ValidatorUi>,
+    mut api: ApiEvents<Validators>,
+) {
+    if let Some(result) = validator.channels.list.poll(&mut api) {
+        if let Ok(validators) = result {
+            validator.data.output.list = Some(validators);
+        }
+        validator.data.input.list.loading = false;
+    }
+}
+
+pub struct ValidatorsPlugin;
+
+impl Plugin for ValidatorsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(handle_validators_response);
+    }
+}
@@ -1,8 +1,18 @@
+use std::sync::Arc;
+
 use bevy::prelude::*;
 use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
 use sugarfunge_api_types::validator::*;
 
+use crate::{
+    api::{AddValidator, ApiCall, RemoveValidator, Validators, ValidatorsOutput},
+    debug_ui::export::RequestExport,
+};
+
 pub mod add;
+pub mod dashboard;
+pub mod list;
 pub mod remove;
 
 #[derive(Resource, Default)]
@@ -12,39 +22,77 @@ pub struct ValidatorUi {
     pub channels: ValidatorChannels,
 }
 
-#[derive(Resource, Debug, Default, Eq, PartialEq)]
+#[derive(Resource, Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
 pub enum ValidatorActions {
     #[default]
+    Dashboard,
     AddValidator,
     RemoveValidator,
 }
 
-#[derive(Resource, Debug, Default, Clone)]
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ValidatorInputData {
     add: add::AddValidatorInputData,
     remove: remove::RemoveValidatorInputData,
+    list: list::ValidatorsInputData,
+    dashboard: dashboard::ValidatorDashboardInputData,
 }
 
 #[derive(Resource, Default, Debug)]
 pub struct ValidatorOutputData {
-    add: Option<AddValidatorOutput>,
-    remove: Option<RemoveValidatorOutput>,
+    add: Option<Arc<AddValidatorOutput>>,
+    remove: Option<Arc<RemoveValidatorOutput>>,
+    list: Option<Arc<ValidatorsOutput>>,
 }
 
 #[derive(Resource, Default)]
 pub struct ValidatorData {
-    input: ValidatorInputData,
+    pub input: ValidatorInputData,
     output: ValidatorOutputData,
 }
 
 #[derive(Resource, Default)]
 pub struct ValidatorChannels {
-    add: add::AddValidatorChannel,
-    remove: remove::RemoveValidatorChannel,
+    add: ApiCall<AddValidator>,
+    remove: ApiCall<RemoveValidator>,
+    list: ApiCall<Validators>,
+}
+
+impl ValidatorUi {
+    /// Restores the selected form's defaults.
+    pub fn reset_form(&mut self) {
+        let input = &mut self.data.input;
+        match self.actions {
+            // Keeps the pending changes so the listed set stays accurate.
+            ValidatorActions::Dashboard => {
+                let defaults = dashboard::ValidatorDashboardInputData::default();
+                input.dashboard.seed = defaults.seed;
+                input.dashboard.validator_id = defaults.validator_id;
+            }
+            ValidatorActions::AddValidator => input.add = Default::default(),
+            ValidatorActions::RemoveValidator => input.remove = Default::default(),
+        }
+    }
+
+    /// The selected form, when it sends a single request.
+    pub fn request_form(&mut self) -> Option<&mut dyn RequestExport> {
+        let input = &mut self.data.input;
+        match self.actions {
+            ValidatorActions::Dashboard => None,
+            ValidatorActions::AddValidator => Some(&mut input.add),
+            ValidatorActions::RemoveValidator => Some(&mut input.remove),
+        }
+    }
 }
 
 pub fn validator_ui(ui: &mut egui::Ui, validator: &mut ResMut<ValidatorUi>) {
     ui.horizontal(|ui| {
+        ui.selectable_value(
+            &mut validator.actions,
+            ValidatorActions::Dashboard,
+            "Dashboard",
+        );
         ui.selectable_value(
             &mut validator.actions,
             ValidatorActions::AddValidator,
@@ -58,6 +106,9 @@ pub fn validator_ui(ui: &mut egui::Ui, validator: &mut ResMut<ValidatorUi>) {
     });
     ui.separator();
     match &validator.actions {
+        ValidatorActions::Dashboard => {
+            dashboard::validator_dashboard_ui(ui, validator);
+        }
         ValidatorActions::AddValidator => {
             add::add_validator_ui(ui, validator);
         }
@@ -73,6 +124,7 @@ impl Plugin for ValidatorPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<ValidatorUi>()
             .add_plugin(add::AddValidatorPlugin)
-            .add_plugin(remove::RemoveValidatorPlugin);
+            .add_plugin(remove::RemoveValidatorPlugin)
+            .add_plugin(list::ValidatorsPlugin);
     }
 }
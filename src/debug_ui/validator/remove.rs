@@ -1,58 +1,26 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
-use crossbeam::channel;
+use serde::{Deserialize, Serialize};
 use sugarfunge_api_types::{
     primitives::{Seed, ValidatorId},
-    validator::{RemoveValidatorInput, RemoveValidatorOutput},
+    validator::RemoveValidatorInput,
 };
 
-use crate::{prelude::*, util::request_handler};
+use crate::{
+    api::{ApiEvents, RemoveValidator},
+    debug_ui::export::{form_input, RequestForm},
+    shortcuts::submit_button,
+};
 
 use super::ValidatorUi;
 
-#[derive(Debug)]
-pub struct RemoveValidatorRequest {
-    pub input: RemoveValidatorInput,
-}
-
-impl Request<RemoveValidatorInput> for RemoveValidatorRequest {
-    fn endpoint(&self) -> &str {
-        "validator/remove_validator"
-    }
-
-    fn input(&self) -> Option<RemoveValidatorInput> {
-        Some(RemoveValidatorInput {
-            seed: self.input.seed.clone(),
-            validator_id: self.input.validator_id.clone(),
-        })
-    }
-}
-
-#[derive(Resource)]
-pub struct RemoveValidatorChannel {
-    pub input_tx: InputSender<RemoveValidatorRequest>,
-    pub input_rx: InputReceiver<RemoveValidatorRequest>,
-    pub output_tx: OutputSender<RemoveValidatorOutput>,
-    pub output_rx: OutputReceiver<RemoveValidatorOutput>,
-}
-
-impl Default for RemoveValidatorChannel {
-    fn default() -> Self {
-        let (input_tx, input_rx) = channel::unbounded::<RemoveValidatorRequest>();
-        let (output_tx, output_rx) = channel::unbounded::<Option<RemoveValidatorOutput>>();
-        Self {
-            input_tx: InputSender(input_tx),
-            input_rx: InputReceiver(input_rx),
-            output_tx: OutputSender(output_tx),
-            output_rx: OutputReceiver(output_rx),
-        }
-    }
-}
-
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RemoveValidatorInputData {
+    #[serde(skip)]
     pub seed: Seed,
     pub validator_id: ValidatorId,
+    #[serde(skip)]
     pub loading: bool,
 }
 
@@ -66,6 +34,27 @@ impl Default for RemoveValidatorInputData {
     }
 }
 
+impl RequestForm for RemoveValidatorInputData {
+    type Endpoint = RemoveValidator;
+
+    fn input(&self) -> Result<RemoveValidatorInput, String> {
+        Ok(RemoveValidatorInput {
+            seed: self.seed.clone(),
+            validator_id: self.validator_id.clone(),
+        })
+    }
+
+    fn set_input(&mut self, input: RemoveValidatorInput) -> Result<(), String> {
+        self.seed = input.seed;
+        self.validator_id = input.validator_id;
+        Ok(())
+    }
+
+    fn seed(&mut self) -> Option<&mut String> {
+        Some(&mut *self.seed)
+    }
+}
+
 pub fn remove_validator_ui(ui: &mut egui::Ui, validator: &mut ResMut<ValidatorUi>) {
     ui.label("Remove Validator");
     ui.separator();
@@ -77,20 +66,11 @@ pub fn remove_validator_ui(ui: &mut egui::Ui, validator: &mut ResMut<ValidatorUi
         ui.separator();
         ui.add(egui::Spinner::default());
     } else {
-        if ui.button("Remove").clicked() {
-            validator
-                .channels
-                .remove
-                .input_tx
-                .0
-                .send(RemoveValidatorRequest {
-                    input: RemoveValidatorInput {
-                        seed: validator.data.input.remove.seed.clone(),
-                        validator_id: validator.data.input.remove.validator_id.clone(),
-                    },
-                })
-                .unwrap();
-            validator.data.input.remove.loading = true;
+        if submit_button(ui, "Remove") {
+            if let Some(input) = form_input(&validator.data.input.remove) {
+                validator.channels.remove.send(input);
+                validator.data.input.remove.loading = true;
+            }
         }
     }
     if let Some(output) = &validator.data.output.remove {
@@ -102,20 +82,14 @@ pub fn remove_validator_ui(ui: &mut egui::Ui, validator: &mut ResMut<ValidatorUi
 
 pub fn handle_remove_response(
     mut validator: ResMut<ValidatorUi>,
-    tokio_runtime: Res<TokioRuntime>,
+    mut api: ApiEvents<RemoveValidator>,
 ) {
-    if let Ok(removed_result) = validator.channels.remove.output_rx.0.try_recv() {
-        if let Some(removed) = removed_result {
+    if let Some(result) = validator.channels.remove.poll(&mut api) {
+        if let Ok(removed) = result {
             validator.data.output.remove = Some(removed);
         }
         validator.data.input.remove.loading = false;
     }
-
-    request_handler::<RemoveValidatorRequest, RemoveValidatorInput, RemoveValidatorOutput>(
-        tokio_runtime.runtime.clone(),
-        validator.channels.remove.input_rx.clone(),
-        validator.channels.remove.output_tx.clone(),
-    );
 }
 
 pub struct RemoveValidatorPlugin;
@@ -0,0 +1,121 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::api::{
+    AssetBalancesCompleted, BurnAssetCompleted, CreateAccountCompleted, CreateMarketCompleted,
+    DepositAssetsCompleted, ExchangeAssetsCompleted, FundAccountCompleted, MintAssetCompleted,
+    SeededAccountCompleted, TransferFromCompleted,
+};
+
+use super::{EconomyEvent, Holding};
+
+/// Completed API calls that create or move assets, from any sender.
+#[derive(SystemParam)]
+pub struct EconomyApiEvents<'w, 's> {
+    create_account: EventReader<'w, 's, CreateAccountCompleted>,
+    seeded_account: EventReader<'w, 's, SeededAccountCompleted>,
+    fund_account: EventReader<'w, 's, FundAccountCompleted>,
+    mint: EventReader<'w, 's, MintAssetCompleted>,
+    burn: EventReader<'w, 's, BurnAssetCompleted>,
+    transfer_from: EventReader<'w, 's, TransferFromCompleted>,
+    balances: EventReader<'w, 's, AssetBalancesCompleted>,
+    create_market: EventReader<'w, 's, CreateMarketCompleted>,
+    deposit: EventReader<'w, 's, DepositAssetsCompleted>,
+    exchange: EventReader<'w, 's, ExchangeAssetsCompleted>,
+}
+
+pub fn api_economy_events(
+    mut api: EconomyApiEvents,
+    mut economy_events: EventWriter<EconomyEvent>,
+) {
+    for created in api.create_account.iter() {
+        economy_events.send(EconomyEvent::Account {
+            account: created.output.account.clone(),
+        });
+    }
+    for seeded in api.seeded_account.iter() {
+        economy_events.send(EconomyEvent::Account {
+            account: seeded.output.account.clone(),
+        });
+    }
+    for funded in api.fund_account.iter() {
+        economy_events.send(EconomyEvent::Account {
+            account: funded.input.to.clone(),
+        });
+    }
+    for mint in api.mint.iter().map(|completed| &completed.output) {
+        economy_events.send(EconomyEvent::Mint {
+            to: mint.to.clone(),
+            class_id: mint.class_id,
+            asset_id: mint.asset_id,
+            amount: u128::from(mint.amount),
+        });
+    }
+    for burn in api.burn.iter().map(|completed| &completed.output) {
+        economy_events.send(EconomyEvent::Burn {
+            from: burn.from.clone(),
+            class_id: burn.class_id,
+            asset_id: burn.asset_id,
+            amount: u128::from(burn.amount),
+        });
+    }
+    for transfer in api.transfer_from.iter().map(|completed| &completed.output) {
+        economy_events.send(EconomyEvent::Transfer {
+            from: transfer.from.clone(),
+            to: transfer.to.clone(),
+            class_id: transfer.class_id,
+            asset_id: transfer.asset_id,
+            amount: u128::from(transfer.amount),
+        });
+    }
+    for balances in api.balances.iter() {
+        // Holdings filtered by class would look like the other classes were emptied.
+        if balances.input.class_id.is_some() {
+            continue;
+        }
+        economy_events.send(EconomyEvent::Balances {
+            account: balances.input.account.clone(),
+            holdings: balances
+                .output
+                .balances
+                .iter()
+                .map(|balance| Holding {
+                    class_id: u64::from(balance.class_id),
+                    asset_id: u64::from(balance.asset_id),
+                    amount: u128::from(balance.amount),
+                })
+                .collect(),
+        });
+    }
+    for created in api.create_market.iter().map(|completed| &completed.output) {
+        economy_events.send(EconomyEvent::Market {
+            market_id: created.market_id,
+            owner: created.who.clone(),
+        });
+    }
+    for deposit in api.deposit.iter().map(|completed| &completed.output) {
+        if deposit.success {
+            economy_events.send(EconomyEvent::Deposit {
+                market_id: deposit.market_id,
+                owner: deposit.who.clone(),
+                balances: deposit.balances.clone(),
+            });
+        }
+    }
+    for exchange in api.exchange.iter().map(|completed| &completed.output) {
+        if exchange.success {
+            economy_events.send(EconomyEvent::Exchange {
+                market_id: exchange.market_id,
+                buyer: exchange.buyer.clone(),
+                balances: exchange.balances.clone(),
+            });
+        }
+    }
+}
+
+pub struct EconomyApiEventsPlugin;
+
+impl Plugin for EconomyApiEventsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(api_economy_events);
+    }
+}
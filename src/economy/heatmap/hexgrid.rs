@@ -0,0 +1,275 @@
+use bevy::{
+    core_pipeline::core_3d::Transparent3d,
+    ecs::{
+        query::QueryItem,
+        system::{lifetimeless::*, SystemParamItem},
+    },
+    pbr::{MeshPipeline, MeshPipelineKey, MeshUniform, SetMeshBindGroup, SetMeshViewBindGroup},
+    prelude::*,
+    render::{
+        extract_component::{ExtractComponent, ExtractComponentPlugin},
+        mesh::{GpuBufferInfo, Indices, MeshVertexBufferLayout},
+        render_asset::RenderAssets,
+        render_phase::{
+            AddRenderCommand, DrawFunctions, PhaseItem, RenderCommand, RenderCommandResult,
+            RenderPhase, SetItemPipeline, TrackedRenderPass,
+        },
+        render_resource::*,
+        renderer::RenderDevice,
+        view::ExtractedView,
+        RenderApp, RenderSet,
+    },
+};
+use bytemuck::{Pod, Zeroable};
+
+/// One hex cell as read by `shaders/hexgrid.wgsl`: position and uniform scale,
+/// plus an RGBA color packed into a `u32` (red in the lowest byte).
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[repr(C)]
+pub struct HexCell {
+    pub pos_scale: [f32; 4],
+    pub color: u32,
+}
+
+impl HexCell {
+    pub fn new(position: Vec3, scale: f32, color: Color) -> Self {
+        Self {
+            pos_scale: [position.x, position.y, position.z, scale],
+            color: color.as_rgba_u32(),
+        }
+    }
+}
+
+#[derive(Component, Clone, Debug, Default, Deref)]
+pub struct HexCells(pub Vec<HexCell>);
+
+impl ExtractComponent for HexCells {
+    type Query = &'static HexCells;
+    type Filter = ();
+    type Out = Self;
+
+    fn extract_component(item: QueryItem<'_, Self::Query>) -> Option<Self> {
+        Some(item.clone())
+    }
+}
+
+/// Unit hexagonal prism, flat side up, used as the instanced cell mesh.
+pub fn hex_prism_mesh(height: f32) -> Mesh {
+    let corners: Vec<Vec3> = (0..6)
+        .map(|i| {
+            let angle = std::f32::consts::FRAC_PI_3 * i as f32;
+            Vec3::new(angle.cos(), 0.0, angle.sin())
+        })
+        .collect();
+    let mut positions: Vec<[f32; 3]> = vec![];
+    let mut normals: Vec<[f32; 3]> = vec![];
+    let mut uvs: Vec<[f32; 2]> = vec![];
+    let mut indices: Vec<u32> = vec![];
+
+    // Top cap
+    let top = positions.len() as u32;
+    positions.push([0.0, height, 0.0]);
+    normals.push([0.0, 1.0, 0.0]);
+    uvs.push([0.5, 0.5]);
+    for corner in corners.iter() {
+        positions.push([corner.x, height, corner.z]);
+        normals.push([0.0, 1.0, 0.0]);
+        uvs.push([corner.x * 0.5 + 0.5, corner.z * 0.5 + 0.5]);
+    }
+    for i in 0..6u32 {
+        indices.extend([top, top + 1 + (i + 1) % 6, top + 1 + i]);
+    }
+
+    // Sides
+    for i in 0..6 {
+        let a = corners[i];
+        let b = corners[(i + 1) % 6];
+        let normal = ((a + b) * 0.5).normalize();
+        let base = positions.len() as u32;
+        for (corner, y, u) in [
+            (a, 0.0, 0.0),
+            (b, 0.0, 1.0),
+            (b, height, 1.0),
+            (a, height, 0.0),
+        ] {
+            positions.push([corner.x, y, corner.z]);
+            normals.push(normal.into());
+            uvs.push([u, y / height]);
+        }
+        indices.extend([base, base + 2, base + 1, base, base + 3, base + 2]);
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.set_indices(Some(Indices::U32(indices)));
+    mesh
+}
+
+#[derive(Component)]
+pub struct HexCellsBuffer {
+    buffer: Buffer,
+    length: usize,
+}
+
+fn prepare_hex_cells_buffers(
+    mut commands: Commands,
+    query: Query<(Entity, &HexCells)>,
+    render_device: Res<RenderDevice>,
+) {
+    for (entity, cells) in query.iter() {
+        let buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("hex cells buffer"),
+            contents: bytemuck::cast_slice(cells.as_slice()),
+            usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+        });
+        commands.entity(entity).insert(HexCellsBuffer {
+            buffer,
+            length: cells.len(),
+        });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn queue_hex_cells(
+    transparent_3d_draw_functions: Res<DrawFunctions<Transparent3d>>,
+    hexgrid_pipeline: Res<HexgridPipeline>,
+    msaa: Res<Msaa>,
+    mut pipelines: ResMut<SpecializedMeshPipelines<HexgridPipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    meshes: Res<RenderAssets<Mesh>>,
+    hexgrids: Query<(Entity, &MeshUniform, &Handle<Mesh>), With<HexCells>>,
+    mut views: Query<(&ExtractedView, &mut RenderPhase<Transparent3d>)>,
+) {
+    let draw_hexgrid = transparent_3d_draw_functions.read().id::<DrawHexgrid>();
+    let msaa_key = MeshPipelineKey::from_msaa_samples(msaa.samples());
+    for (view, mut transparent_phase) in views.iter_mut() {
+        let view_key = msaa_key | MeshPipelineKey::from_hdr(view.hdr);
+        let rangefinder = view.rangefinder3d();
+        for (entity, mesh_uniform, mesh_handle) in hexgrids.iter() {
+            if let Some(mesh) = meshes.get(mesh_handle) {
+                let key =
+                    view_key | MeshPipelineKey::from_primitive_topology(mesh.primitive_topology);
+                let Ok(pipeline) =
+                    pipelines.specialize(&pipeline_cache, &hexgrid_pipeline, key, &mesh.layout)
+                else {
+                    continue;
+                };
+                transparent_phase.add(Transparent3d {
+                    entity,
+                    pipeline,
+                    draw_function: draw_hexgrid,
+                    distance: rangefinder.distance(&mesh_uniform.transform),
+                });
+            }
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct HexgridPipeline {
+    shader: Handle<Shader>,
+    mesh_pipeline: MeshPipeline,
+}
+
+impl FromWorld for HexgridPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let shader = world.resource::<AssetServer>().load("shaders/hexgrid.wgsl");
+        let mesh_pipeline = world.resource::<MeshPipeline>().clone();
+        Self {
+            shader,
+            mesh_pipeline,
+        }
+    }
+}
+
+impl SpecializedMeshPipeline for HexgridPipeline {
+    type Key = MeshPipelineKey;
+
+    fn specialize(
+        &self,
+        key: Self::Key,
+        layout: &MeshVertexBufferLayout,
+    ) -> Result<RenderPipelineDescriptor, SpecializedMeshPipelineError> {
+        let mut descriptor = self.mesh_pipeline.specialize(key, layout)?;
+        descriptor.vertex.shader = self.shader.clone();
+        descriptor.vertex.buffers.push(VertexBufferLayout {
+            array_stride: std::mem::size_of::<HexCell>() as u64,
+            step_mode: VertexStepMode::Instance,
+            attributes: vec![
+                VertexAttribute {
+                    format: VertexFormat::Float32x4,
+                    offset: 0,
+                    shader_location: 3,
+                },
+                VertexAttribute {
+                    format: VertexFormat::Uint32,
+                    offset: VertexFormat::Float32x4.size(),
+                    shader_location: 4,
+                },
+            ],
+        });
+        descriptor.fragment.as_mut().unwrap().shader = self.shader.clone();
+        Ok(descriptor)
+    }
+}
+
+type DrawHexgrid = (
+    SetItemPipeline,
+    SetMeshViewBindGroup<0>,
+    SetMeshBindGroup<1>,
+    DrawHexCells,
+);
+
+pub struct DrawHexCells;
+
+impl<P: PhaseItem> RenderCommand<P> for DrawHexCells {
+    type Param = SRes<RenderAssets<Mesh>>;
+    type ViewWorldQuery = ();
+    type ItemWorldQuery = (Read<Handle<Mesh>>, Read<HexCellsBuffer>);
+
+    #[inline]
+    fn render<'w>(
+        _item: &P,
+        _view: (),
+        (mesh_handle, cells_buffer): (&'w Handle<Mesh>, &'w HexCellsBuffer),
+        meshes: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(gpu_mesh) = meshes.into_inner().get(mesh_handle) else {
+            return RenderCommandResult::Failure;
+        };
+        pass.set_vertex_buffer(0, gpu_mesh.vertex_buffer.slice(..));
+        pass.set_vertex_buffer(1, cells_buffer.buffer.slice(..));
+        match &gpu_mesh.buffer_info {
+            GpuBufferInfo::Indexed {
+                buffer,
+                index_format,
+                count,
+            } => {
+                pass.set_index_buffer(buffer.slice(..), 0, *index_format);
+                pass.draw_indexed(0..*count, 0, 0..cells_buffer.length as u32);
+            }
+            GpuBufferInfo::NonIndexed { vertex_count } => {
+                pass.draw(0..*vertex_count, 0..cells_buffer.length as u32);
+            }
+        }
+        RenderCommandResult::Success
+    }
+}
+
+/// Instanced renderer for `HexCells` using `shaders/hexgrid.wgsl`.
+pub struct HexgridPlugin;
+
+impl Plugin for HexgridPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(ExtractComponentPlugin::<HexCells>::default());
+        app.sub_app_mut(RenderApp)
+            .add_render_command::<Transparent3d, DrawHexgrid>()
+            .init_resource::<HexgridPipeline>()
+            .init_resource::<SpecializedMeshPipelines<HexgridPipeline>>()
+            .add_system(queue_hex_cells.in_set(RenderSet::Queue))
+            .add_system(prepare_hex_cells_buffers.in_set(RenderSet::Prepare));
+    }
+}
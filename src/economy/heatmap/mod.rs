@@ -0,0 +1,319 @@
+use std::collections::{BTreeMap, HashMap};
+
+use bevy::{prelude::*, render::view::NoFrustumCulling};
+use bevy_egui::{egui, EguiContexts};
+use sugarfunge_api_types::primitives::{Account, ClassId};
+
+use super::{EconomyEvent, Holding};
+
+pub mod hexgrid;
+pub mod voxels;
+
+const HEATMAP_ORIGIN: Vec3 = Vec3::new(0.0, 0.0, -9.0);
+const HEX_CELL_SIZE: f32 = 0.5;
+const VOXEL_SIZE: f32 = 0.3;
+const VOXEL_GAP: f32 = 0.05;
+const VOXEL_LEVELS: usize = 10;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HeatmapMode {
+    #[default]
+    Hexgrid,
+    Voxels,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HeatmapMetric {
+    #[default]
+    Supply,
+    Holders,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AssetStats {
+    pub supply: u128,
+    pub holders: usize,
+}
+
+#[derive(Resource)]
+pub struct EconomyHeatmap {
+    pub enabled: bool,
+    pub class_id: ClassId,
+    pub mode: HeatmapMode,
+    pub metric: HeatmapMetric,
+    pub account: Account,
+    /// Latest `asset/balances` result per account, as seen in `EconomyEvent`s.
+    pub balances: HashMap<String, Vec<Holding>>,
+    pub dirty: bool,
+    pub entity: Option<Entity>,
+    pub hex_mesh: Option<Handle<Mesh>>,
+}
+
+impl Default for EconomyHeatmap {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            class_id: ClassId::from(0),
+            mode: HeatmapMode::default(),
+            metric: HeatmapMetric::default(),
+            account: Account::from("".to_string()),
+            balances: HashMap::new(),
+            dirty: false,
+            entity: None,
+            hex_mesh: None,
+        }
+    }
+}
+
+impl EconomyHeatmap {
+    /// Supply and holder count for every asset of the selected class, summed
+    /// over all accounts with known balances.
+    pub fn asset_stats(&self) -> BTreeMap<u64, AssetStats> {
+        let class_id = u64::from(self.class_id);
+        let mut stats: BTreeMap<u64, AssetStats> = BTreeMap::new();
+        for holdings in self.balances.values() {
+            for holding in holdings
+                .iter()
+                .filter(|holding| holding.class_id == class_id && holding.amount > 0)
+            {
+                let asset = stats.entry(holding.asset_id).or_default();
+                asset.supply += holding.amount;
+                asset.holders += 1;
+            }
+        }
+        stats
+    }
+
+    fn metric_value(&self, stats: &AssetStats) -> f32 {
+        match self.metric {
+            HeatmapMetric::Supply => stats.supply as f32,
+            HeatmapMetric::Holders => stats.holders as f32,
+        }
+    }
+}
+
+fn heat_color(heat: f32) -> Color {
+    Color::hsl(240.0 * (1.0 - heat), 0.9, 0.5)
+}
+
+/// Axial coordinates of a hex spiral, starting at the center.
+fn hex_spiral(count: usize) -> Vec<(i32, i32)> {
+    const DIRECTIONS: [(i32, i32); 6] = [(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)];
+    let mut cells = vec![(0, 0)];
+    let mut ring = 1;
+    while cells.len() < count {
+        let (mut q, mut r) = (DIRECTIONS[4].0 * ring, DIRECTIONS[4].1 * ring);
+        for direction in DIRECTIONS {
+            for _ in 0..ring {
+                cells.push((q, r));
+                q += direction.0;
+                r += direction.1;
+            }
+        }
+        ring += 1;
+    }
+    cells.truncate(count);
+    cells
+}
+
+fn hex_position(q: i32, r: i32) -> Vec3 {
+    let size = HEX_CELL_SIZE * 1.1;
+    Vec3::new(
+        size * 1.5 * q as f32,
+        0.0,
+        size * 3f32.sqrt() * (r as f32 + q as f32 * 0.5),
+    )
+}
+
+pub fn collect_heatmap_balances(
+    mut heatmap: ResMut<EconomyHeatmap>,
+    mut events: EventReader<EconomyEvent>,
+) {
+    for event in events.iter() {
+        if let EconomyEvent::Balances { account, holdings } = event {
+            heatmap
+                .balances
+                .insert(account.to_string(), holdings.clone());
+            heatmap.dirty = true;
+        }
+    }
+}
+
+pub fn heatmap_ui(
+    mut egui_contexts: EguiContexts,
+    mut heatmap: ResMut<EconomyHeatmap>,
+    mut economy_events: EventWriter<EconomyEvent>,
+) {
+    egui::Window::new("Economy Heatmap")
+        .default_open(false)
+        .show(egui_contexts.ctx_mut(), |ui| {
+            let enabled = heatmap.enabled;
+            let class_id = u64::from(heatmap.class_id);
+            let mode = heatmap.mode;
+            let metric = heatmap.metric;
+
+            ui.checkbox(&mut heatmap.enabled, "Show Heatmap");
+            ui.label("Class ID");
+            ui.add(egui::DragValue::new::<u64>(&mut *heatmap.class_id).speed(0.1));
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut heatmap.mode, HeatmapMode::Hexgrid, "Hex Grid");
+                ui.radio_value(&mut heatmap.mode, HeatmapMode::Voxels, "Voxels");
+            });
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut heatmap.metric, HeatmapMetric::Supply, "Total Supply");
+                ui.radio_value(&mut heatmap.metric, HeatmapMetric::Holders, "Holders");
+            });
+            ui.separator();
+            ui.label("Watch Account");
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(&mut *heatmap.account);
+                if ui.button("Add").clicked() && !heatmap.account.is_empty() {
+                    economy_events.send(EconomyEvent::Account {
+                        account: heatmap.account.clone(),
+                    });
+                    heatmap.account = Account::from("".to_string());
+                }
+            });
+            ui.label(format!(
+                "Accounts with balances: {}",
+                heatmap.balances.len()
+            ));
+            ui.separator();
+            egui::Grid::new("economy_heatmap_assets")
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Asset ID");
+                    ui.label("Supply");
+                    ui.label("Holders");
+                    ui.end_row();
+                    for (asset_id, stats) in heatmap.asset_stats() {
+                        ui.label(asset_id.to_string());
+                        ui.label(stats.supply.to_string());
+                        ui.label(stats.holders.to_string());
+                        ui.end_row();
+                    }
+                });
+
+            if enabled != heatmap.enabled
+                || class_id != u64::from(heatmap.class_id)
+                || mode != heatmap.mode
+                || metric != heatmap.metric
+            {
+                heatmap.dirty = true;
+            }
+        });
+}
+
+pub fn update_heatmap(
+    mut commands: Commands,
+    mut heatmap: ResMut<EconomyHeatmap>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut voxels_materials: ResMut<Assets<voxels::VoxelsMaterial>>,
+) {
+    if !heatmap.dirty {
+        return;
+    }
+    heatmap.dirty = false;
+    if let Some(entity) = heatmap.entity.take() {
+        commands.entity(entity).despawn_recursive();
+    }
+    if !heatmap.enabled {
+        return;
+    }
+
+    let stats = heatmap.asset_stats();
+    let max = stats
+        .values()
+        .map(|stats| heatmap.metric_value(stats))
+        .fold(0.0, f32::max);
+    // Log scale so a single whale asset doesn't flatten the rest of the map.
+    let heats: Vec<f32> = stats
+        .values()
+        .map(|stats| {
+            if max > 0.0 {
+                (1.0 + heatmap.metric_value(stats)).ln() / (1.0 + max).ln()
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    let name = Name::new(format!("Heatmap: Class {}", u64::from(heatmap.class_id)));
+
+    let entity = match heatmap.mode {
+        HeatmapMode::Hexgrid => {
+            let hex_mesh = heatmap
+                .hex_mesh
+                .get_or_insert_with(|| meshes.add(hexgrid::hex_prism_mesh(1.0)))
+                .clone();
+            let cells = hex_spiral(heats.len())
+                .into_iter()
+                .zip(heats.iter())
+                .map(|((q, r), heat)| {
+                    hexgrid::HexCell::new(
+                        hex_position(q, r),
+                        HEX_CELL_SIZE * (0.3 + 0.7 * heat),
+                        heat_color(*heat),
+                    )
+                })
+                .collect();
+            commands
+                .spawn((
+                    hex_mesh,
+                    SpatialBundle::from_transform(Transform::from_translation(HEATMAP_ORIGIN)),
+                    hexgrid::HexCells(cells),
+                    NoFrustumCulling,
+                ))
+                .insert(name)
+                .id()
+        }
+        HeatmapMode::Voxels => {
+            let columns = (heats.len() as f32).sqrt().ceil().max(1.0) as usize;
+            let pitch = VOXEL_SIZE + VOXEL_GAP;
+            let mut voxels = vec![];
+            for (i, heat) in heats.iter().enumerate() {
+                let x = (i % columns) as f32 * pitch;
+                let z = (i / columns) as f32 * pitch;
+                let levels = ((heat * VOXEL_LEVELS as f32).ceil() as usize).max(1);
+                for level in 0..levels {
+                    voxels.push(voxels::Voxel {
+                        position: Vec3::new(x, VOXEL_SIZE * 0.5 + level as f32 * pitch, z),
+                        size: VOXEL_SIZE,
+                        color: heat_color(level as f32 / (VOXEL_LEVELS - 1) as f32),
+                    });
+                }
+            }
+            let offset = Vec3::new(
+                columns as f32 * pitch * 0.5,
+                0.0,
+                columns as f32 * pitch * 0.5,
+            );
+            commands
+                .spawn(MaterialMeshBundle {
+                    mesh: meshes.add(voxels::voxels_mesh(&voxels)),
+                    material: voxels_materials.add(voxels::VoxelsMaterial::default()),
+                    transform: Transform::from_translation(HEATMAP_ORIGIN - offset),
+                    ..Default::default()
+                })
+                .insert(name)
+                .id()
+        }
+    };
+    heatmap.entity = Some(entity);
+}
+
+pub struct EconomyHeatmapPlugin;
+
+impl Plugin for EconomyHeatmapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EconomyHeatmap>()
+            .add_plugin(MaterialPlugin::<voxels::VoxelsMaterial>::default())
+            .add_plugin(hexgrid::HexgridPlugin)
+            .add_system(collect_heatmap_balances)
+            .add_system(heatmap_ui)
+            .add_system(
+                update_heatmap
+                    .after(collect_heatmap_balances)
+                    .after(heatmap_ui),
+            );
+    }
+}
@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+use sugarfunge_api_types::{
+    market::RateBalance,
+    primitives::{Account, AssetId, ClassId, MarketId},
+};
+
+pub mod api_events;
+pub mod heatmap;
+pub mod picking;
+pub mod token_gate;
+pub mod viz;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Holding {
+    pub class_id: u64,
+    pub asset_id: u64,
+    pub amount: u128,
+}
+
+/// Chain activity seen by the app. Sent by `api_events::api_economy_events`
+/// for every completed API call that creates or moves assets, whoever sent it;
+/// the token gate, heatmap and mission windows only send `Account` to start
+/// polling an account's balances.
+#[derive(Debug)]
+pub enum EconomyEvent {
+    Account {
+        account: Account,
+    },
+    Balances {
+        account: Account,
+        holdings: Vec<Holding>,
+    },
+    Transfer {
+        from: Account,
+        to: Account,
+        class_id: ClassId,
+        asset_id: AssetId,
+        amount: u128,
+    },
+    Mint {
+        to: Account,
+        class_id: ClassId,
+        asset_id: AssetId,
+        amount: u128,
+    },
+    Burn {
+        from: Account,
+        class_id: ClassId,
+        asset_id: AssetId,
+        amount: u128,
+    },
+    Market {
+        market_id: MarketId,
+        owner: Account,
+    },
+    Deposit {
+        market_id: MarketId,
+        owner: Account,
+        balances: Vec<RateBalance>,
+    },
+    Exchange {
+        market_id: MarketId,
+        buyer: Account,
+        balances: Vec<RateBalance>,
+    },
+}
+
+pub struct EconomyPlugin;
+
+impl Plugin for EconomyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<EconomyEvent>()
+            .add_plugin(api_events::EconomyApiEventsPlugin)
+            .add_plugin(viz::EconomyVizPlugin)
+            .add_plugin(picking::EconomyPickingPlugin)
+            .add_plugin(heatmap::EconomyHeatmapPlugin)
+            .add_plugin(token_gate::TokenGatePlugin);
+    }
+}
@@ -0,0 +1,247 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_egui::{egui, EguiContexts};
+use simula_camera::orbitcam::OrbitCamera;
+use simula_viz::lines::Lines;
+use sugarfunge_api_types::primitives::{AssetId, ClassId};
+
+use crate::debug_ui::{
+    asset::{balances::show_asset_balances, info::show_asset_info, AssetUi},
+    market::{show_market, MarketUi},
+    DebugUiActions,
+};
+
+use super::{
+    token_gate::TokenGateState,
+    viz::{EconomyAccount, EconomyFlowLines, EconomyHolding, EconomyMarket},
+};
+
+/// Presses that move the cursor further than this are camera drags, not clicks.
+const CLICK_TOLERANCE: f32 = 4.0;
+const FOCUS_SPEED: f32 = 6.0;
+const TOOLTIP_HOLDINGS: usize = 5;
+
+/// Local-space box used for ray picking, centered on the entity's origin.
+#[derive(Component, Debug, Clone)]
+pub struct EconomyPickable {
+    pub half_extents: Vec3,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct EconomyPicking {
+    pub hovered: Option<Entity>,
+    pub selected: Option<Entity>,
+    pub press_position: Option<Vec2>,
+    pub focus: Option<Vec3>,
+}
+
+fn ray_box_distance(ray: Ray, transform: &GlobalTransform, half_extents: Vec3) -> Option<f32> {
+    let world_to_local = transform.compute_matrix().inverse();
+    let origin = world_to_local.transform_point3(ray.origin);
+    let direction = world_to_local.transform_vector3(ray.direction);
+    let mut near = f32::NEG_INFINITY;
+    let mut far = f32::INFINITY;
+    for axis in 0..3 {
+        if direction[axis].abs() < f32::EPSILON {
+            if origin[axis].abs() > half_extents[axis] {
+                return None;
+            }
+            continue;
+        }
+        let a = (-half_extents[axis] - origin[axis]) / direction[axis];
+        let b = (half_extents[axis] - origin[axis]) / direction[axis];
+        near = near.max(a.min(b));
+        far = far.min(a.max(b));
+    }
+    if near > far || far < 0.0 {
+        return None;
+    }
+    // `t` is in local units; convert back to a world distance for sorting.
+    let hit = transform
+        .compute_matrix()
+        .transform_point3(origin + direction * near.max(0.0));
+    Some(hit.distance(ray.origin))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn pick_economy_entities(
+    mut egui_contexts: EguiContexts,
+    mut picking: ResMut<EconomyPicking>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<OrbitCamera>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    pickables: Query<(
+        Entity,
+        &GlobalTransform,
+        &EconomyPickable,
+        Option<&TokenGateState>,
+    )>,
+    accounts: Query<&EconomyAccount>,
+    markets: Query<&EconomyMarket>,
+    holdings: Query<&EconomyHolding>,
+    mut debug_actions: ResMut<DebugUiActions>,
+    mut asset: ResMut<AssetUi>,
+    mut market: ResMut<MarketUi>,
+) {
+    let ctx = egui_contexts.ctx_mut();
+    let (Ok(window), Ok((camera, camera_transform))) = (windows.get_single(), cameras.get_single())
+    else {
+        return;
+    };
+    let Some(cursor) = window.cursor_position() else {
+        picking.hovered = None;
+        return;
+    };
+    if ctx.wants_pointer_input() {
+        picking.hovered = None;
+        picking.press_position = None;
+        return;
+    }
+
+    picking.hovered = camera
+        .viewport_to_world(camera_transform, cursor)
+        .and_then(|ray| {
+            pickables
+                .iter()
+                // Locked token-gated content can't be picked.
+                .filter(|(_, _, _, gate)| gate.map_or(true, |gate| gate.unlocked))
+                .filter_map(|(entity, transform, pickable, _)| {
+                    ray_box_distance(ray, transform, pickable.half_extents)
+                        .map(|distance| (entity, distance))
+                })
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(entity, _)| entity)
+        });
+
+    if mouse_buttons.just_pressed(MouseButton::Left) {
+        picking.press_position = Some(cursor);
+    }
+    if !mouse_buttons.just_released(MouseButton::Left) {
+        return;
+    }
+    let clicked = picking
+        .press_position
+        .take()
+        .map_or(false, |press| press.distance(cursor) < CLICK_TOLERANCE);
+    let Some(entity) = picking.hovered.filter(|_| clicked) else {
+        return;
+    };
+    picking.selected = Some(entity);
+    picking.focus = pickables
+        .get(entity)
+        .ok()
+        .map(|(_, transform, _, _)| transform.translation());
+
+    if let Ok(account) = accounts.get(entity) {
+        *debug_actions = DebugUiActions::Asset;
+        show_asset_balances(&mut asset, account.account.clone());
+    } else if let Ok(holding) = holdings.get(entity) {
+        *debug_actions = DebugUiActions::Asset;
+        show_asset_info(
+            &mut asset,
+            ClassId::from(holding.holding.class_id),
+            AssetId::from(holding.holding.asset_id),
+        );
+    } else if let Ok(economy_market) = markets.get(entity) {
+        *debug_actions = DebugUiActions::Market;
+        show_market(&mut market, economy_market.market_id);
+    }
+}
+
+pub fn economy_tooltip(
+    mut egui_contexts: EguiContexts,
+    picking: Res<EconomyPicking>,
+    accounts: Query<&EconomyAccount>,
+    markets: Query<&EconomyMarket>,
+    holdings: Query<&EconomyHolding>,
+) {
+    let Some(entity) = picking.hovered else {
+        return;
+    };
+    let ctx = egui_contexts.ctx_mut();
+    egui::show_tooltip_at_pointer(ctx, egui::Id::new("economy_tooltip"), |ui| {
+        if let Ok(account) = accounts.get(entity) {
+            ui.label(format!("Account {}", account.account.as_str()));
+            let mut account_holdings: Vec<&EconomyHolding> = holdings
+                .iter()
+                .filter(|holding| holding.account.as_str() == account.account.as_str())
+                .collect();
+            account_holdings.sort_by_key(|holding| std::cmp::Reverse(holding.holding.amount));
+            if account_holdings.is_empty() {
+                ui.label("No holdings");
+            }
+            for holding in account_holdings.iter().take(TOOLTIP_HOLDINGS) {
+                ui.label(format!(
+                    "{}/{}: {}",
+                    holding.holding.class_id, holding.holding.asset_id, holding.holding.amount
+                ));
+            }
+            if account_holdings.len() > TOOLTIP_HOLDINGS {
+                ui.label(format!(
+                    "… {} more",
+                    account_holdings.len() - TOOLTIP_HOLDINGS
+                ));
+            }
+        } else if let Ok(holding) = holdings.get(entity) {
+            ui.label(format!(
+                "Asset {}/{}",
+                holding.holding.class_id, holding.holding.asset_id
+            ));
+            ui.label(format!("Held by {}", holding.account.as_str()));
+            ui.label(format!("Amount: {}", holding.holding.amount));
+        } else if let Ok(market) = markets.get(entity) {
+            ui.label(format!("Market {}", u64::from(market.market_id)));
+        }
+    });
+}
+
+pub fn focus_economy_selection(
+    time: Res<Time>,
+    mut picking: ResMut<EconomyPicking>,
+    mut cameras: Query<&mut OrbitCamera>,
+    selections: Query<(&GlobalTransform, &EconomyPickable)>,
+    mut lines: Query<&mut Lines, With<EconomyFlowLines>>,
+) {
+    if let Some(focus) = picking.focus {
+        for mut camera in cameras.iter_mut() {
+            let t = (time.delta_seconds() * FOCUS_SPEED).min(1.0);
+            camera.center = camera.center.lerp(focus, t);
+            if camera.center.distance(focus) < 0.01 {
+                camera.center = focus;
+                picking.focus = None;
+            }
+        }
+    }
+
+    // Outline the selection with a ring around it.
+    let (Some(selected), Ok(mut lines)) = (picking.selected, lines.get_single_mut()) else {
+        return;
+    };
+    let Ok((transform, pickable)) = selections.get(selected) else {
+        return;
+    };
+    let center = transform.translation();
+    let scale = transform.compute_transform().scale;
+    let radius = (pickable.half_extents * scale).max_element() * 1.5;
+    let steps = 24;
+    for step in 0..steps {
+        let a = step as f32 / steps as f32 * std::f32::consts::TAU;
+        let b = (step + 1) as f32 / steps as f32 * std::f32::consts::TAU;
+        lines.line_colored(
+            center + Vec3::new(a.cos(), 0.0, a.sin()) * radius,
+            center + Vec3::new(b.cos(), 0.0, b.sin()) * radius,
+            0.0,
+            Color::WHITE,
+        );
+    }
+}
+
+pub struct EconomyPickingPlugin;
+
+impl Plugin for EconomyPickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EconomyPicking>()
+            .add_system(pick_economy_entities)
+            .add_system(economy_tooltip.after(pick_economy_entities))
+            .add_system(focus_economy_selection);
+    }
+}
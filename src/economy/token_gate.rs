@@ -0,0 +1,334 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use crossbeam::channel::{Receiver, TryRecvError};
+use sugarfunge_api_types::{
+    asset::{AssetBalanceInput, AssetBalanceOutput},
+    market::AmountOp,
+    primitives::{Account, AssetId, Balance, ClassId},
+};
+
+use crate::{
+    api::{send_raw_request, AssetBalance, RawResponse},
+    behavior::actions::amount_op_holds,
+};
+
+use super::{picking::EconomyPickable, EconomyEvent};
+
+/// Shows the entity only while the active account's balance of the asset
+/// satisfies `op amount`, like a `RateAction::Has` rate.
+#[derive(Component, Debug, Clone)]
+pub struct TokenGate {
+    pub class_id: ClassId,
+    pub asset_id: AssetId,
+    pub op: AmountOp,
+    pub amount: Balance,
+}
+
+/// Keeps a gated entity visible and only toggles `TokenGateState`, for content
+/// that should look locked rather than disappear.
+#[derive(Component, Debug, Clone, Default)]
+pub struct TokenGateLock;
+
+/// Result of the last gate evaluation, inserted by `evaluate_token_gates`.
+#[derive(Component, Debug, Clone, Default, PartialEq, Eq)]
+pub struct TokenGateState {
+    pub unlocked: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct CachedBalance {
+    /// `None` until the first `asset/balance` result arrives.
+    pub amount: Option<u128>,
+    pub error: Option<String>,
+    pending: Option<Receiver<RawResponse>>,
+    stale: bool,
+}
+
+#[derive(Resource)]
+pub struct TokenGates {
+    /// Account the gates are evaluated against.
+    pub account: Account,
+    pub input_account: Account,
+    /// `asset/balance` results per `(class_id, asset_id)` for `account`.
+    pub balances: BTreeMap<(u64, u64), CachedBalance>,
+    pub spawn: TokenGate,
+    pub spawn_amount: u64,
+    pub spawn_lock: bool,
+}
+
+impl Default for TokenGates {
+    fn default() -> Self {
+        Self {
+            account: Account::from("".to_string()),
+            input_account: Account::from("".to_string()),
+            balances: BTreeMap::new(),
+            spawn: TokenGate {
+                class_id: ClassId::from(0),
+                asset_id: AssetId::from(0),
+                op: AmountOp::GreaterEqualThan,
+                amount: Balance::from(1),
+            },
+            spawn_amount: 1,
+            spawn_lock: false,
+        }
+    }
+}
+
+impl TokenGates {
+    pub fn set_account(&mut self, account: Account) {
+        self.account = account;
+        self.balances.clear();
+    }
+
+    /// Re-requests every cached balance, e.g. after the account traded.
+    pub fn invalidate(&mut self) {
+        for balance in self.balances.values_mut() {
+            balance.stale = true;
+        }
+    }
+
+    fn touches(&self, account: &Account) -> bool {
+        account.as_str() == self.account.as_str()
+    }
+}
+
+pub fn refresh_token_gate_balances(
+    mut commands: Commands,
+    mut gates: ResMut<TokenGates>,
+    mut economy_events: EventReader<EconomyEvent>,
+    gated: Query<&TokenGate>,
+) {
+    for event in economy_events.iter() {
+        match event {
+            EconomyEvent::Balances { account, holdings } if gates.touches(account) => {
+                for ((class_id, asset_id), balance) in gates.balances.iter_mut() {
+                    balance.amount = Some(
+                        holdings
+                            .iter()
+                            .filter(|holding| {
+                                holding.class_id == *class_id && holding.asset_id == *asset_id
+                            })
+                            .map(|holding| holding.amount)
+                            .sum(),
+                    );
+                    balance.error = None;
+                }
+            }
+            EconomyEvent::Transfer { from, to, .. } if gates.touches(from) || gates.touches(to) => {
+                gates.invalidate()
+            }
+            EconomyEvent::Mint { to: account, .. }
+            | EconomyEvent::Burn { from: account, .. }
+            | EconomyEvent::Deposit { owner: account, .. }
+            | EconomyEvent::Exchange { buyer: account, .. }
+                if gates.touches(account) =>
+            {
+                gates.invalidate()
+            }
+            _ => {}
+        }
+    }
+
+    if gates.account.is_empty() {
+        return;
+    }
+    for gate in gated.iter() {
+        gates
+            .balances
+            .entry((u64::from(gate.class_id), u64::from(gate.asset_id)))
+            .or_insert_with(|| CachedBalance {
+                stale: true,
+                ..Default::default()
+            });
+    }
+
+    let account = gates.account.clone();
+    for ((class_id, asset_id), balance) in gates.balances.iter_mut() {
+        if let Some(pending) = &balance.pending {
+            let response = match pending.try_recv() {
+                Ok(response) => response,
+                Err(TryRecvError::Empty) => continue,
+                Err(TryRecvError::Disconnected) => Err("Request dropped".to_string()),
+            };
+            balance.pending = None;
+            match response.and_then(|value| {
+                serde_json::from_value::<AssetBalanceOutput>(value).map_err(|err| err.to_string())
+            }) {
+                Ok(output) => {
+                    balance.amount = Some(u128::from(output.amount));
+                    balance.error = None;
+                }
+                Err(err) => balance.error = Some(err),
+            }
+        }
+        if balance.stale && balance.pending.is_none() {
+            balance.stale = false;
+            balance.pending = Some(send_raw_request::<AssetBalance>(
+                &mut commands,
+                AssetBalanceInput {
+                    account: account.clone(),
+                    class_id: ClassId::from(*class_id),
+                    asset_id: AssetId::from(*asset_id),
+                },
+            ));
+        }
+    }
+}
+
+type GatedQuery<'a> = (
+    Entity,
+    &'a TokenGate,
+    Option<&'a TokenGateState>,
+    Option<&'a mut Visibility>,
+    Option<&'a TokenGateLock>,
+);
+
+pub fn evaluate_token_gates(
+    mut commands: Commands,
+    gates: Res<TokenGates>,
+    mut gated: Query<GatedQuery>,
+) {
+    for (entity, gate, state, visibility, lock) in gated.iter_mut() {
+        let unlocked = gates
+            .balances
+            .get(&(u64::from(gate.class_id), u64::from(gate.asset_id)))
+            .and_then(|balance| balance.amount)
+            .map_or(false, |amount| {
+                amount_op_holds(&gate.op, amount, u128::from(gate.amount))
+            });
+        if state.map(|state| state.unlocked) != Some(unlocked) {
+            commands.entity(entity).insert(TokenGateState { unlocked });
+        }
+        if let (Some(mut visibility), None) = (visibility, lock) {
+            let target = if unlocked {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+            if *visibility != target {
+                *visibility = target;
+            }
+        }
+    }
+}
+
+fn spawn_gated_cube(commands: &mut Commands, asset_server: &AssetServer, gates: &TokenGates) {
+    let mut entity = commands.spawn(SceneBundle {
+        scene: asset_server.load("models/cube/cube.gltf#Scene0"),
+        transform: Transform::from_xyz(0.0, 1.0, 6.0).with_scale(Vec3::splat(0.5)),
+        ..Default::default()
+    });
+    entity
+        .insert(gates.spawn.clone())
+        .insert(EconomyPickable {
+            half_extents: Vec3::ONE,
+        })
+        .insert(Name::new(format!(
+            "Gated: {}/{}",
+            u64::from(gates.spawn.class_id),
+            u64::from(gates.spawn.asset_id)
+        )));
+    if gates.spawn_lock {
+        entity.insert(TokenGateLock);
+    }
+}
+
+pub fn token_gates_ui(
+    mut commands: Commands,
+    mut egui_contexts: EguiContexts,
+    mut gates: ResMut<TokenGates>,
+    asset_server: Res<AssetServer>,
+    gated: Query<(&Name, &TokenGateState), With<TokenGate>>,
+    mut economy_events: EventWriter<EconomyEvent>,
+) {
+    egui::Window::new("Token Gates")
+        .default_open(false)
+        .show(egui_contexts.ctx_mut(), |ui| {
+            ui.label(format!("Active Account: {}", gates.account.as_str()));
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(&mut *gates.input_account);
+                if ui.button("Set Active").clicked() {
+                    let account = gates.input_account.clone();
+                    gates.set_account(account.clone());
+                    // Puts the account in the balance polling, which keeps the cache current.
+                    economy_events.send(EconomyEvent::Account { account });
+                }
+                if ui.button("Re-check").clicked() {
+                    gates.invalidate();
+                }
+            });
+            ui.separator();
+            egui::Grid::new("token_gate_balances")
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Class/Asset");
+                    ui.label("Balance");
+                    ui.end_row();
+                    for ((class_id, asset_id), balance) in gates.balances.iter() {
+                        ui.label(format!("{}/{}", class_id, asset_id));
+                        match (&balance.error, balance.amount) {
+                            (Some(err), _) => ui.colored_label(egui::Color32::RED, err),
+                            (None, Some(amount)) => ui.label(amount.to_string()),
+                            (None, None) => ui.add(egui::Spinner::default()),
+                        };
+                        ui.end_row();
+                    }
+                });
+            ui.separator();
+            for (name, state) in gated.iter() {
+                let (color, label) = if state.unlocked {
+                    (egui::Color32::GREEN, "Unlocked")
+                } else {
+                    (egui::Color32::RED, "Locked")
+                };
+                ui.horizontal(|ui| {
+                    ui.label(name.as_str());
+                    ui.colored_label(color, label);
+                });
+            }
+            egui::CollapsingHeader::new("Spawn Gated Cube").show(ui, |ui| {
+                ui.label("Class ID");
+                ui.add(egui::DragValue::new::<u64>(&mut *gates.spawn.class_id).speed(0.1));
+                ui.label("Asset ID");
+                ui.add(egui::DragValue::new::<u64>(&mut *gates.spawn.asset_id).speed(0.1));
+                ui.label("Amount Option");
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut gates.spawn.op, AmountOp::Equal, "Equal");
+                    ui.selectable_value(&mut gates.spawn.op, AmountOp::LessThan, "Less Than");
+                    ui.selectable_value(
+                        &mut gates.spawn.op,
+                        AmountOp::LessEqualThan,
+                        "Less Equal Than",
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut gates.spawn.op, AmountOp::GreaterThan, "Greater Than");
+                    ui.selectable_value(
+                        &mut gates.spawn.op,
+                        AmountOp::GreaterEqualThan,
+                        "Greater Equal Than",
+                    );
+                });
+                ui.label("Amount");
+                ui.add(egui::DragValue::new(&mut gates.spawn_amount).speed(1.0));
+                ui.checkbox(&mut gates.spawn_lock, "Keep visible while locked");
+                if ui.button("Spawn").clicked() {
+                    gates.spawn.amount = Balance::from(gates.spawn_amount as u128);
+                    spawn_gated_cube(&mut commands, &asset_server, &gates);
+                }
+            });
+        });
+}
+
+pub struct TokenGatePlugin;
+
+impl Plugin for TokenGatePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TokenGates>()
+            .add_system(refresh_token_gate_balances)
+            .add_system(evaluate_token_gates.after(refresh_token_gate_balances))
+            .add_system(token_gates_ui);
+    }
+}
@@ -0,0 +1,431 @@
+use std::collections::{HashMap, VecDeque};
+
+use bevy::prelude::*;
+use simula_viz::lines::{Lines, LinesBundle};
+use sugarfunge_api_types::{
+    asset::AssetBalancesInput,
+    market::{RateAccount, RateAction},
+    primitives::{Account, ClassId, MarketId},
+};
+
+use crate::api::{ApiEvents, AssetBalances, AssetBalancesRequest, RequestId};
+
+use super::{picking::EconomyPickable, EconomyEvent, Holding};
+
+const ACCOUNTS_PER_RING: usize = 12;
+const ACCOUNT_RADIUS: f32 = 4.0;
+const MARKETS_PER_RING: usize = 6;
+const MARKET_RADIUS: f32 = 1.5;
+const RING_SPACING: f32 = 1.5;
+const NODE_HEIGHT: f32 = 0.5;
+const HOLDING_WIDTH: f32 = 0.25;
+const HOLDING_GAP: f32 = 0.02;
+const FLOW_SECONDS: f32 = 1.5;
+const FLOW_FADE_SECONDS: f32 = 0.5;
+const BALANCES_POLL_SECONDS: f32 = 5.0;
+
+#[derive(Component, Debug, Clone)]
+pub struct EconomyAccount {
+    pub account: Account,
+}
+
+#[derive(Component, Debug, Clone)]
+pub struct EconomyMarket {
+    pub market_id: MarketId,
+}
+
+#[derive(Component, Debug, Clone)]
+pub struct EconomyHolding {
+    pub account: Account,
+    pub holding: Holding,
+}
+
+/// A transfer in flight between two nodes, drawn as a line growing from `from`
+/// to `to` and then fading out.
+#[derive(Component, Debug, Clone)]
+pub struct EconomyFlow {
+    pub from: Vec3,
+    pub to: Vec3,
+    pub color: Color,
+    pub elapsed: f32,
+}
+
+#[derive(Component)]
+pub struct EconomyFlowLines;
+
+#[derive(Resource)]
+pub struct EconomyViz {
+    pub accounts: HashMap<String, (Entity, Vec3)>,
+    pub markets: HashMap<u64, (Entity, Vec3)>,
+    pub poll: Timer,
+    pub pending: VecDeque<Account>,
+    /// `asset/balances` request of the account being polled; its response
+    /// reaches the viz as an `EconomyEvent::Balances`.
+    pub in_flight: Option<RequestId>,
+}
+
+impl Default for EconomyViz {
+    fn default() -> Self {
+        Self {
+            accounts: HashMap::new(),
+            markets: HashMap::new(),
+            poll: Timer::from_seconds(BALANCES_POLL_SECONDS, TimerMode::Repeating),
+            pending: VecDeque::new(),
+            in_flight: None,
+        }
+    }
+}
+
+impl EconomyViz {
+    fn refresh(&mut self, account: &Account) {
+        if !self
+            .pending
+            .iter()
+            .any(|pending| pending.as_str() == account.as_str())
+        {
+            self.pending.push_back(account.clone());
+        }
+    }
+}
+
+pub fn class_color(class_id: ClassId) -> Color {
+    Color::hsl((u64::from(class_id) * 47 % 360) as f32, 0.8, 0.6)
+}
+
+fn ring_position(index: usize, per_ring: usize, radius: f32) -> Vec3 {
+    let ring = index / per_ring;
+    let angle =
+        (index % per_ring) as f32 / per_ring as f32 * std::f32::consts::TAU + ring as f32 * 0.5;
+    let radius = radius + ring as f32 * RING_SPACING;
+    Vec3::new(angle.cos() * radius, NODE_HEIGHT, angle.sin() * radius)
+}
+
+fn holding_height(amount: u128) -> f32 {
+    ((amount as f64 + 1.0).log10() as f32 * 0.2).max(0.05)
+}
+
+fn spawn_account(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    viz: &mut EconomyViz,
+    account: &Account,
+) -> Vec3 {
+    if let Some((_, position)) = viz.accounts.get(account.as_str()) {
+        return *position;
+    }
+    let position = ring_position(viz.accounts.len(), ACCOUNTS_PER_RING, ACCOUNT_RADIUS);
+    let entity = commands
+        .spawn(SpatialBundle::from_transform(Transform::from_translation(
+            position,
+        )))
+        .insert(EconomyAccount {
+            account: account.clone(),
+        })
+        .insert(EconomyPickable {
+            half_extents: Vec3::splat(0.4),
+        })
+        .insert(Name::new(format!("Account: {}", account.as_str())))
+        .with_children(|parent| {
+            parent.spawn(SceneBundle {
+                scene: asset_server.load("models/sphere/sphere.gltf#Scene0"),
+                transform: Transform::from_scale(Vec3::splat(0.4)),
+                ..Default::default()
+            });
+        })
+        .id();
+    viz.accounts.insert(account.to_string(), (entity, position));
+    viz.refresh(account);
+    position
+}
+
+fn spawn_market(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    viz: &mut EconomyViz,
+    market_id: MarketId,
+) -> Vec3 {
+    if let Some((_, position)) = viz.markets.get(&u64::from(market_id)) {
+        return *position;
+    }
+    let position = ring_position(viz.markets.len(), MARKETS_PER_RING, MARKET_RADIUS);
+    let entity = commands
+        .spawn(SpatialBundle::from_transform(Transform::from_translation(
+            position,
+        )))
+        .insert(EconomyMarket { market_id })
+        .insert(EconomyPickable {
+            half_extents: Vec3::new(0.6, 0.15, 0.6),
+        })
+        .insert(Name::new(format!("Market: {}", u64::from(market_id))))
+        .with_children(|parent| {
+            parent.spawn(SceneBundle {
+                scene: asset_server.load("models/cube/cube.gltf#Scene0"),
+                transform: Transform::from_scale(Vec3::new(0.6, 0.15, 0.6)),
+                ..Default::default()
+            });
+        })
+        .id();
+    viz.markets.insert(u64::from(market_id), (entity, position));
+    position
+}
+
+fn spawn_flow(commands: &mut Commands, from: Vec3, to: Vec3, class_id: ClassId) {
+    commands
+        .spawn(EconomyFlow {
+            from,
+            to,
+            color: class_color(class_id),
+            elapsed: 0.0,
+        })
+        .insert(Name::new("Economy Flow"));
+}
+
+/// Spawns nodes for every account and market seen in `EconomyEvent`s and
+/// starts flows for transfers, mints, burns, deposits and exchanges. Mints
+/// start and burns end at the world origin.
+pub fn handle_economy_events(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut viz: ResMut<EconomyViz>,
+    mut events: EventReader<EconomyEvent>,
+) {
+    let viz = &mut *viz;
+    for event in events.iter() {
+        match event {
+            EconomyEvent::Account { account } | EconomyEvent::Balances { account, .. } => {
+                spawn_account(&mut commands, &asset_server, viz, account);
+            }
+            EconomyEvent::Transfer {
+                from, to, class_id, ..
+            } => {
+                let from_position = spawn_account(&mut commands, &asset_server, viz, from);
+                let to_position = spawn_account(&mut commands, &asset_server, viz, to);
+                spawn_flow(&mut commands, from_position, to_position, *class_id);
+                viz.refresh(from);
+                viz.refresh(to);
+            }
+            EconomyEvent::Mint { to, class_id, .. } => {
+                let to_position = spawn_account(&mut commands, &asset_server, viz, to);
+                spawn_flow(&mut commands, Vec3::ZERO, to_position, *class_id);
+                viz.refresh(to);
+            }
+            EconomyEvent::Burn { from, class_id, .. } => {
+                let from_position = spawn_account(&mut commands, &asset_server, viz, from);
+                spawn_flow(&mut commands, from_position, Vec3::ZERO, *class_id);
+                viz.refresh(from);
+            }
+            EconomyEvent::Market { market_id, owner } => {
+                spawn_market(&mut commands, &asset_server, viz, *market_id);
+                spawn_account(&mut commands, &asset_server, viz, owner);
+            }
+            EconomyEvent::Deposit {
+                market_id, owner, ..
+            } => {
+                let market_position = spawn_market(&mut commands, &asset_server, viz, *market_id);
+                let owner_position = spawn_account(&mut commands, &asset_server, viz, owner);
+                spawn_flow(
+                    &mut commands,
+                    owner_position,
+                    market_position,
+                    ClassId::from(0),
+                );
+                viz.refresh(owner);
+            }
+            EconomyEvent::Exchange {
+                market_id,
+                buyer,
+                balances,
+            } => {
+                let market_position = spawn_market(&mut commands, &asset_server, viz, *market_id);
+                let buyer_position = spawn_account(&mut commands, &asset_server, viz, buyer);
+                for rate in balances.iter().map(|balance| &balance.rate) {
+                    let mut positions = [Vec3::ZERO; 2];
+                    for (position, rate_account) in positions.iter_mut().zip([&rate.from, &rate.to])
+                    {
+                        *position = match rate_account {
+                            RateAccount::Market => market_position,
+                            RateAccount::Buyer => buyer_position,
+                            RateAccount::Account(account) => {
+                                viz.refresh(account);
+                                spawn_account(&mut commands, &asset_server, viz, account)
+                            }
+                        };
+                    }
+                    let [from_position, to_position] = positions;
+                    match &rate.action {
+                        RateAction::Transfer(_) | RateAction::MarketTransfer(..) => {
+                            spawn_flow(&mut commands, from_position, to_position, rate.class_id)
+                        }
+                        RateAction::Mint(_) => {
+                            spawn_flow(&mut commands, Vec3::ZERO, to_position, rate.class_id)
+                        }
+                        RateAction::Burn(_) => {
+                            spawn_flow(&mut commands, from_position, Vec3::ZERO, rate.class_id)
+                        }
+                        RateAction::Has(..) => {}
+                    }
+                }
+                viz.refresh(buyer);
+            }
+        }
+    }
+}
+
+/// Rebuilds an account's stack of holdings whenever fetched balances differ
+/// from the ones currently shown.
+pub fn update_economy_holdings(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    viz: Res<EconomyViz>,
+    mut events: EventReader<EconomyEvent>,
+    holdings: Query<(Entity, &EconomyHolding)>,
+) {
+    for event in events.iter() {
+        if let EconomyEvent::Balances {
+            account,
+            holdings: balances,
+        } = event
+        {
+            let Some((account_entity, _)) = viz.accounts.get(account.as_str()) else {
+                continue;
+            };
+            let mut current: Vec<(Entity, Holding)> = holdings
+                .iter()
+                .filter(|(_, holding)| holding.account.as_str() == account.as_str())
+                .map(|(entity, holding)| (entity, holding.holding.clone()))
+                .collect();
+            current.sort_by(|(_, a), (_, b)| a.cmp(b));
+            let mut balances: Vec<Holding> = balances
+                .iter()
+                .filter(|holding| holding.amount > 0)
+                .cloned()
+                .collect();
+            balances.sort();
+            if current
+                .iter()
+                .map(|(_, holding)| holding)
+                .eq(balances.iter())
+            {
+                continue;
+            }
+            for (entity, _) in current {
+                commands.entity(entity).despawn_recursive();
+            }
+            let mut y = 0.5;
+            commands.entity(*account_entity).with_children(|parent| {
+                for holding in balances {
+                    let height = holding_height(holding.amount);
+                    parent
+                        .spawn(SceneBundle {
+                            scene: asset_server.load("models/cube/cube.gltf#Scene0"),
+                            transform: Transform::from_xyz(0.0, y + height * 0.5, 0.0)
+                                .with_scale(Vec3::new(HOLDING_WIDTH, height, HOLDING_WIDTH)),
+                            ..Default::default()
+                        })
+                        .insert(Name::new(format!(
+                            "Holding: {}/{} x{}",
+                            holding.class_id, holding.asset_id, holding.amount
+                        )))
+                        .insert(EconomyHolding {
+                            account: account.clone(),
+                            holding,
+                        })
+                        .insert(EconomyPickable {
+                            half_extents: Vec3::splat(0.5),
+                        });
+                    y += height + HOLDING_GAP;
+                }
+            });
+        }
+    }
+}
+
+pub fn animate_economy_flows(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut flows: Query<(Entity, &mut EconomyFlow)>,
+    mut lines: Query<&mut Lines, With<EconomyFlowLines>>,
+) {
+    let Ok(mut lines) = lines.get_single_mut() else {
+        return;
+    };
+    for (entity, mut flow) in flows.iter_mut() {
+        flow.elapsed += time.delta_seconds();
+        if flow.elapsed > FLOW_SECONDS + FLOW_FADE_SECONDS {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let t = (flow.elapsed / FLOW_SECONDS).min(1.0);
+        let fade = 1.0 - ((flow.elapsed - FLOW_SECONDS) / FLOW_FADE_SECONDS).clamp(0.0, 1.0);
+        // Arc above the grid so flows between the same nodes stay readable.
+        let lift = Vec3::Y * (flow.from.distance(flow.to) * 0.25);
+        let point = |t: f32| flow.from.lerp(flow.to, t) + lift * (4.0 * t * (1.0 - t));
+        let color = *flow.color.clone().set_a(fade);
+        let steps = 16;
+        let mut previous = point(0.0);
+        for step in 1..=steps {
+            let current = point(step as f32 / steps as f32 * t);
+            lines.line_colored(previous, current, 0.0, color);
+            previous = current;
+        }
+        if t < 1.0 {
+            lines.line_colored(previous, previous + Vec3::Y * 0.2, 0.0, Color::WHITE);
+        }
+    }
+}
+
+pub fn poll_economy_balances(
+    time: Res<Time>,
+    mut viz: ResMut<EconomyViz>,
+    mut api: ApiEvents<AssetBalances>,
+) {
+    if viz.poll.tick(time.delta()).just_finished() {
+        let accounts: Vec<Account> = viz
+            .accounts
+            .keys()
+            .map(|account| Account::from(account.clone()))
+            .collect();
+        for account in accounts.iter() {
+            viz.refresh(account);
+        }
+    }
+
+    if let Some(in_flight) = viz.in_flight {
+        let completed = api.completed.iter().any(|event| event.id == in_flight);
+        let failed = api.failed.iter().any(|event| event.id == in_flight);
+        if completed || failed {
+            viz.in_flight = None;
+        }
+    }
+
+    if viz.in_flight.is_none() {
+        if let Some(account) = viz.pending.pop_front() {
+            let request = AssetBalancesRequest::new(AssetBalancesInput {
+                class_id: None,
+                account,
+            });
+            viz.in_flight = Some(request.id);
+            api.requests.send(request);
+        }
+    }
+}
+
+fn setup_economy_viz(mut commands: Commands) {
+    commands
+        .spawn(LinesBundle::default())
+        .insert(EconomyFlowLines)
+        .insert(Name::new("Economy Flows"));
+}
+
+pub struct EconomyVizPlugin;
+
+impl Plugin for EconomyVizPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EconomyViz>()
+            .add_startup_system(setup_economy_viz)
+            .add_system(handle_economy_events)
+            .add_system(update_economy_holdings.after(handle_economy_events))
+            .add_system(animate_economy_flows)
+            .add_system(poll_economy_balances);
+    }
+}
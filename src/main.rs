@@ -1,11 +1,18 @@
+use api::ApiPlugin;
+use behavior::BehaviorPlugin;
 use bevy::{
     diagnostic::{Diagnostics, FrameTimeDiagnosticsPlugin},
     prelude::*,
     window::PresentMode,
 };
 use bevy_inspector_egui::quick::WorldInspectorPlugin;
+use console::ConsolePlugin;
 use debug_ui::DebugUiPlugin;
+use economy::EconomyPlugin;
+use mission::MissionPlugin;
 use prelude::*;
+use scenario::ScenarioPlugin;
+use shortcuts::{world_inspector_visible, ShortcutsPlugin};
 use simula_action::ActionPlugin;
 use simula_camera::orbitcam::*;
 use simula_viz::{
@@ -13,9 +20,19 @@ use simula_viz::{
     grid::{Grid, GridBundle, GridPlugin},
     lines::LinesPlugin,
 };
+use simulation::SimulationPlugin;
 
+pub mod api;
+pub mod asset_files;
+pub mod behavior;
+pub mod console;
 pub mod debug_ui;
+pub mod economy;
+pub mod mission;
 pub mod prelude;
+pub mod scenario;
+pub mod shortcuts;
+pub mod simulation;
 pub mod util;
 
 fn main() {
@@ -36,14 +53,22 @@ fn main() {
             }),
             ..default()
         }))
-        .add_plugin(WorldInspectorPlugin::new())
+        .add_plugin(WorldInspectorPlugin::new().run_if(world_inspector_visible))
         .add_plugin(ActionPlugin)
+        .add_plugin(ShortcutsPlugin)
         .add_plugin(FrameTimeDiagnosticsPlugin::default())
         .add_plugin(OrbitCameraPlugin)
         .add_plugin(LinesPlugin)
         .add_plugin(AxesPlugin)
         .add_plugin(GridPlugin)
+        .add_plugin(ApiPlugin)
+        .add_plugin(EconomyPlugin)
         .add_plugin(DebugUiPlugin)
+        .add_plugin(BehaviorPlugin)
+        .add_plugin(SimulationPlugin)
+        .add_plugin(MissionPlugin)
+        .add_plugin(ScenarioPlugin)
+        .add_plugin(ConsolePlugin)
         .add_startup_system(setup)
         .add_system(debug_info)
         .run();
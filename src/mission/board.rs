@@ -0,0 +1,201 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use sugarfunge_api_types::primitives::Account;
+
+use crate::{behavior::BehaviorStatus, economy::EconomyEvent};
+
+use super::{Mission, MissionBoard, MissionIcon, MissionReceipt, WalletProgress};
+
+const ICON_SIZE: f32 = 32.0;
+
+fn icon_ui(ui: &mut egui::Ui, board: &MissionBoard, icon: MissionIcon) {
+    if let Some(texture_id) = board.icons.get(&icon) {
+        ui.image(*texture_id, [ICON_SIZE, ICON_SIZE]);
+    }
+}
+
+enum MissionAction {
+    /// Lets the mission pay out again.
+    Reset,
+    /// Pays the rewards that failed.
+    Retry,
+}
+
+fn mission_ui(
+    ui: &mut egui::Ui,
+    board: &MissionBoard,
+    mission: &Mission,
+    wallet: Option<&WalletProgress>,
+) -> Option<MissionAction> {
+    let mut action = None;
+    ui.horizontal(|ui| {
+        icon_ui(ui, board, mission.definition.icon);
+        ui.vertical(|ui| {
+            ui.strong(&mission.definition.name);
+            ui.label(&mission.definition.description);
+            let holdings = wallet.and_then(|wallet| wallet.holdings.as_deref());
+            for objective in mission.definition.objectives.iter() {
+                let (mark, progress) = match holdings {
+                    Some(holdings) => (
+                        if objective.met(holdings) {
+                            "✔"
+                        } else {
+                            "☐"
+                        },
+                        objective.balance(holdings).to_string(),
+                    ),
+                    None => ("☐", "?".to_string()),
+                };
+                ui.label(format!(
+                    "{} {} ({} {:?} {})",
+                    mark, objective.description, progress, objective.op, objective.amount
+                ));
+            }
+            for reward in mission.definition.rewards.iter() {
+                ui.label(format!("Reward: {}", reward.label()));
+            }
+            let Some(wallet) = wallet else {
+                return;
+            };
+            if wallet.completed.contains(&mission.id) {
+                ui.horizontal(|ui| {
+                    ui.colored_label(egui::Color32::GREEN, "Completed");
+                    if ui.button("Reset").clicked() {
+                        action = Some(MissionAction::Reset);
+                    }
+                });
+            } else if let Some(unpaid) = wallet.unpaid.get(&mission.id) {
+                ui.horizontal(|ui| {
+                    ui.colored_label(
+                        egui::Color32::RED,
+                        format!("{} reward(s) failed to pay", unpaid.len()),
+                    );
+                    if ui.button("Retry").clicked() {
+                        action = Some(MissionAction::Retry);
+                    }
+                    if ui.button("Reset").clicked() {
+                        action = Some(MissionAction::Reset);
+                    }
+                });
+            } else if wallet.claims.contains_key(&mission.id) {
+                ui.horizontal(|ui| {
+                    ui.label("Paying rewards");
+                    ui.add(egui::Spinner::default());
+                });
+            }
+        });
+    });
+    action
+}
+
+fn receipt_ui(ui: &mut egui::Ui, receipt: &MissionReceipt) {
+    ui.strong(&receipt.mission);
+    ui.label(format!("Account: {}", receipt.account.as_str()));
+    for (label, status, result) in receipt.rewards.iter() {
+        let color = match status {
+            BehaviorStatus::Success => egui::Color32::GREEN,
+            _ => egui::Color32::RED,
+        };
+        ui.colored_label(color, label).on_hover_text(result);
+    }
+}
+
+pub fn mission_board_ui(
+    mut commands: Commands,
+    mut egui_contexts: EguiContexts,
+    mut board: ResMut<MissionBoard>,
+    mut economy_events: EventWriter<EconomyEvent>,
+    asset_server: Res<AssetServer>,
+) {
+    let ctx = egui_contexts.ctx_mut();
+    egui::Window::new("Mission Board")
+        .default_open(false)
+        .scroll2([false, true])
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                icon_ui(ui, &board, MissionIcon::Balance);
+                ui.vertical(|ui| {
+                    ui.label("Wallet Account");
+                    ui.text_edit_singleline(&mut *board.account);
+                    ui.label("Wallet Seed");
+                    ui.text_edit_singleline(&mut board.seed);
+                });
+            });
+            ui.horizontal(|ui| {
+                if ui.button("Track Wallet").clicked() && !board.account.is_empty() {
+                    let account = board.account.clone();
+                    let seed = board.seed.clone();
+                    board.track(&account, &seed);
+                    board.check(&account, &mut commands);
+                    economy_events.send(EconomyEvent::Account { account });
+                }
+                if ui.button("Reload Missions").clicked() {
+                    board.load_missions(&asset_server);
+                }
+            });
+            let accounts: Vec<String> = board.wallets.keys().cloned().collect();
+            ui.horizontal_wrapped(|ui| {
+                for account in accounts {
+                    let selected = board.selected.as_ref() == Some(&account);
+                    if ui.selectable_label(selected, &account).clicked() {
+                        board.selected = Some(account);
+                    }
+                }
+            });
+            if let Some(account) = board.selected.clone() {
+                if ui.button("Check Progress").clicked() {
+                    board.check(&Account::from(account), &mut commands);
+                }
+            }
+            for error in board.errors.iter() {
+                ui.colored_label(egui::Color32::RED, error);
+            }
+            ui.separator();
+
+            let mut action = None;
+            let wallet = board
+                .selected
+                .as_ref()
+                .and_then(|account| board.wallets.get(account));
+            for mission in board.missions.iter() {
+                if let Some(picked) = mission_ui(ui, &board, mission, wallet) {
+                    action = Some((mission.id.clone(), picked));
+                }
+                ui.separator();
+            }
+            if let Some(wallet) = wallet {
+                egui::CollapsingHeader::new(format!("Receipts ({})", wallet.receipts.len())).show(
+                    ui,
+                    |ui| {
+                        for receipt in wallet.receipts.iter().rev() {
+                            receipt_ui(ui, receipt);
+                            ui.separator();
+                        }
+                    },
+                );
+            }
+
+            if let (Some((mission_id, action)), Some(account)) = (action, board.selected.clone()) {
+                match action {
+                    MissionAction::Reset => {
+                        if let Some(wallet) = board.wallets.get_mut(&account) {
+                            wallet.completed.remove(&mission_id);
+                            wallet.unpaid.remove(&mission_id);
+                        }
+                    }
+                    MissionAction::Retry => board.retry(&account, &mission_id, &mut commands),
+                }
+            }
+        });
+
+    let mut open = board.receipt.is_some();
+    if let Some(receipt) = board.receipt.clone() {
+        egui::Window::new("Mission Receipt")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| receipt_ui(ui, &receipt));
+    }
+    if !open {
+        board.receipt = None;
+    }
+}
@@ -0,0 +1,472 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use crossbeam::channel::{Receiver, TryRecvError};
+use serde::{Deserialize, Serialize};
+use sugarfunge_api_types::{
+    asset::{AssetBalancesInput, AssetBalancesOutput},
+    market::AmountOp,
+    primitives::Account,
+};
+
+use crate::{
+    api::{send_raw_request, AssetBalances, RawResponse},
+    asset_files::{load_ron_assets, RonAssets},
+    behavior::{
+        actions::{amount_op_holds, finish_action, start_action},
+        document::{Behavior, MarketAssetsBehavior, MintAssetBehavior},
+        BehaviorStatus,
+    },
+    economy::{EconomyEvent, Holding},
+};
+
+pub mod board;
+
+/// Relative to the asset folder.
+pub const MISSIONS_DIR: &str = "mission";
+pub const MISSION_EXTENSION: &str = ".mission.ron";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MissionIcon {
+    Labor,
+    Cash,
+    Coins,
+    Balance,
+}
+
+impl MissionIcon {
+    pub const ALL: [MissionIcon; 4] = [
+        MissionIcon::Labor,
+        MissionIcon::Cash,
+        MissionIcon::Coins,
+        MissionIcon::Balance,
+    ];
+
+    /// Path relative to the asset folder.
+    pub fn path(&self) -> &'static str {
+        match self {
+            MissionIcon::Labor => "mission/labor-icon.png",
+            MissionIcon::Cash => "mission/Money - Cash.png",
+            MissionIcon::Coins => "mission/Money - Coins.png",
+            MissionIcon::Balance => "mission/Balance.png",
+        }
+    }
+}
+
+/// Met while the wallet's balance of the asset satisfies `op amount`, like a
+/// `RateAction::Has` rate.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MissionObjective {
+    pub description: String,
+    pub class_id: u64,
+    pub asset_id: u64,
+    pub op: AmountOp,
+    pub amount: u64,
+}
+
+impl MissionObjective {
+    pub fn balance(&self, holdings: &[Holding]) -> u128 {
+        holdings
+            .iter()
+            .filter(|holding| {
+                holding.class_id == self.class_id && holding.asset_id == self.asset_id
+            })
+            .map(|holding| holding.amount)
+            .sum()
+    }
+
+    pub fn met(&self, holdings: &[Holding]) -> bool {
+        amount_op_holds(&self.op, self.balance(holdings), self.amount as u128)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum MissionReward {
+    /// Minted to the wallet by `seed`, which must own the class.
+    Mint {
+        seed: String,
+        class_id: u64,
+        asset_id: u64,
+        amount: u64,
+    },
+    /// Exchanged by the wallet itself, for rates that pay out to the buyer.
+    Exchange {
+        market_id: u64,
+        market_rate_id: u64,
+        amount: u64,
+    },
+}
+
+impl MissionReward {
+    pub fn label(&self) -> String {
+        match self {
+            MissionReward::Mint {
+                class_id,
+                asset_id,
+                amount,
+                ..
+            } => format!("Mint {} of {}/{}", amount, class_id, asset_id),
+            MissionReward::Exchange {
+                market_id,
+                market_rate_id,
+                amount,
+            } => format!(
+                "Exchange {} on market {} rate {}",
+                amount, market_id, market_rate_id
+            ),
+        }
+    }
+
+    fn behavior(&self, seed: &str, account: &str) -> Behavior {
+        match self {
+            MissionReward::Mint {
+                seed: issuer,
+                class_id,
+                asset_id,
+                amount,
+            } => Behavior::MintAsset(MintAssetBehavior {
+                seed: issuer.clone(),
+                to: account.to_string(),
+                class_id: *class_id,
+                asset_id: *asset_id,
+                amount: *amount,
+            }),
+            MissionReward::Exchange {
+                market_id,
+                market_rate_id,
+                amount,
+            } => Behavior::ExchangeAssets(MarketAssetsBehavior {
+                seed: seed.to_string(),
+                market_id: *market_id,
+                market_rate_id: *market_rate_id,
+                amount: *amount,
+            }),
+        }
+    }
+}
+
+/// A `.mission.ron` file.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MissionDefinition {
+    pub name: String,
+    pub description: String,
+    pub icon: MissionIcon,
+    pub objectives: Vec<MissionObjective>,
+    pub rewards: Vec<MissionReward>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Mission {
+    /// File stem, unique per mission.
+    pub id: String,
+    pub definition: MissionDefinition,
+}
+
+impl Mission {
+    pub fn completed_by(&self, holdings: &[Holding]) -> bool {
+        self.definition
+            .objectives
+            .iter()
+            .all(|objective| objective.met(holdings))
+    }
+}
+
+#[derive(Debug)]
+pub struct RewardClaim {
+    /// Index into the mission's rewards.
+    pub reward: usize,
+    pub label: String,
+    behavior: Behavior,
+    pending: Option<Receiver<RawResponse>>,
+    pub result: Option<(BehaviorStatus, String)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MissionReceipt {
+    pub mission: String,
+    pub account: Account,
+    pub rewards: Vec<(String, BehaviorStatus, String)>,
+}
+
+#[derive(Debug, Default)]
+pub struct WalletProgress {
+    /// Signs `Exchange` rewards.
+    pub seed: String,
+    /// `None` until the first balances arrive.
+    pub holdings: Option<Vec<Holding>>,
+    pub claims: HashMap<String, Vec<RewardClaim>>,
+    /// Missions with rewards that failed to pay, by the indices of those
+    /// rewards. They wait for a retry instead of being completed.
+    pub unpaid: HashMap<String, Vec<usize>>,
+    pub completed: HashSet<String>,
+    pub receipts: Vec<MissionReceipt>,
+}
+
+#[derive(Resource)]
+pub struct MissionBoard {
+    pub missions: Vec<Mission>,
+    pub errors: Vec<String>,
+    pub wallets: BTreeMap<String, WalletProgress>,
+    pub account: Account,
+    pub seed: String,
+    pub selected: Option<String>,
+    /// Receipt shown in a popup until dismissed.
+    pub receipt: Option<MissionReceipt>,
+    pub icons: HashMap<MissionIcon, egui::TextureId>,
+    icon_handles: Vec<Handle<Image>>,
+    balances: Vec<(String, Receiver<RawResponse>)>,
+    /// Mission files being read.
+    loading: RonAssets<MissionDefinition>,
+}
+
+impl Default for MissionBoard {
+    fn default() -> Self {
+        Self {
+            missions: vec![],
+            errors: vec![],
+            wallets: BTreeMap::new(),
+            account: Account::from("".to_string()),
+            seed: "".to_string(),
+            selected: None,
+            receipt: None,
+            icons: HashMap::new(),
+            icon_handles: vec![],
+            balances: vec![],
+            loading: RonAssets::default(),
+        }
+    }
+}
+
+impl MissionBoard {
+    pub fn load_missions(&mut self, asset_server: &AssetServer) {
+        self.missions.clear();
+        self.errors.clear();
+        self.loading = load_ron_assets(asset_server, MISSIONS_DIR, MISSION_EXTENSION);
+        if self.loading.is_empty() {
+            self.errors.push(format!("No missions in {}", MISSIONS_DIR));
+        }
+    }
+
+    /// Adds the missions whose files have been read, keeping them sorted by
+    /// file name.
+    fn receive_missions(&mut self) {
+        let received = self.loading.receive();
+        if received.is_empty() {
+            return;
+        }
+        for result in received {
+            match result {
+                Ok((id, definition)) => self.missions.push(Mission { id, definition }),
+                Err(err) => self.errors.push(err),
+            }
+        }
+        self.missions.sort_by(|a, b| a.id.cmp(&b.id));
+    }
+
+    /// Starts tracking `account`, or updates its seed if already tracked.
+    pub fn track(&mut self, account: &Account, seed: &str) {
+        let wallet = self.wallets.entry(account.to_string()).or_default();
+        wallet.seed = seed.to_string();
+        self.selected = Some(account.to_string());
+    }
+
+    /// Pays `account` the rewards of `mission_id` that failed last time.
+    pub fn retry(&mut self, account: &str, mission_id: &str, commands: &mut Commands) {
+        let Some(mission) = self
+            .missions
+            .iter()
+            .find(|mission| mission.id == mission_id)
+        else {
+            return;
+        };
+        let Some(wallet) = self.wallets.get_mut(account) else {
+            return;
+        };
+        let Some(rewards) = wallet.unpaid.remove(mission_id) else {
+            return;
+        };
+        let claims = claim_rewards(mission, &rewards, &wallet.seed, account, commands);
+        wallet.claims.insert(mission.id.clone(), claims);
+    }
+
+    pub fn check(&mut self, account: &Account, commands: &mut Commands) {
+        let pending = send_raw_request::<AssetBalances>(
+            commands,
+            AssetBalancesInput {
+                account: account.clone(),
+                class_id: None,
+            },
+        );
+        self.balances.push((account.to_string(), pending));
+    }
+}
+
+fn setup_missions(
+    mut board: ResMut<MissionBoard>,
+    mut egui_contexts: EguiContexts,
+    asset_server: Res<AssetServer>,
+) {
+    board.load_missions(&asset_server);
+    for icon in MissionIcon::ALL {
+        let handle: Handle<Image> = asset_server.load(icon.path());
+        let texture_id = egui_contexts.add_image(handle.clone_weak());
+        board.icons.insert(icon, texture_id);
+        board.icon_handles.push(handle);
+    }
+}
+
+/// Sends the `rewards` of `mission`, by index, to `account`.
+fn claim_rewards(
+    mission: &Mission,
+    rewards: &[usize],
+    seed: &str,
+    account: &str,
+    commands: &mut Commands,
+) -> Vec<RewardClaim> {
+    rewards
+        .iter()
+        .filter_map(|&index| {
+            let reward = mission.definition.rewards.get(index)?;
+            let behavior = reward.behavior(seed, account);
+            Some(RewardClaim {
+                reward: index,
+                label: reward.label(),
+                pending: start_action(&behavior, commands),
+                behavior,
+                result: None,
+            })
+        })
+        .collect()
+}
+
+fn poll_claim(claim: &mut RewardClaim) {
+    let response = match claim.pending.as_ref().map(|pending| pending.try_recv()) {
+        Some(Ok(response)) => response,
+        Some(Err(TryRecvError::Empty)) => return,
+        Some(Err(TryRecvError::Disconnected)) => Err("Request dropped".to_string()),
+        None if claim.result.is_some() => return,
+        None => Err("Request not sent".to_string()),
+    };
+    claim.pending = None;
+    claim.result = Some(finish_action(&claim.behavior, response));
+}
+
+/// Keeps wallet holdings current, pays out missions whose objectives are all
+/// met and turns finished payouts into receipts.
+pub fn track_mission_progress(
+    mut commands: Commands,
+    mut board: ResMut<MissionBoard>,
+    mut economy_events: EventReader<EconomyEvent>,
+) {
+    let board = &mut *board;
+    board.receive_missions();
+    for event in economy_events.iter() {
+        if let EconomyEvent::Balances { account, holdings } = event {
+            if let Some(wallet) = board.wallets.get_mut(account.as_str()) {
+                wallet.holdings = Some(holdings.clone());
+            }
+        }
+    }
+    board.balances.retain(|(account, pending)| {
+        let response = match pending.try_recv() {
+            Ok(response) => response,
+            Err(TryRecvError::Empty) => return true,
+            Err(TryRecvError::Disconnected) => return false,
+        };
+        let balances = response.and_then(|value| {
+            serde_json::from_value::<AssetBalancesOutput>(value).map_err(|err| err.to_string())
+        });
+        match (board.wallets.get_mut(account), balances) {
+            (Some(wallet), Ok(balances)) => {
+                wallet.holdings = Some(
+                    balances
+                        .balances
+                        .iter()
+                        .map(|balance| Holding {
+                            class_id: u64::from(balance.class_id),
+                            asset_id: u64::from(balance.asset_id),
+                            amount: u128::from(balance.amount),
+                        })
+                        .collect(),
+                );
+            }
+            (_, Err(err)) => board.errors.push(format!("{}: {}", account, err)),
+            _ => {}
+        }
+        false
+    });
+
+    for (account, wallet) in board.wallets.iter_mut() {
+        if let Some(holdings) = &wallet.holdings {
+            for mission in board.missions.iter() {
+                if wallet.completed.contains(&mission.id)
+                    || wallet.claims.contains_key(&mission.id)
+                    || wallet.unpaid.contains_key(&mission.id)
+                    || !mission.completed_by(holdings)
+                {
+                    continue;
+                }
+                let rewards: Vec<usize> = (0..mission.definition.rewards.len()).collect();
+                let claims = claim_rewards(mission, &rewards, &wallet.seed, account, &mut commands);
+                wallet.claims.insert(mission.id.clone(), claims);
+            }
+        }
+
+        let mut finished = vec![];
+        for (mission_id, claims) in wallet.claims.iter_mut() {
+            for claim in claims.iter_mut() {
+                poll_claim(claim);
+            }
+            if claims.iter().all(|claim| claim.result.is_some()) {
+                finished.push(mission_id.clone());
+            }
+        }
+        for mission_id in finished {
+            let Some(claims) = wallet.claims.remove(&mission_id) else {
+                continue;
+            };
+            let mission = board
+                .missions
+                .iter()
+                .find(|mission| mission.id == mission_id)
+                .map(|mission| mission.definition.name.clone())
+                .unwrap_or(mission_id.clone());
+            let failed: Vec<usize> = claims
+                .iter()
+                .filter(|claim| !matches!(claim.result, Some((BehaviorStatus::Success, _))))
+                .map(|claim| claim.reward)
+                .collect();
+            let receipt = MissionReceipt {
+                mission,
+                account: Account::from(account.clone()),
+                rewards: claims
+                    .into_iter()
+                    .filter_map(|claim| {
+                        claim
+                            .result
+                            .map(|(status, result)| (claim.label, status, result))
+                    })
+                    .collect(),
+            };
+            if failed.is_empty() {
+                wallet.completed.insert(mission_id);
+            } else {
+                wallet.unpaid.insert(mission_id, failed);
+            }
+            wallet.receipts.push(receipt.clone());
+            board.receipt = Some(receipt);
+        }
+    }
+}
+
+pub struct MissionPlugin;
+
+impl Plugin for MissionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MissionBoard>()
+            .add_startup_system(setup_missions)
+            .add_system(track_mission_progress)
+            .add_system(board::mission_board_ui.after(track_mission_progress));
+    }
+}
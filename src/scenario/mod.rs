@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use crossbeam::channel::{Receiver, TryRecvError};
+use sugarfunge_client::{
+    scenario::{Scenario, ScenarioReport, ScenarioRun, StepSender, StepStatus, SCENARIO_EXTENSION},
+    Endpoint,
+};
+
+use crate::{
+    api::{send_raw_request, RawResponse},
+    asset_files::{load_ron_assets, RonAssets},
+};
+
+/// Relative to the asset folder.
+pub const SCENARIOS_DIR: &str = "scenarios";
+
+/// Sends scenario steps as `ApiRequest` events, so their results reach the
+/// economy and the debug UI like any other request.
+struct RequestSender<'a, 'w, 's>(&'a mut Commands<'w, 's>);
+
+impl StepSender for RequestSender<'_, '_, '_> {
+    type Pending = Receiver<RawResponse>;
+
+    fn send<E: Endpoint>(&mut self, input: E::Input) -> Self::Pending {
+        send_raw_request::<E>(self.0, input)
+    }
+}
+
+struct RunningScenario {
+    run: ScenarioRun,
+    /// Response of the step being run.
+    pending: Option<Receiver<RawResponse>>,
+}
+
+#[derive(Resource, Default)]
+pub struct ScenarioRunner {
+    /// File stem and scenario, sorted by file name.
+    pub scenarios: Vec<(String, Scenario)>,
+    pub errors: Vec<String>,
+    /// Latest report of each scenario, by file stem.
+    pub reports: HashMap<String, ScenarioReport>,
+    running: HashMap<String, RunningScenario>,
+    /// Scenario files being read.
+    loading: RonAssets<Scenario>,
+}
+
+impl ScenarioRunner {
+    pub fn load_scenarios(&mut self, asset_server: &AssetServer) {
+        self.scenarios.clear();
+        self.errors.clear();
+        self.loading = load_ron_assets(asset_server, SCENARIOS_DIR, SCENARIO_EXTENSION);
+        if self.loading.is_empty() {
+            self.errors
+                .push(format!("No scenarios in {}", SCENARIOS_DIR));
+        }
+    }
+
+    /// Adds the scenarios whose files have been read, keeping them sorted by
+    /// file name.
+    fn receive_scenarios(&mut self) {
+        let received = self.loading.receive();
+        if received.is_empty() {
+            return;
+        }
+        for result in received {
+            match result {
+                Ok(scenario) => self.scenarios.push(scenario),
+                Err(err) => self.errors.push(err),
+            }
+        }
+        self.scenarios.sort_by(|(a, _), (b, _)| a.cmp(b));
+    }
+
+    pub fn is_running(&self, id: &str) -> bool {
+        self.running.contains_key(id)
+    }
+
+    /// Starts `id`; `run_scenario_steps` sends its steps one at a time.
+    pub fn run(&mut self, id: &str) {
+        let Some((_, scenario)) = self.scenarios.iter().find(|(other, _)| other == id) else {
+            return;
+        };
+        self.running.insert(
+            id.to_string(),
+            RunningScenario {
+                run: ScenarioRun::new(scenario),
+                pending: None,
+            },
+        );
+    }
+}
+
+fn setup_scenarios(mut runner: ResMut<ScenarioRunner>, asset_server: Res<AssetServer>) {
+    runner.load_scenarios(&asset_server);
+}
+
+/// Records the responses of running steps, sends the next ones and keeps the
+/// reports of finished scenarios.
+pub fn run_scenario_steps(mut commands: Commands, mut runner: ResMut<ScenarioRunner>) {
+    let runner = &mut *runner;
+    runner.receive_scenarios();
+    let mut sender = RequestSender(&mut commands);
+    let mut finished = vec![];
+    for (id, running) in runner.running.iter_mut() {
+        if let Some(pending) = &running.pending {
+            let response = match pending.try_recv() {
+                Ok(response) => response,
+                Err(TryRecvError::Empty) => continue,
+                Err(TryRecvError::Disconnected) => Err("Request dropped".to_string()),
+            };
+            running.run.finish_step(response);
+        }
+        running.pending = running.run.send_next(&mut sender);
+        if running.pending.is_none() {
+            finished.push(id.clone());
+        }
+    }
+    for id in finished {
+        if let Some(running) = runner.running.remove(&id) {
+            runner.reports.insert(id, running.run.into_report());
+        }
+    }
+}
+
+fn report_ui(ui: &mut egui::Ui, report: &ScenarioReport) {
+    for step in report.steps.iter() {
+        let (mark, color) = match step.status {
+            StepStatus::Passed => ("✔", egui::Color32::GREEN),
+            StepStatus::Failed => ("✖", egui::Color32::RED),
+            StepStatus::Skipped => ("-", egui::Color32::GRAY),
+        };
+        let label = if step.id.is_empty() {
+            format!("{} {}", mark, step.step)
+        } else {
+            format!("{} {} ({})", mark, step.step, step.id)
+        };
+        let result = serde_json::to_string_pretty(&step.result).unwrap_or_default();
+        ui.colored_label(color, label).on_hover_text(result);
+    }
+}
+
+pub fn scenario_runner_ui(
+    mut egui_contexts: EguiContexts,
+    mut runner: ResMut<ScenarioRunner>,
+    asset_server: Res<AssetServer>,
+) {
+    egui::Window::new("Scenarios")
+        .default_open(false)
+        .scroll2([false, true])
+        .show(egui_contexts.ctx_mut(), |ui| {
+            if ui.button("Reload Scenarios").clicked() {
+                runner.load_scenarios(&asset_server);
+            }
+            for error in runner.errors.iter() {
+                ui.colored_label(egui::Color32::RED, error);
+            }
+            ui.separator();
+
+            let mut run = None;
+            for (id, scenario) in runner.scenarios.iter() {
+                ui.horizontal(|ui| {
+                    ui.strong(&scenario.name);
+                    if runner.is_running(id) {
+                        ui.add(egui::Spinner::default());
+                    } else if ui.button("Run").clicked() {
+                        run = Some(id.clone());
+                    }
+                    match runner.reports.get(id) {
+                        Some(report) if report.passed => {
+                            ui.colored_label(egui::Color32::GREEN, "Passed");
+                        }
+                        Some(_) => {
+                            ui.colored_label(egui::Color32::RED, "Failed");
+                        }
+                        None => {}
+                    }
+                });
+                if !scenario.description.is_empty() {
+                    ui.label(&scenario.description);
+                }
+                if let Some(report) = runner.reports.get(id) {
+                    egui::CollapsingHeader::new("Report")
+                        .id_source(id)
+                        .show(ui, |ui| report_ui(ui, report));
+                }
+                ui.separator();
+            }
+            if let Some(id) = run {
+                runner.run(&id);
+            }
+        });
+}
+
+pub struct ScenarioPlugin;
+
+impl Plugin for ScenarioPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScenarioRunner>()
+            .add_startup_system(setup_scenarios)
+            .add_system(run_scenario_steps)
+            .add_system(scenario_runner_ui.after(run_scenario_steps));
+    }
+}
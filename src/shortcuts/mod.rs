@@ -0,0 +1,263 @@
+//! Keyboard shortcuts, bound through `simula_action`.
+//!
+//! Bindings are read from `assets/shortcuts.ron` when present:
+//! ```ron
+//! [
+//!     (shortcut: CommandPalette, key: K, ctrl: true),
+//!     (shortcut: ToggleInspector, key: F2),
+//! ]
+//! ```
+//! Shortcuts the file leaves out keep their default keys. The file is read
+//! through the asset server, so the defaults apply until it arrives.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use serde::{Deserialize, Serialize};
+use simula_action::{action_map, Action, ActionInput, ActionMap, ActionMapInput};
+
+use crate::{
+    asset_files::{poll_asset_file, read_asset_file, AssetFileReceiver},
+    console::Console,
+    debug_ui::{debug_ui, dock::DebugUiLayouts, palette::CommandPalette},
+};
+
+/// Relative to the asset folder.
+pub const SHORTCUTS_PATH: &str = "shortcuts.ron";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, FromReflect, Serialize, Deserialize)]
+pub enum Shortcut {
+    CommandPalette,
+    Console,
+    /// Presses the submit button of the active debug UI form.
+    Submit,
+    NextTab,
+    PreviousTab,
+    ToggleInspector,
+    /// Takes keyboard focus away from the UI so the 3D view gets the input.
+    FocusView,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ShortcutBinding {
+    pub shortcut: Shortcut,
+    pub key: KeyCode,
+    #[serde(default)]
+    pub ctrl: bool,
+    #[serde(default)]
+    pub shift: bool,
+    #[serde(default)]
+    pub alt: bool,
+}
+
+impl ShortcutBinding {
+    fn ctrl(shortcut: Shortcut, key: KeyCode) -> Self {
+        Self {
+            shortcut,
+            key,
+            ctrl: true,
+            shift: false,
+            alt: false,
+        }
+    }
+}
+
+fn default_bindings() -> Vec<ShortcutBinding> {
+    vec![
+        ShortcutBinding::ctrl(Shortcut::CommandPalette, KeyCode::K),
+        ShortcutBinding::ctrl(Shortcut::Console, KeyCode::Grave),
+        ShortcutBinding::ctrl(Shortcut::Submit, KeyCode::Return),
+        ShortcutBinding::ctrl(Shortcut::NextTab, KeyCode::RBracket),
+        ShortcutBinding::ctrl(Shortcut::PreviousTab, KeyCode::LBracket),
+        ShortcutBinding::ctrl(Shortcut::ToggleInspector, KeyCode::I),
+        ShortcutBinding {
+            shortcut: Shortcut::FocusView,
+            key: KeyCode::Escape,
+            ctrl: false,
+            shift: false,
+            alt: false,
+        },
+    ]
+}
+
+/// The default bindings, with those listed in the `contents` of
+/// `SHORTCUTS_PATH` replacing the defaults of their shortcuts.
+fn parse_bindings(contents: &str) -> Result<Vec<ShortcutBinding>, String> {
+    let custom = ron::from_str::<Vec<ShortcutBinding>>(contents).map_err(|err| err.to_string())?;
+    let mut bindings = default_bindings();
+    bindings.retain(|binding| {
+        !custom
+            .iter()
+            .any(|other| other.shortcut == binding.shortcut)
+    });
+    bindings.extend(custom);
+    Ok(bindings)
+}
+
+fn shortcut_map(bindings: Vec<ShortcutBinding>) -> ActionMap<Shortcut> {
+    let mut map = ActionMap::<Shortcut>::default();
+    for binding in bindings {
+        map.insert(ActionMapInput {
+            action: binding.shortcut,
+            button: ActionInput::KeyCode(binding.key),
+            ctrl: binding.ctrl,
+            shift: binding.shift,
+            alt: binding.alt,
+        });
+    }
+    map
+}
+
+/// `SHORTCUTS_PATH` while it's being read.
+#[derive(Resource, Default)]
+pub struct ShortcutsFile(Option<AssetFileReceiver>);
+
+/// Marks the entity holding the shortcut actions.
+#[derive(Component)]
+pub struct Shortcuts;
+
+#[derive(Resource, Debug)]
+pub struct WorldInspectorVisible(pub bool);
+
+impl Default for WorldInspectorVisible {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// Run condition for the world inspector.
+pub fn world_inspector_visible(visible: Res<WorldInspectorVisible>) -> bool {
+    visible.0
+}
+
+fn submit_id() -> egui::Id {
+    egui::Id::new("shortcut_submit")
+}
+
+/// A form's submit button. Also reports a click when the submit shortcut was
+/// pressed, for the first form drawn that frame.
+pub fn submit_button(ui: &mut egui::Ui, text: &str) -> bool {
+    let clicked = ui.button(text).clicked();
+    let submitted = ui.ctx().data_mut(|data| {
+        let submitted = data.get_temp::<bool>(submit_id()).unwrap_or(false);
+        data.remove::<bool>(submit_id());
+        submitted
+    });
+    clicked || (submitted && ui.is_enabled())
+}
+
+fn setup_shortcuts(
+    mut commands: Commands,
+    mut file: ResMut<ShortcutsFile>,
+    asset_server: Res<AssetServer>,
+) {
+    commands.spawn((
+        Action::<Shortcut>::default(),
+        shortcut_map(default_bindings()),
+        Shortcuts,
+        Name::new("Shortcuts"),
+    ));
+    file.0 = Some(read_asset_file(&asset_server, SHORTCUTS_PATH));
+}
+
+/// Rebinds the shortcuts once `SHORTCUTS_PATH` is read. Without the file the
+/// defaults stay.
+fn receive_shortcuts_file(
+    mut file: ResMut<ShortcutsFile>,
+    mut maps: Query<&mut ActionMap<Shortcut>, With<Shortcuts>>,
+) {
+    let Some(contents) = file
+        .0
+        .as_ref()
+        .and_then(|receiver| poll_asset_file(SHORTCUTS_PATH, receiver))
+    else {
+        return;
+    };
+    file.0 = None;
+    let Ok(contents) = contents else {
+        return;
+    };
+    match parse_bindings(&contents) {
+        Ok(bindings) => {
+            for mut map in maps.iter_mut() {
+                *map = shortcut_map(bindings.clone());
+            }
+        }
+        Err(err) => warn!("{}: {}", SHORTCUTS_PATH, err),
+    }
+}
+
+fn run_shortcuts(
+    mut ctx: EguiContexts,
+    shortcuts: Query<&Action<Shortcut>, With<Shortcuts>>,
+    mut palette: ResMut<CommandPalette>,
+    mut console: ResMut<Console>,
+    mut layouts: ResMut<DebugUiLayouts>,
+    mut inspector: ResMut<WorldInspectorVisible>,
+) {
+    let Ok(action) = shortcuts.get_single() else {
+        return;
+    };
+    let ctx = ctx.ctx_mut();
+    // A submit nothing drew a button for last frame shouldn't linger.
+    ctx.data_mut(|data| data.remove::<bool>(submit_id()));
+    if action.on_enter(Shortcut::CommandPalette) {
+        palette.toggle();
+    }
+    if action.on_enter(Shortcut::Console) {
+        console.toggle();
+    }
+    if action.on_enter(Shortcut::Submit) {
+        ctx.data_mut(|data| data.insert_temp(submit_id(), true));
+    }
+    if action.on_enter(Shortcut::NextTab) {
+        layouts.current.cycle(1);
+    }
+    if action.on_enter(Shortcut::PreviousTab) {
+        layouts.current.cycle(-1);
+    }
+    if action.on_enter(Shortcut::ToggleInspector) {
+        inspector.0 = !inspector.0;
+    }
+    if action.on_enter(Shortcut::FocusView) {
+        palette.close();
+        ctx.memory_mut(|memory| {
+            if let Some(focused) = memory.focus() {
+                memory.surrender_focus(focused);
+            }
+        });
+    }
+}
+
+pub struct ShortcutsPlugin;
+
+impl Plugin for ShortcutsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Shortcut>()
+            .init_resource::<WorldInspectorVisible>()
+            .init_resource::<ShortcutsFile>()
+            .add_startup_system(setup_shortcuts)
+            .add_system(receive_shortcuts_file.before(action_map::<Shortcut, Shortcuts>))
+            .add_system(action_map::<Shortcut, Shortcuts>.before(run_shortcuts))
+            .add_system(run_shortcuts.before(debug_ui));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_bindings_replace_their_defaults() {
+        let bindings =
+            parse_bindings("[(shortcut: CommandPalette, key: P, ctrl: true, shift: true)]")
+                .unwrap();
+        assert_eq!(bindings.len(), default_bindings().len());
+        let palette = bindings
+            .iter()
+            .find(|binding| binding.shortcut == Shortcut::CommandPalette)
+            .unwrap();
+        assert_eq!(palette.key, KeyCode::P);
+        assert!(palette.ctrl && palette.shift && !palette.alt);
+        assert!(parse_bindings("[(shortcut: Nope, key: P)]").is_err());
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::{BTreeMap, HashMap};
+
+use bevy::prelude::*;
+use bevy_egui::egui::{
+    self,
+    plot::{Legend, Line, Plot, PlotPoints},
+};
+use sugarfunge_api_types::market::{RateAccount, RateBalance};
+
+use crate::economy::{EconomyEvent, Holding};
+
+use super::Simulation;
+
+const SAMPLE_INTERVAL: f32 = 1.0;
+const MAX_SAMPLES: usize = 600;
+const PLOT_HEIGHT: f32 = 120.0;
+
+#[derive(Debug, Default, Clone)]
+pub struct MetricSample {
+    pub time: f64,
+    /// Total supply per `(class_id, asset_id)` over all accounts with known balances.
+    pub supply: BTreeMap<(u64, u64), u128>,
+    /// Balance per `(market_id, class_id, asset_id)` the market pays out from.
+    pub markets: BTreeMap<(u64, u64, u64), i128>,
+    pub trades: u64,
+    pub deposits: u64,
+    pub mints: u64,
+}
+
+#[derive(Resource, Debug)]
+pub struct SimulationMetrics {
+    pub balances: HashMap<String, Vec<Holding>>,
+    pub current: MetricSample,
+    pub samples: Vec<MetricSample>,
+    pub timer: Timer,
+    pub elapsed: f64,
+}
+
+impl Default for SimulationMetrics {
+    fn default() -> Self {
+        Self {
+            balances: HashMap::new(),
+            current: MetricSample::default(),
+            samples: vec![],
+            timer: Timer::from_seconds(SAMPLE_INTERVAL, TimerMode::Repeating),
+            elapsed: 0.0,
+        }
+    }
+}
+
+impl SimulationMetrics {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    fn update_market(&mut self, market_id: u64, balances: &[RateBalance]) {
+        for balance in balances {
+            if let RateAccount::Market = balance.rate.from {
+                self.current.markets.insert(
+                    (
+                        market_id,
+                        u64::from(balance.rate.class_id),
+                        u64::from(balance.rate.asset_id),
+                    ),
+                    i128::from(balance.balance),
+                );
+            }
+        }
+    }
+
+    fn sample(&mut self) {
+        let mut supply = BTreeMap::new();
+        for holding in self.balances.values().flatten() {
+            *supply
+                .entry((holding.class_id, holding.asset_id))
+                .or_default() += holding.amount;
+        }
+        self.current.supply = supply;
+        self.current.time = self.elapsed;
+        self.samples.push(self.current.clone());
+        if self.samples.len() > MAX_SAMPLES {
+            self.samples.remove(0);
+        }
+    }
+}
+
+pub fn collect_simulation_metrics(
+    time: Res<Time>,
+    simulation: Res<Simulation>,
+    mut metrics: ResMut<SimulationMetrics>,
+    mut events: EventReader<EconomyEvent>,
+) {
+    if !simulation.running {
+        events.clear();
+        return;
+    }
+    for event in events.iter() {
+        match event {
+            EconomyEvent::Balances { account, holdings } => {
+                metrics
+                    .balances
+                    .insert(account.to_string(), holdings.clone());
+            }
+            EconomyEvent::Exchange {
+                market_id,
+                balances,
+                ..
+            } => {
+                metrics.current.trades += 1;
+                metrics.update_market(u64::from(*market_id), balances);
+            }
+            EconomyEvent::Deposit {
+                market_id,
+                balances,
+                ..
+            } => {
+                metrics.current.deposits += 1;
+                metrics.update_market(u64::from(*market_id), balances);
+            }
+            EconomyEvent::Mint { .. } => metrics.current.mints += 1,
+            _ => {}
+        }
+    }
+    metrics.elapsed += time.delta_seconds_f64();
+    if metrics.timer.tick(time.delta()).just_finished() {
+        metrics.sample();
+    }
+}
+
+fn plot_series<K: Ord + Clone>(
+    ui: &mut egui::Ui,
+    id: &str,
+    samples: &[MetricSample],
+    series: impl Fn(&MetricSample) -> Vec<(K, f64)>,
+    label: impl Fn(&K) -> String,
+) {
+    let mut lines: BTreeMap<K, Vec<[f64; 2]>> = BTreeMap::new();
+    for sample in samples {
+        for (key, value) in series(sample) {
+            lines.entry(key).or_default().push([sample.time, value]);
+        }
+    }
+    Plot::new(id)
+        .height(PLOT_HEIGHT)
+        .legend(Legend::default())
+        .show(ui, |plot_ui| {
+            for (key, points) in lines {
+                plot_ui.line(Line::new(PlotPoints::new(points)).name(label(&key)));
+            }
+        });
+}
+
+pub fn metrics_ui(ui: &mut egui::Ui, metrics: &SimulationMetrics) {
+    ui.label(format!(
+        "Trades: {}  Deposits: {}  Mints: {}  Accounts: {}",
+        metrics.current.trades,
+        metrics.current.deposits,
+        metrics.current.mints,
+        metrics.balances.len()
+    ));
+    ui.label("Supply per Asset");
+    plot_series(
+        ui,
+        "simulation_supply",
+        &metrics.samples,
+        |sample| {
+            sample
+                .supply
+                .iter()
+                .map(|(key, amount)| (*key, *amount as f64))
+                .collect()
+        },
+        |(class_id, asset_id)| format!("{}/{}", class_id, asset_id),
+    );
+    ui.label("Market Balances");
+    plot_series(
+        ui,
+        "simulation_markets",
+        &metrics.samples,
+        |sample| {
+            sample
+                .markets
+                .iter()
+                .map(|(key, amount)| (*key, *amount as f64))
+                .collect()
+        },
+        |(market_id, class_id, asset_id)| format!("Market {} {}/{}", market_id, class_id, asset_id),
+    );
+    ui.label("Trade Counts");
+    plot_series(
+        ui,
+        "simulation_trades",
+        &metrics.samples,
+        |sample| {
+            vec![
+                ("Trades", sample.trades as f64),
+                ("Deposits", sample.deposits as f64),
+                ("Mints", sample.mints as f64),
+            ]
+        },
+        |name| name.to_string(),
+    );
+}
@@ -0,0 +1,265 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use crossbeam::channel::{Receiver, TryRecvError};
+use sugarfunge_api_types::{
+    account::{SeededAccountInput, SeededAccountOutput},
+    primitives::{Account, Seed},
+};
+
+use crate::{
+    api::{send_raw_request, RawResponse, SeededAccount},
+    behavior::BehaviorTree,
+};
+
+pub mod metrics;
+pub mod policy;
+
+use metrics::SimulationMetrics;
+use policy::AgentPolicy;
+
+#[derive(Debug, Clone)]
+pub struct SimulationConfig {
+    pub agents: usize,
+    /// Funds agent wallets and signs `Mint` policy mints, so it must own the class.
+    pub issuer_seed: String,
+    pub market_id: u64,
+    pub buy_rate_id: u64,
+    pub sell_rate_id: u64,
+    pub class_id: u64,
+    pub asset_id: u64,
+    /// Whole tokens each agent is funded with.
+    pub fund_amount: u64,
+    pub mint_amount: u64,
+    pub trade_amount: u64,
+    pub hoard_target: u64,
+    /// Seconds between an agent's actions.
+    pub interval: f32,
+    /// Relative share of agents per policy, in `AgentPolicy::ALL` order.
+    pub weights: [u32; 4],
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            agents: 8,
+            issuer_seed: "//Alice".to_string(),
+            market_id: 1000,
+            buy_rate_id: 1,
+            sell_rate_id: 2,
+            class_id: 1000,
+            asset_id: 1,
+            fund_amount: 1,
+            mint_amount: 10,
+            trade_amount: 1,
+            hoard_target: 10,
+            interval: 3.0,
+            weights: [1, 1, 1, 1],
+        }
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct Simulation {
+    pub config: SimulationConfig,
+    pub running: bool,
+    pub agents: Vec<Entity>,
+}
+
+#[derive(Component, Debug)]
+pub struct SimulationAgent {
+    pub index: usize,
+    pub policy: AgentPolicy,
+    pub seed: String,
+    /// `None` until `account/seeded` resolves the wallet.
+    pub account: Option<Account>,
+    pub error: Option<String>,
+    pending: Option<Receiver<RawResponse>>,
+}
+
+fn start_simulation(
+    commands: &mut Commands,
+    simulation: &mut Simulation,
+    metrics: &mut SimulationMetrics,
+) {
+    metrics.reset();
+    for index in 0..simulation.config.agents {
+        let seed = format!("//Agent{}", index);
+        let policy = AgentPolicy::for_agent(index, &simulation.config.weights);
+        let pending = send_raw_request::<SeededAccount>(
+            commands,
+            SeededAccountInput {
+                seed: Seed::from(seed.clone()),
+            },
+        );
+        let agent = commands
+            .spawn(SimulationAgent {
+                index,
+                policy,
+                seed,
+                account: None,
+                error: None,
+                pending: Some(pending),
+            })
+            .insert(Name::new(format!("Agent {} ({})", index, policy.label())))
+            .id();
+        simulation.agents.push(agent);
+    }
+    simulation.running = true;
+}
+
+fn stop_simulation(commands: &mut Commands, simulation: &mut Simulation) {
+    for agent in simulation.agents.drain(..) {
+        commands.entity(agent).despawn();
+    }
+    simulation.running = false;
+}
+
+/// Gives each agent its behavior tree once its wallet address is known.
+pub fn resolve_agent_wallets(
+    mut commands: Commands,
+    simulation: Res<Simulation>,
+    mut agents: Query<(Entity, &mut SimulationAgent)>,
+) {
+    for (entity, mut agent) in agents.iter_mut() {
+        let response = match agent.pending.as_ref().map(|pending| pending.try_recv()) {
+            Some(Ok(response)) => response,
+            Some(Err(TryRecvError::Disconnected)) => Err("Request dropped".to_string()),
+            Some(Err(TryRecvError::Empty)) | None => continue,
+        };
+        agent.pending = None;
+        match response.and_then(|value| {
+            serde_json::from_value::<SeededAccountOutput>(value).map_err(|err| err.to_string())
+        }) {
+            Ok(seeded) => {
+                let document = policy::agent_document(
+                    &simulation.config,
+                    agent.policy,
+                    &agent.seed,
+                    seeded.account.as_str(),
+                );
+                let path = format!("agent {} ({})", agent.index, agent.policy.label());
+                commands
+                    .entity(entity)
+                    .insert(BehaviorTree::from_document(&path, document));
+                agent.account = Some(seeded.account);
+            }
+            Err(err) => agent.error = Some(err),
+        }
+    }
+}
+
+fn config_ui(ui: &mut egui::Ui, config: &mut SimulationConfig) {
+    egui::Grid::new("simulation_config").show(ui, |ui| {
+        ui.label("Agents");
+        ui.add(egui::DragValue::new(&mut config.agents).clamp_range(1..=256));
+        ui.end_row();
+        ui.label("Issuer Seed");
+        ui.text_edit_singleline(&mut config.issuer_seed);
+        ui.end_row();
+        ui.label("Market ID");
+        ui.add(egui::DragValue::new(&mut config.market_id).speed(0.1));
+        ui.end_row();
+        ui.label("Buy Rate ID");
+        ui.add(egui::DragValue::new(&mut config.buy_rate_id).speed(0.1));
+        ui.end_row();
+        ui.label("Sell Rate ID");
+        ui.add(egui::DragValue::new(&mut config.sell_rate_id).speed(0.1));
+        ui.end_row();
+        ui.label("Class ID");
+        ui.add(egui::DragValue::new(&mut config.class_id).speed(0.1));
+        ui.end_row();
+        ui.label("Asset ID");
+        ui.add(egui::DragValue::new(&mut config.asset_id).speed(0.1));
+        ui.end_row();
+        ui.label("Fund Amount");
+        ui.add(egui::DragValue::new(&mut config.fund_amount).speed(0.1));
+        ui.end_row();
+        ui.label("Mint Amount");
+        ui.add(egui::DragValue::new(&mut config.mint_amount).speed(0.1));
+        ui.end_row();
+        ui.label("Trade Amount");
+        ui.add(egui::DragValue::new(&mut config.trade_amount).speed(0.1));
+        ui.end_row();
+        ui.label("Hoard Target");
+        ui.add(egui::DragValue::new(&mut config.hoard_target).speed(0.1));
+        ui.end_row();
+        ui.label("Interval (s)");
+        ui.add(
+            egui::DragValue::new(&mut config.interval)
+                .speed(0.1)
+                .clamp_range(0.1..=60.0),
+        );
+        ui.end_row();
+        for (policy, weight) in AgentPolicy::ALL.iter().zip(config.weights.iter_mut()) {
+            ui.label(format!("{} Weight", policy.label()));
+            ui.add(egui::DragValue::new(weight).speed(0.1));
+            ui.end_row();
+        }
+    });
+}
+
+pub fn simulation_ui(
+    mut commands: Commands,
+    mut egui_contexts: EguiContexts,
+    mut simulation: ResMut<Simulation>,
+    mut metrics: ResMut<SimulationMetrics>,
+    agents: Query<(&SimulationAgent, Option<&BehaviorTree>)>,
+) {
+    egui::Window::new("Economy Simulation")
+        .default_open(false)
+        .show(egui_contexts.ctx_mut(), |ui| {
+            ui.add_enabled_ui(!simulation.running, |ui| {
+                config_ui(ui, &mut simulation.config);
+            });
+            ui.separator();
+            if simulation.running {
+                if ui.button("Stop").clicked() {
+                    stop_simulation(&mut commands, &mut simulation);
+                }
+            } else if ui.button("Start").clicked() {
+                start_simulation(&mut commands, &mut simulation, &mut metrics);
+            }
+            egui::CollapsingHeader::new(format!("Agents ({})", simulation.agents.len())).show(
+                ui,
+                |ui| {
+                    let mut rows: Vec<_> = agents.iter().collect();
+                    rows.sort_by_key(|(agent, _)| agent.index);
+                    for (agent, tree) in rows {
+                        let status = match (&agent.error, &agent.account, tree) {
+                            (Some(err), _, _) => err.clone(),
+                            (_, None, _) => "Resolving wallet".to_string(),
+                            (_, Some(_), Some(tree)) => {
+                                crate::behavior::ui::status_label(tree.status()).to_string()
+                            }
+                            (_, Some(_), None) => "Idle".to_string(),
+                        };
+                        ui.label(format!(
+                            "{} {} {}: {}",
+                            agent.index,
+                            agent.policy.label(),
+                            agent
+                                .account
+                                .as_ref()
+                                .map(|account| account.as_str())
+                                .unwrap_or(&agent.seed),
+                            status
+                        ));
+                    }
+                },
+            );
+            ui.separator();
+            metrics::metrics_ui(ui, &metrics);
+        });
+}
+
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Simulation>()
+            .init_resource::<SimulationMetrics>()
+            .add_system(resolve_agent_wallets)
+            .add_system(metrics::collect_simulation_metrics)
+            .add_system(simulation_ui);
+    }
+}
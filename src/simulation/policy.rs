@@ -0,0 +1,160 @@
+use sugarfunge_api_types::market::AmountOp;
+
+use crate::behavior::document::{
+    Behavior, BehaviorDocument, BehaviorNode, CheckBalanceBehavior, DebugBehavior, DelayBehavior,
+    FundAccountBehavior, MarketAssetsBehavior, MintAssetBehavior, RepeaterBehavior,
+    SelectorBehavior,
+};
+
+use super::SimulationConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgentPolicy {
+    Buy,
+    Sell,
+    Mint,
+    Hoard,
+}
+
+impl AgentPolicy {
+    pub const ALL: [AgentPolicy; 4] = [
+        AgentPolicy::Buy,
+        AgentPolicy::Sell,
+        AgentPolicy::Mint,
+        AgentPolicy::Hoard,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AgentPolicy::Buy => "Buy",
+            AgentPolicy::Sell => "Sell",
+            AgentPolicy::Mint => "Mint",
+            AgentPolicy::Hoard => "Hoard",
+        }
+    }
+
+    /// Policy of the `index`th agent, spreading agents over the policies in
+    /// proportion to `weights`.
+    pub fn for_agent(index: usize, weights: &[u32; 4]) -> Self {
+        let total: u32 = weights.iter().sum();
+        if total == 0 {
+            return AgentPolicy::ALL[index % AgentPolicy::ALL.len()];
+        }
+        let mut slot = index as u32 % total;
+        for (policy, weight) in AgentPolicy::ALL.iter().zip(weights) {
+            if slot < *weight {
+                return *policy;
+            }
+            slot -= weight;
+        }
+        AgentPolicy::Hoard
+    }
+}
+
+fn node(name: &str, behavior: Behavior, children: Vec<BehaviorNode>) -> BehaviorNode {
+    BehaviorNode(name.to_string(), behavior, children)
+}
+
+fn exchange(config: &SimulationConfig, seed: &str, market_rate_id: u64) -> Behavior {
+    Behavior::ExchangeAssets(MarketAssetsBehavior {
+        seed: seed.to_string(),
+        market_id: config.market_id,
+        market_rate_id,
+        amount: config.trade_amount,
+    })
+}
+
+fn policy_node(
+    config: &SimulationConfig,
+    policy: AgentPolicy,
+    seed: &str,
+    account: &str,
+) -> BehaviorNode {
+    match policy {
+        AgentPolicy::Buy => node("Buy", exchange(config, seed, config.buy_rate_id), vec![]),
+        AgentPolicy::Sell => node("Sell", exchange(config, seed, config.sell_rate_id), vec![]),
+        AgentPolicy::Mint => node(
+            "Mint",
+            Behavior::MintAsset(MintAssetBehavior {
+                seed: config.issuer_seed.clone(),
+                to: account.to_string(),
+                class_id: config.class_id,
+                asset_id: config.asset_id,
+                amount: config.mint_amount,
+            }),
+            vec![],
+        ),
+        // Buys until the target is held, then sits on it.
+        AgentPolicy::Hoard => node(
+            "Hoard",
+            Behavior::Selector(SelectorBehavior::default()),
+            vec![
+                node(
+                    "Holding enough",
+                    Behavior::CheckBalance(CheckBalanceBehavior {
+                        account: account.to_string(),
+                        class_id: config.class_id,
+                        asset_id: config.asset_id,
+                        op: AmountOp::GreaterEqualThan,
+                        amount: config.hoard_target,
+                    }),
+                    vec![],
+                ),
+                node("Buy", exchange(config, seed, config.buy_rate_id), vec![]),
+            ],
+        ),
+    }
+}
+
+/// Funds the agent's wallet once, then acts on its policy every
+/// `config.interval` seconds.
+pub fn agent_document(
+    config: &SimulationConfig,
+    policy: AgentPolicy,
+    seed: &str,
+    account: &str,
+) -> BehaviorDocument {
+    BehaviorDocument {
+        root: node(
+            "Agent",
+            Behavior::Sequencer(()),
+            vec![
+                node(
+                    "Fund wallet",
+                    Behavior::FundAccount(FundAccountBehavior {
+                        seed: config.issuer_seed.clone(),
+                        to: account.to_string(),
+                        amount: config.fund_amount,
+                    }),
+                    vec![],
+                ),
+                node(
+                    "Act",
+                    Behavior::Repeater(RepeaterBehavior::default()),
+                    vec![node(
+                        policy.label(),
+                        Behavior::Sequencer(()),
+                        vec![
+                            // A failed action still waits, so it isn't retried every frame.
+                            node(
+                                "Try",
+                                Behavior::Selector(SelectorBehavior::default()),
+                                vec![
+                                    policy_node(config, policy, seed, account),
+                                    node("Skip", Behavior::Debug(DebugBehavior::default()), vec![]),
+                                ],
+                            ),
+                            node(
+                                "Wait",
+                                Behavior::Delay(DelayBehavior {
+                                    duration: config.interval,
+                                }),
+                                vec![],
+                            ),
+                        ],
+                    )],
+                ),
+            ],
+        ),
+    }
+}
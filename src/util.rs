@@ -1,101 +1,5 @@
-use std::sync::Arc;
-
-use bevy::prelude::*;
 use bevy_inspector_egui::egui;
-use crossbeam::channel::{self, Receiver, Sender};
-use serde::{Deserialize, Serialize};
-use serde_json::json;
 use sugarfunge_api_types::primitives::{AssetId, Balance, ClassId};
-use tokio::runtime::Runtime;
-
-use crate::prelude::*;
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct RequestError {
-    pub message: serde_json::Value,
-    pub description: String,
-}
-
-fn endpoint(cmd: &str) -> String {
-    format!("http://127.0.0.1:4000/{}", cmd)
-}
-
-pub async fn req<'a, I, O>(cmd: &str, args: I) -> Result<O, RequestError>
-where
-    I: Serialize,
-    O: for<'de> Deserialize<'de>,
-{
-    let sf_res = reqwest::Client::new()
-        .post(endpoint(cmd))
-        .json(&args)
-        .send()
-        .await;
-
-    match sf_res {
-        Ok(res) => {
-            if let Err(err) = res.error_for_status_ref() {
-                match res.json::<RequestError>().await {
-                    Ok(err) => Err(err),
-                    Err(_) => Err(RequestError {
-                        message: json!(format!("{:#?}", err)),
-                        description: "Reqwest json error.".into(),
-                    }),
-                }
-            } else {
-                match res.json().await {
-                    Ok(res) => Ok(res),
-                    Err(err) => Err(RequestError {
-                        message: json!(format!("{:#?}", err)),
-                        description: "Reqwest json error.".into(),
-                    }),
-                }
-            }
-        }
-        Err(err) => Err(RequestError {
-            message: json!(format!("{:#?}", err)),
-            description: "Reqwest error.".into(),
-        }),
-    }
-}
-
-pub fn setup_in_out_channels<T: Send + 'static, E: Send + 'static>(mut commands: Commands) {
-    let (input_sender, input_receiver) = channel::unbounded::<T>();
-    commands.insert_resource(InputSender(input_sender));
-    commands.insert_resource(InputReceiver(input_receiver));
-    let (output_sender, output_receiver) = channel::unbounded::<Option<E>>();
-    commands.insert_resource(OutputSender(output_sender));
-    commands.insert_resource(OutputReceiver(output_receiver));
-}
-
-pub fn request_handler<
-    T: Request<E>,
-    E: Serialize + Send + Sync,
-    K: Send + Sync + 'static + for<'de> Deserialize<'de>,
->(
-    rt: Arc<Runtime>,
-    input_rx: Receiver<T>,
-    output_tx: Sender<Option<K>>,
-) {
-    if let Ok(request) = input_rx.try_recv() {
-        rt.spawn(async move {
-            let result: Result<K, RequestError>;
-            if let Some(input) = request.input() {
-                result = req(&request.endpoint(), input).await;
-            } else {
-                result = req(&request.endpoint(), ()).await;
-            }
-            match result {
-                Ok(value) => {
-                    output_tx.send(Some(value)).unwrap();
-                }
-                Err(err) => {
-                    output_tx.send(None).unwrap();
-                    error!("Request error: {:?}", err);
-                }
-            }
-        });
-    }
-}
 
 pub fn vec_u64_input_ui(ui: &mut egui::Ui, input: &mut Vec<u64>) {
     if ui.button("Add").clicked() {
@@ -165,3 +69,37 @@ pub fn transform_doublevec_of_u64_to_balance(input: Vec<Vec<u64>>) -> Vec<Vec<Ba
         })
         .collect()
 }
+
+pub fn transform_vec_of_class_id_to_u64(input: &[ClassId]) -> Vec<u64> {
+    input.iter().map(|class_id| u64::from(*class_id)).collect()
+}
+
+pub fn transform_doublevec_of_asset_id_to_u64(input: &[Vec<AssetId>]) -> Vec<Vec<u64>> {
+    input
+        .iter()
+        .map(|asset_ids| {
+            asset_ids
+                .iter()
+                .map(|asset_id| u64::from(*asset_id))
+                .collect()
+        })
+        .collect()
+}
+
+/// Fails on amounts too large for the `u64` form fields.
+pub fn transform_doublevec_of_balance_to_u64(
+    input: &[Vec<Balance>],
+) -> Result<Vec<Vec<u64>>, String> {
+    input
+        .iter()
+        .map(|amounts| {
+            amounts
+                .iter()
+                .map(|amount| {
+                    u64::try_from(u128::from(*amount))
+                        .map_err(|_| format!("Amount {} is too large", u128::from(*amount)))
+                })
+                .collect()
+        })
+        .collect()
+}